@@ -4,23 +4,33 @@ Generic benchmark comparison tool that can compare any two benchmark results.
 Supports both Python vs Mojo and branch vs branch comparisons.
 """
 
+import gzip
 import json
 import sys
 from typing import Tuple
 
+# Newest results file layout this script understands; files without a
+# schema_version predate it
+SCHEMA_VERSION = 2
+LEGACY_SCHEMA_VERSION = 1
+
 
 def load_results(filename: str) -> dict:
     """Load benchmark results from JSON file.
 
     Args:
-        filename: Path to JSON results file
+        filename: Path to JSON results file, plain or gzipped
 
     Returns:
         Dictionary with benchmark data
     """
     try:
-        with open(filename, "r") as f:
-            return json.load(f)
+        with open(filename, "rb") as f:
+            content = f.read()
+        # Runs that keep every sample are written gzipped
+        if content[:2] == b"\x1f\x8b":
+            content = gzip.decompress(content)
+        data = json.loads(content)
     except FileNotFoundError:
         print(f"Error: Results file '{filename}' not found", file=sys.stderr)
         sys.exit(1)
@@ -28,6 +38,62 @@ def load_results(filename: str) -> dict:
         print(f"Error: Invalid JSON in '{filename}': {e}", file=sys.stderr)
         sys.exit(1)
 
+    version = data.get("schema_version", LEGACY_SCHEMA_VERSION)
+    if version > SCHEMA_VERSION:
+        print(
+            f"Error: '{filename}' uses results schema {version}, but this"
+            f" script only reads up to {SCHEMA_VERSION}",
+            file=sys.stderr,
+        )
+        sys.exit(1)
+    data["results"] = normalize_results(data.get("results", {}), version)
+    return data
+
+
+def normalize_results(results: dict, version: int = LEGACY_SCHEMA_VERSION) -> dict:
+    """Flatten results into a name -> result map of measured benchmarks.
+
+    Versioned files are always categorized (category -> name -> result), as
+    the Rust harness writes them; unversioned ones may be that or the flat
+    layout of the Python and Mojo harnesses. Fills in whichever of
+    time_ns/time_ms is missing, and drops benchmarks that were skipped or
+    timed out.
+
+    Args:
+        results: The "results" object of a results file
+        version: The file's schema_version
+
+    Returns:
+        Dictionary mapping benchmark name to its result
+    """
+    flat = {}
+    for key, value in results.items():
+        if version > LEGACY_SCHEMA_VERSION:
+            flat.update(value)
+            continue
+        is_category = isinstance(value, dict) and all(
+            isinstance(bench, dict) for bench in value.values()
+        )
+        if is_category and value:
+            flat.update(value)
+        else:
+            flat[key] = value
+
+    measured = {}
+    for name, result in flat.items():
+        if result.get("skipped") or result.get("timed_out"):
+            continue
+        # Unmeasured results of any harness carry no timing at all
+        if not result.get("time_ns") and not result.get("time_ms"):
+            continue
+        if "time_ms" not in result and "time_ns" in result:
+            result["time_ms"] = result["time_ns"] / 1e6
+        if "time_ns" not in result and "time_ms" in result:
+            result["time_ns"] = result["time_ms"] * 1e6
+        result.setdefault("iterations", 0)
+        measured[name] = result
+    return measured
+
 
 def calculate_speedup(baseline_time: float, test_time: float) -> float:
     """Calculate speedup factor (how many times faster test is vs baseline).
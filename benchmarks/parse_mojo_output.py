@@ -85,6 +85,11 @@ def main():
         "results": results,
     }
 
+    # Set by run_comparison_with_rust.sh --container to the pinned image digest
+    container_image = os.environ.get("BENCH_CONTAINER_IMAGE")
+    if container_image:
+        json_data["environment"] = {"container_image": container_image}
+
     # Ensure directory exists
     os.makedirs(os.path.dirname(output_file), exist_ok=True)
 
@@ -1,15 +1,30 @@
 #!/bin/bash
 # Run complete benchmark comparison between Mojo and Rust regex implementations
+#
+# Usage: ./run_comparison_with_rust.sh [--container=IMAGE] [benchmark_type]
+#   --container=IMAGE: Run each harness inside IMAGE (docker or podman), pinned
+#                      by digest, CPU-set and memory-limited. Tune with
+#                      CONTAINER_ENGINE, CONTAINER_CPUSET (default: 2-3) and
+#                      CONTAINER_MEMORY (default: 4g).
 
 set -e  # Exit on error
 
+CONTAINER_IMAGE=""
+POSITIONAL=()
+for arg in "$@"; do
+    case "$arg" in
+        --container=*) CONTAINER_IMAGE="${arg#--container=}" ;;
+        *) POSITIONAL+=("$arg") ;;
+    esac
+done
+
 # Default benchmark type
-BENCHMARK_TYPE="${1:-bench_engine}"
+BENCHMARK_TYPE="${POSITIONAL[0]:-bench_engine}"
 
 # Validate argument
 if [[ "$BENCHMARK_TYPE" != "bench_engine" && "$BENCHMARK_TYPE" != "simd_focused_benchmark" ]]; then
     echo "Error: Invalid benchmark type '$BENCHMARK_TYPE'"
-    echo "Usage: $0 [bench_engine|simd_focused_benchmark]"
+    echo "Usage: $0 [--container=IMAGE] [bench_engine|simd_focused_benchmark]"
     echo ""
     echo "Available benchmark types:"
     echo "  bench_engine           - General regex engine benchmarks (default)"
@@ -37,8 +52,48 @@ else
     OUTPUT_PREFIX="rust_"
 fi
 
-# Check if Rust is installed
-if ! command -v cargo >/dev/null 2>&1; then
+# Resolve the container image to an immutable digest so every run (and every
+# team member) measures in exactly the same environment
+if [[ -n "$CONTAINER_IMAGE" ]]; then
+    CONTAINER_ENGINE="${CONTAINER_ENGINE:-$(command -v podman >/dev/null 2>&1 && echo podman || echo docker)}"
+    CONTAINER_CPUSET="${CONTAINER_CPUSET:-2-3}"
+    CONTAINER_MEMORY="${CONTAINER_MEMORY:-4g}"
+    if ! command -v "$CONTAINER_ENGINE" >/dev/null 2>&1; then
+        echo "Error: --container requires docker or podman"
+        exit 1
+    fi
+    if ! "$CONTAINER_ENGINE" image inspect "$CONTAINER_IMAGE" >/dev/null 2>&1; then
+        "$CONTAINER_ENGINE" pull "$CONTAINER_IMAGE"
+    fi
+    BENCH_CONTAINER_IMAGE=$("$CONTAINER_ENGINE" image inspect --format '{{index .RepoDigests 0}}' "$CONTAINER_IMAGE" 2>/dev/null || true)
+    if [[ -z "$BENCH_CONTAINER_IMAGE" ]]; then
+        echo "Error: image '$CONTAINER_IMAGE' has no registry digest; push it or pull it by digest"
+        exit 1
+    fi
+    # Picked up by the harnesses and parse_mojo_output.py into result metadata
+    export BENCH_CONTAINER_IMAGE
+    echo "Container: $BENCH_CONTAINER_IMAGE via $CONTAINER_ENGINE (cpus $CONTAINER_CPUSET, memory $CONTAINER_MEMORY)"
+    echo ""
+fi
+
+# Run a harness command from the project root, inside the pinned container
+# when --container was given
+run_harness() {
+    if [[ -n "$CONTAINER_IMAGE" ]]; then
+        "$CONTAINER_ENGINE" run --rm \
+            --cpuset-cpus "$CONTAINER_CPUSET" \
+            --memory "$CONTAINER_MEMORY" --memory-swap "$CONTAINER_MEMORY" \
+            -e BENCH_CONTAINER_IMAGE \
+            -e RUSTFLAGS \
+            -v "$PWD":/work -w /work \
+            "$BENCH_CONTAINER_IMAGE" bash -c "$1"
+    else
+        bash -c "$1"
+    fi
+}
+
+# Check if Rust is installed (the container image must provide it otherwise)
+if [[ -z "$CONTAINER_IMAGE" ]] && ! command -v cargo >/dev/null 2>&1; then
     echo "Error: Rust/Cargo not found. Please install Rust from https://rustup.rs/"
     exit 1
 fi
@@ -53,19 +108,15 @@ fi
 # Run Rust benchmarks
 echo "Step 1: Running Rust regex benchmarks..."
 echo "-----------------------------------------"
-cd benchmarks/rust
 
 # Build in release mode with optimizations
 echo "Building Rust benchmarks with maximum optimizations..."
 export RUSTFLAGS="-C target-cpu=native"
-cargo build --release --bin bench_engine
+run_harness "cd benchmarks/rust && cargo build --release --bin bench_engine"
 
 # Run the benchmark
 echo "Executing Rust benchmarks..."
-cargo run --release --bin bench_engine
-
-# Return to project root
-cd ../..
+run_harness "cd benchmarks/rust && cargo run --release --bin bench_engine"
 
 # Check if Rust results were generated
 if [[ ! -f "$RUST_RESULTS" ]]; then
@@ -79,7 +130,7 @@ echo ""
 echo "Step 2: Running Mojo regex benchmarks..."
 echo "----------------------------------------"
 # Capture Mojo output and parse it
-mojo run -I src "benchmarks/${BENCHMARK_TYPE}.mojo" | tee "benchmarks/results/${OUTPUT_PREFIX}mojo_output.txt" | python3 benchmarks/parse_mojo_output.py "$MOJO_RESULTS"
+run_harness "mojo run -I src benchmarks/${BENCHMARK_TYPE}.mojo" | tee "benchmarks/results/${OUTPUT_PREFIX}mojo_output.txt" | python3 benchmarks/parse_mojo_output.py "$MOJO_RESULTS"
 
 # Show engine usage summary if available
 MOJO_OUTPUT="benchmarks/results/${OUTPUT_PREFIX}mojo_output.txt"
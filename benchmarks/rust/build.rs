@@ -0,0 +1,46 @@
+//! Records how the harness was built, for `build_info::BuildInfo`
+
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    println!("cargo:rerun-if-env-changed=RUSTFLAGS");
+    println!("cargo:rerun-if-env-changed=CARGO_ENCODED_RUSTFLAGS");
+
+    let env = |name: &str| std::env::var(name).unwrap_or_default();
+    let rustc = Command::new(env("RUSTC"))
+        .arg("-V")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+
+    let mut features: Vec<String> = std::env::vars()
+        .filter_map(|(name, _)| Some(name.strip_prefix("CARGO_FEATURE_")?.to_lowercase().replace('_', "-")))
+        .collect();
+    features.sort();
+
+    let lock = std::fs::read_to_string("Cargo.lock").unwrap_or_default();
+    let locked_version = |package: &str| {
+        let header = format!("name = \"{}\"\n", package);
+        let rest = &lock[lock.find(&header)? + header.len()..];
+        let version = rest.strip_prefix("version = \"")?;
+        Some(version[..version.find('"')?].to_string())
+    };
+
+    let flags = env("CARGO_ENCODED_RUSTFLAGS").replace('\x1f', " ");
+    for (name, value) in [
+        ("RUSTC", rustc),
+        ("TARGET", env("TARGET")),
+        ("PROFILE", env("PROFILE")),
+        ("OPT_LEVEL", env("OPT_LEVEL")),
+        ("FEATURES", features.join(",")),
+        ("RUSTFLAGS", flags),
+        ("REGEX_VERSION", locked_version("regex").unwrap_or_default()),
+        ("REGEX_AUTOMATA_VERSION", locked_version("regex-automata").unwrap_or_default()),
+    ] {
+        println!("cargo:rustc-env=BENCH_BUILD_{}={}", name, value);
+    }
+}
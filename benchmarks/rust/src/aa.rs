@@ -0,0 +1,77 @@
+//! A/A testing: compare two runs of the same binary to measure the noise
+//! floor of the methodology itself
+//!
+//! Every delta between identical runs is spurious, so their spread is the
+//! smallest change a single comparison can credibly report.
+
+use crate::compare::Comparison;
+use crate::percentile;
+
+/// Spurious delta above which the methodology is reported as too noisy
+pub const DEFAULT_MAX_SPURIOUS_PCT: f64 = 5.0;
+
+/// Upper bounds (percent) of the buckets the spurious deltas are counted in
+pub const DELTA_BUCKETS_PCT: [f64; 4] = [1.0, 2.0, 5.0, 10.0];
+
+/// Distribution of the spurious deltas between two identical runs
+#[derive(Debug, Clone)]
+pub struct AaReport {
+    /// Benchmarks measured in both runs
+    pub compared: usize,
+    /// Median, 95th percentile and largest absolute delta, in percent
+    pub median_abs_pct: f64,
+    pub p95_abs_pct: f64,
+    pub max_abs_pct: f64,
+    /// Benchmarks whose |delta| falls in each of [`DELTA_BUCKETS_PCT`],
+    /// plus a final bucket for anything larger
+    pub buckets: [usize; DELTA_BUCKETS_PCT.len() + 1],
+    /// Deltas the Mann-Whitney test called significant (false positives)
+    pub significant: usize,
+    /// Benchmarks whose |delta| exceeds the threshold, largest first
+    pub over_threshold: Vec<(String, f64)>,
+    pub threshold_pct: f64,
+}
+
+impl AaReport {
+    /// Summarize the comparisons of two identical runs, or `None` if no
+    /// benchmark was measured in both
+    pub fn new(comparisons: &[Comparison], threshold_pct: f64) -> Option<Self> {
+        if comparisons.is_empty() {
+            return None;
+        }
+        let mut abs: Vec<f64> = comparisons.iter().map(|c| c.delta_pct.abs()).collect();
+        abs.sort_by(|a, b| a.total_cmp(b));
+
+        let mut buckets = [0; DELTA_BUCKETS_PCT.len() + 1];
+        for delta in &abs {
+            let bucket = DELTA_BUCKETS_PCT
+                .iter()
+                .position(|bound| delta <= bound)
+                .unwrap_or(DELTA_BUCKETS_PCT.len());
+            buckets[bucket] += 1;
+        }
+
+        let mut over_threshold: Vec<(String, f64)> = comparisons
+            .iter()
+            .filter(|c| c.delta_pct.abs() > threshold_pct)
+            .map(|c| (c.name.clone(), c.delta_pct))
+            .collect();
+        over_threshold.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+
+        Some(AaReport {
+            compared: comparisons.len(),
+            median_abs_pct: percentile(&abs, 50.0),
+            p95_abs_pct: percentile(&abs, 95.0),
+            max_abs_pct: abs[abs.len() - 1],
+            buckets,
+            significant: comparisons.iter().filter(|c| c.significant).count(),
+            over_threshold,
+            threshold_pct,
+        })
+    }
+
+    /// Whether every spurious delta stayed within the threshold
+    pub fn passed(&self) -> bool {
+        self.over_threshold.is_empty()
+    }
+}
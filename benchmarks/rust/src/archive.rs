@@ -0,0 +1,164 @@
+//! Timestamped results files, so a run never overwrites an earlier baseline
+//!
+//! Each run is written to `rust_results[-<label>]-<UTC time>.json`, and
+//! `rust_results[-<label>].json` is overwritten with a copy of the latest of
+//! them, so scripts reading the fixed name keep working and runs from two
+//! branches can coexist under different labels. The timestamped files are
+//! git-ignored; the checked-in `rust_results.json` stays a regular file.
+//!
+//! Runs that keep every sample can run to hundreds of MB, so they're written
+//! gzip-compressed (`.json.gz`). The latest copy keeps its `.json` name
+//! either way, so there's one latest file to read:
+//! [`read_to_string`] decompresses whatever it's given transparently.
+//!
+//! As with cargo-criterion's `--save-baseline`/`--baseline`, a run can also
+//! be saved as a named baseline, `baselines/<name>.json`, for later runs to
+//! compare against by name.
+
+use chrono::{DateTime, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// File name stem shared by every results file of this harness
+pub const RESULTS_STEM: &str = "rust_results";
+
+/// Filename-safe UTC timestamp format of archived runs
+const TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Appended to the file names of compressed results
+pub const GZIP_EXTENSION: &str = ".gz";
+
+/// First bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Subdirectory of the results directory holding named baselines
+pub const BASELINES_DIR: &str = "baselines";
+
+fn validate_name(what: &str, name: &str) -> Result<(), String> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c)) {
+        return Err(format!(
+            "{} {:?} must be non-empty and only use letters, digits, '.', '_' and '-'",
+            what, name
+        ));
+    }
+    Ok(())
+}
+
+/// Check a `--label` is safe to put in a file name
+pub fn validate_label(label: &str) -> Result<(), String> {
+    validate_name("--label", label)
+}
+
+/// Check a baseline name is safe to put in a file name
+pub fn validate_baseline_name(name: &str) -> Result<(), String> {
+    validate_name("baseline name", name)
+}
+
+/// Where the baseline `name` is saved under `results_dir`
+pub fn baseline_path(results_dir: &Path, name: &str, compressed: bool) -> PathBuf {
+    let extension = if compressed { GZIP_EXTENSION } else { "" };
+    results_dir.join(BASELINES_DIR).join(format!("{}.json{}", name, extension))
+}
+
+/// Save `content` as the baseline `name`, replacing an earlier baseline of
+/// that name whether or not it was compressed
+pub fn save_baseline(
+    results_dir: &Path,
+    name: &str,
+    content: &str,
+    compress: bool,
+) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    validate_baseline_name(name)?;
+    let dir = results_dir.join(BASELINES_DIR);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    let path = baseline_path(results_dir, name, compress);
+    write(&path, content, compress)?;
+    let stale = baseline_path(results_dir, name, !compress);
+    if stale.exists() {
+        std::fs::remove_file(&stale).map_err(|e| format!("failed to remove {}: {}", stale.display(), e))?;
+    }
+    Ok(path)
+}
+
+/// The results file `spec` refers to: an existing file as given, otherwise
+/// the saved baseline of that name
+pub fn resolve_baseline(results_dir: &Path, spec: &str) -> Result<PathBuf, String> {
+    if Path::new(spec).is_file() {
+        return Ok(PathBuf::from(spec));
+    }
+    if validate_baseline_name(spec).is_ok() {
+        if let Some(path) = [false, true]
+            .into_iter()
+            .map(|compressed| baseline_path(results_dir, spec, compressed))
+            .find(|path| path.is_file())
+        {
+            return Ok(path);
+        }
+    }
+    Err(format!(
+        "{:?} is neither a results file nor a baseline saved in {}",
+        spec,
+        results_dir.join(BASELINES_DIR).display()
+    ))
+}
+
+fn stem(label: Option<&str>) -> String {
+    match label {
+        Some(label) => format!("{}-{}", RESULTS_STEM, label),
+        None => RESULTS_STEM.to_string(),
+    }
+}
+
+/// Name of the file a run at `time` is written to
+pub fn timestamped_name(label: Option<&str>, time: DateTime<Utc>) -> String {
+    format!("{}-{}.json", stem(label), time.format(TIMESTAMP_FORMAT))
+}
+
+/// Name of the copy of the latest run with `label`
+pub fn latest_name(label: Option<&str>) -> String {
+    format!("{}.json", stem(label))
+}
+
+/// Replace `dir/latest` with a copy of `dir/target` (a sibling file name).
+/// A regular file rather than a symlink, so a checked-in latest file stays
+/// one; the copy is renamed into place, so readers never see it half written
+pub fn copy_latest(dir: &Path, target: &str, latest: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let latest_path = dir.join(latest);
+    let partial = dir.join(format!(".{}.partial", latest));
+    std::fs::copy(dir.join(target), &partial)
+        .map_err(|e| format!("failed to copy {} to {}: {}", target, partial.display(), e))?;
+    // Links left by runs before latest files were copies are replaced too
+    std::fs::rename(&partial, &latest_path)
+        .map_err(|e| format!("failed to replace {}: {}", latest_path.display(), e))?;
+    Ok(())
+}
+
+/// Write `content` to `path`, gzip-compressed if `compress`
+pub fn write(path: &Path, content: &str, compress: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let failed = |e: std::io::Error| format!("failed to write {}: {}", path.display(), e);
+    if !compress {
+        return Ok(std::fs::write(path, content).map_err(failed)?);
+    }
+    let file = std::fs::File::create(path).map_err(failed)?;
+    let mut encoder = GzEncoder::new(std::io::BufWriter::new(file), Compression::default());
+    encoder.write_all(content.as_bytes()).map_err(failed)?;
+    encoder.finish().and_then(|mut writer| writer.flush()).map_err(failed)?;
+    Ok(())
+}
+
+/// Contents of the text file at `path`, decompressed if it's gzipped
+/// (whatever its name)
+pub fn read_to_string(path: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(String::from_utf8(bytes).map_err(|e| format!("failed to read {}: {}", path, e))?);
+    }
+    let mut content = String::new();
+    GzDecoder::new(bytes.as_slice())
+        .read_to_string(&mut content)
+        .map_err(|e| format!("failed to decompress {}: {}", path, e))?;
+    Ok(content)
+}
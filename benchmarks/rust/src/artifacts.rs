@@ -0,0 +1,93 @@
+//! Finding the results recorded at a git commit, for `compare --against`
+//!
+//! Runs are looked up in the JSON Lines history and in results files, whose
+//! records and build info both carry the full commit hash (older results
+//! files only `git describe` of the checkout, which git resolves). Runs from
+//! a checkout with local changes never count as results of the commit.
+
+use crate::build_info::{git, BuildInfo};
+use crate::history;
+use crate::BenchmarkResults;
+use std::path::{Path, PathBuf};
+
+/// Results found for a commit, and where they came from
+#[derive(Debug)]
+pub struct Artifact {
+    /// The results file, or `history.jsonl:<line>` for a history record
+    pub source: String,
+    /// The results file, if the results came from one (for its signature)
+    pub path: Option<PathBuf>,
+    pub results: BenchmarkResults,
+}
+
+/// Full hash of the commit `git_ref` names, e.g. `origin/main`
+pub fn resolve_ref(git_ref: &str) -> Result<String, String> {
+    git(&["rev-parse", "--verify", "--quiet", &format!("{}^{{commit}}", git_ref)])
+        .ok_or_else(|| format!("{:?} doesn't name a commit in this checkout", git_ref))
+}
+
+/// Whether `build` was recorded from a clean checkout of commit `sha`
+pub fn built_at(build: &BuildInfo, sha: &str) -> bool {
+    if build.git_dirty {
+        return false;
+    }
+    if let Some(built) = &build.git_sha {
+        return built == sha;
+    }
+    // Recorded before the full hash was: let git resolve the describe output,
+    // as a tag can look just like an abbreviated hash
+    build
+        .git_describe
+        .as_deref()
+        .is_some_and(|describe| resolve_ref(describe).is_ok_and(|resolved| resolved == sha))
+}
+
+/// The latest results of `engine` recorded at commit `sha`, from the
+/// `history` file (if it exists) or a `.json`/`.json.gz` file directly in
+/// one of `dirs`
+pub fn find(
+    sha: &str,
+    engine: &str,
+    history: &Path,
+    dirs: &[PathBuf],
+) -> Result<Option<Artifact>, Box<dyn std::error::Error>> {
+    let mut found: Vec<Artifact> = Vec::new();
+    if history.is_file() {
+        let path = history.to_string_lossy();
+        for (number, record) in history::load(&path)?.into_iter().enumerate() {
+            if record.git_sha.as_deref() == Some(sha) && record.results.engine == engine {
+                found.push(Artifact {
+                    source: format!("{}:{}", path, number + 1),
+                    path: None,
+                    results: record.results,
+                });
+            }
+        }
+    }
+    for dir in dirs {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+        for path in paths {
+            let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+            if !path.is_file() || !(name.ends_with(".json") || name.ends_with(".json.gz")) {
+                continue;
+            }
+            // Weights, thresholds and other harnesses' files share the directory
+            let Ok(results) = BenchmarkResults::load(&path.to_string_lossy()) else {
+                continue;
+            };
+            if results.engine == engine && results.build_info.as_ref().is_some_and(|build| built_at(build, sha)) {
+                found.push(Artifact {
+                    source: path.display().to_string(),
+                    path: Some(path),
+                    results,
+                });
+            }
+        }
+    }
+    // A latest-run link duplicates its archived run; the first found wins
+    Ok(found.into_iter().rev().max_by(|a, b| a.results.timestamp.cmp(&b.results.timestamp)))
+}
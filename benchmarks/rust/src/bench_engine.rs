@@ -2,647 +2,1471 @@
 //! Rust regex benchmark program
 //! Mirrors benchmarks/bench_engine.py for direct performance comparison
 
+use mojo_regex_rust_bench::aa::{self, AaReport};
+use mojo_regex_rust_bench::archive;
+use mojo_regex_rust_bench::build_info::BuildInfo;
+use mojo_regex_rust_bench::clock::{self, Clock};
+use mojo_regex_rust_bench::compare::{self, compare, DEFAULT_ALPHA};
+use mojo_regex_rust_bench::criteria;
+use mojo_regex_rust_bench::criterion;
+use mojo_regex_rust_bench::environment::Environment;
+use mojo_regex_rust_bench::manifest::{self, Manifest};
+use mojo_regex_rust_bench::memory::CountingAllocator;
+use mojo_regex_rust_bench::metric;
+use mojo_regex_rust_bench::orchestrator;
+use mojo_regex_rust_bench::provenance::{self, Verification};
+use mojo_regex_rust_bench::sentinel::{self, Sentinel};
+use mojo_regex_rust_bench::suite::{self, register_benchmarks, Timer};
+use mojo_regex_rust_bench::summary::{self, format_ns, Grouping, SortKey, TableOptions};
 use mojo_regex_rust_bench::*;
-use regex::Regex;
-use std::collections::HashMap;
-use std::hint::black_box;
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let timer = BenchmarkTimer::new();
-    let mut all_results = HashMap::new();
-
-    println!("=== RUST REGEX BENCHMARKS ===");
-    println!();
-
-    // Pre-create test strings to avoid measurement overhead - scaled up to match Mojo benchmarks
-    let text_10000 = make_test_string(10000, "abcdefghijklmnopqrstuvwxyz");     // Increased from 1000 to 10000
-    let text_100000 = make_test_string(100000, "abcdefghijklmnopqrstuvwxyz");   // Increased from 10000 to 100000
-    let text_range_10000 = make_test_string(10000, "abc123XYZ");
-    let text_alternation_10000 = make_test_string(10000, "abcdefghijklmnopqrstuvwxyz");
-    let text_group_10000 = make_test_string(10000, "abcabcabc");
-
-    // Create complex text patterns - scaled up to match Mojo benchmarks
-    let base_text = make_test_string(2000, "abcdefghijklmnopqrstuvwxyz");  // Increased from 100 to 2000
-    let emails = " user@example.com more text john@test.org ";
-    let email_text = format!("{} {} {} {} {}", base_text, emails, base_text, emails, base_text);
-
-    let base_number_text = make_test_string(20000, "abc def ghi ");  // Increased from 500 to 20000
-    let number_text = format!("{} 123 price $456.78 quantity 789 {}", base_number_text, base_number_text);
-
-    // Pre-compile all regex patterns to avoid compilation overhead in benchmarks
-    let patterns = create_all_patterns()?;
-
-    // ===-----------------------------------------------------------------------===
-    // Basic Literal Matching Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Literal Matching Benchmarks ===");
-
-    run_benchmark(
-        &timer,
-        &mut all_results,
-        "literal_match_short",
-        &patterns.hello,
-        &format!("{} hello world {}", text_10000, text_10000),  // Updated variable names
-        2000,  // Increased from 100 to 2000
-        BenchType::Search
-    );
-
-    run_benchmark(
-        &timer,
-        &mut all_results,
-        "literal_match_long",
-        &patterns.hello,
-        &format!("{} hello world {}", text_100000, text_10000),  // Updated variable names
-        2000,  // Increased from 100 to 2000
-        BenchType::Search
-    );
-
-    // ===-----------------------------------------------------------------------===
-    // Wildcard and Quantifier Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Wildcard and Quantifier Benchmarks ===");
-
-    run_benchmark(&timer, &mut all_results, "wildcard_match_any", &patterns.dot_star, &text_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
-    run_benchmark(&timer, &mut all_results, "quantifier_zero_or_more", &patterns.a_star, &text_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
-    run_benchmark(&timer, &mut all_results, "quantifier_one_or_more", &patterns.a_plus, &text_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
-    run_benchmark(&timer, &mut all_results, "quantifier_zero_or_one", &patterns.a_question, &text_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
-
-    // ===-----------------------------------------------------------------------===
-    // Character Range Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Character Range Benchmarks ===");
-
-    run_benchmark(&timer, &mut all_results, "range_lowercase", &patterns.range_a_z, &text_range_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
-    run_benchmark(&timer, &mut all_results, "range_digits", &patterns.range_0_9, &text_range_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
-    run_benchmark(&timer, &mut all_results, "range_alphanumeric", &patterns.range_alnum, &text_range_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
-
-    // ===-----------------------------------------------------------------------===
-    // Predefined Character Class Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Predefined Character Class Benchmarks ===");
-
-    run_benchmark(&timer, &mut all_results, "predefined_digits", &patterns.predefined_digits, &text_range_10000, 1000, BenchType::Search);
-    run_benchmark(&timer, &mut all_results, "predefined_word", &patterns.predefined_word, &text_range_10000, 1000, BenchType::IsMatch);
-
-    // ===-----------------------------------------------------------------------===
-    // Anchor Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Anchor Benchmarks ===");
-
-    run_benchmark(&timer, &mut all_results, "anchor_start", &patterns.anchor_start, &text_10000, 2000, BenchType::IsMatch);  // Updated text size and iterations (100->2000)
-    run_benchmark(&timer, &mut all_results, "anchor_end", &patterns.anchor_end, &text_10000, 2000, BenchType::IsMatch);  // Updated text size and iterations (100->2000)
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+/// Counts allocations for the `max_allocations` criterion
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Disables colored output when set (https://no-color.org)
+const NO_COLOR_VAR: &str = "NO_COLOR";
+
+/// Where `--history` appends one line per run instead
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// Where `--format md` writes the markdown report
+const MARKDOWN_FILE: &str = "rust_results.md";
+
+/// Where `--format html` writes the HTML report
+const HTML_FILE: &str = "rust_results.html";
+
+/// Where `--format gbench` writes the Google Benchmark JSON
+const GBENCH_FILE: &str = "rust_results.gbench.json";
+
+/// Regressions and improvements `compare` leads with
+const TOP_CHANGES: usize = 5;
+
+/// Where `--format junit` writes the JUnit XML
+const JUNIT_FILE: &str = "rust_results.junit.xml";
+
+/// Where `--format criterion` writes its directory tree
+const CRITERION_DIR: &str = "criterion";
+
+/// Why `--history-db` and `trend` on a database are refused without the
+/// `sqlite` feature
+const SQLITE_REQUIRED: &str =
+    "--history-db and trend on a database need the harness built with --features sqlite";
+
+/// Results `trend` shows unless given `--limit`
+const DEFAULT_TREND_LIMIT: usize = 50;
+
+/// Default for `--deadline`: generous next to the ~1s a benchmark normally
+/// takes, short enough that a hung one doesn't stall the suite for long
+const DEFAULT_BENCHMARK_DEADLINE: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Single-call samples collected per benchmark in `--latency` mode
+const LATENCY_SAMPLES: usize = 10_000;
+
+/// Subcommand selected by the first positional argument
+#[derive(Debug, Default)]
+enum Command {
+    /// Run the benchmark suite (no subcommand)
+    #[default]
+    Run,
+    /// `compare <baseline.json> <candidate.json>`, or with `--against`
+    /// (no baseline) `compare [candidate.json]`, defaulting to the latest run
+    Compare { baseline: Option<String>, candidate: String },
+    /// `lint`: check the suite definitions without running them
+    Lint,
+    /// `keygen <key-file>`: create an Ed25519 key for `--sign-key`
+    Keygen { key_path: String },
+    /// `promote <results.json> <name>`: save an existing results file as a
+    /// named baseline
+    Promote { results: String, name: String },
+    /// `gate <results.json>`: fail if any benchmark broke its declared
+    /// criteria, or regressed against `--check-against`
+    Gate { results: String },
+    /// `export-defs`: print every benchmark definition as JSON
+    ExportDefs,
+    /// `manifest [other.json]...`: print what each benchmark runs as JSON,
+    /// or diff it against other harnesses' manifests or results files
+    Manifest { others: Vec<String> },
+    /// `report <results.json> [baseline.json]`: render a results file
+    /// (markdown, or with `--brief` a plain-text summary against `baseline`)
+    Report { results: String, baseline: Option<String> },
+    /// `orchestrate <engines.json> <merged.json>`: run every engine's harness
+    /// and merge their results
+    Orchestrate { config: String, output: String },
+    /// `merge <merged.json> <run.json>...`: combine repeated runs of one
+    /// engine into a median-of-runs result set
+    Merge { output: String, runs: Vec<String> },
+    /// `trend <history> <benchmark>`: one benchmark's latest results from
+    /// a `--history` JSON Lines file or a `--history-db` database (`sqlite`
+    /// feature), with their drift
+    Trend { history: String, benchmark: String },
+}
 
-    // ===-----------------------------------------------------------------------===
-    // Alternation Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Alternation Benchmarks ===");
+/// Extra report format written next to the JSON results
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// JSON only
+    #[default]
+    Json,
+    /// Also a GitHub-flavored markdown report
+    Markdown,
+    /// Also a self-contained HTML report with charts
+    Html,
+    /// Also Criterion's `estimates.json` tree, for `critcmp`
+    Criterion,
+    /// Also Google Benchmark JSON, for dashboards built around C++ suites
+    GoogleBenchmark,
+    /// Also JUnit XML, for CI test dashboards
+    Junit,
+    /// For `compare`: only a collapsible markdown comment for pull requests
+    PrComment,
+}
 
-    run_benchmark(&timer, &mut all_results, "alternation_simple", &patterns.alt_simple, &text_alternation_10000, 1000, BenchType::Search);  // Updated text size and iterations (50->1000)
-    run_benchmark(&timer, &mut all_results, "alternation_words", &patterns.alt_words, &text_alternation_10000, 1000, BenchType::Search);  // Updated text size and iterations (50->1000)
+/// Command-line options
+#[derive(Debug)]
+struct Options {
+    command: Command,
+    /// Also measure per-call latency distributions
+    latency: bool,
+    /// Keep every timing sample in the exported results
+    samples: bool,
+    /// Gzip the exported results (always done when keeping samples)
+    gzip: bool,
+    /// Skip benchmarks that would start after this much suite time
+    time_budget: Option<std::time::Duration>,
+    /// Abandon a single benchmark still running after this long
+    deadline: Option<std::time::Duration>,
+    /// Re-run benchmarks whose coefficient of variation exceeds this (percent)
+    max_cv_pct: f64,
+    /// Maximum extra rounds per noisy benchmark (0 disables re-running)
+    reruns: u32,
+    /// Full rounds per benchmark, for a round-to-round stability score
+    rounds: u32,
+    /// Flag the run unstable if the sentinel drifts more than this (percent)
+    max_drift_pct: f64,
+    /// Run exactly this many samples per benchmark instead of a time target
+    iterations: Option<u64>,
+    /// Take at least this many samples even past the time target
+    min_iterations: u64,
+    /// Measurement clock (see `clock::CLOCK_NAMES`)
+    clock: String,
+    /// Extra metrics recorded alongside the clock (see `metric::METRIC_NAMES`)
+    metrics: Option<String>,
+    /// Order and grouping of the results table
+    table: TableOptions,
+    /// Report format besides the JSON results
+    format: Format,
+    /// Baseline name the Criterion tree is written under
+    criterion_baseline: String,
+    /// Results (a file or saved baseline name) the table shows deltas
+    /// against, and `--format junit` checks for regressions against
+    baseline: Option<String>,
+    /// Also save the run as this named baseline
+    save_baseline: Option<String>,
+    /// Like `baseline`, but regressions also make the run (or `gate`) exit
+    /// non-zero
+    check_against: Option<String>,
+    /// A benchmark slower by more than this (percent) has regressed...
+    max_regression_pct: f64,
+    /// ...unless this file sets its own threshold
+    thresholds: Option<String>,
+    /// Append to the history file instead of overwriting the results file
+    history: bool,
+    /// Directory the results, history and reports are written to
+    output_dir: PathBuf,
+    /// Tag in the results file names, so runs of different branches
+    /// coexist (see `archive`)
+    label: Option<String>,
+    /// Also record the run in this SQLite database (`sqlite` feature)
+    history_db: Option<String>,
+    /// `trend`: show at most this many results...
+    limit: usize,
+    /// ...of this engine only
+    engine: Option<String>,
+    /// `trend`: also write an SVG chart of the results to `plots/`
+    plot: bool,
+    /// `report` prints the plain-text brief instead of markdown
+    brief: bool,
+    /// Run the suite twice and report the spurious deltas instead of
+    /// exporting results
+    aa_test: bool,
+    /// Category weights file for the weighted `compare` score
+    weights: Option<String>,
+    /// `compare` against the results recorded at this git ref...
+    against: Option<String>,
+    /// ...looking in this directory too, besides the results directory
+    artifacts_dir: Option<PathBuf>,
+    /// `compare` runs from different machines, scaled by their times on
+    /// this benchmark
+    normalize_by: Option<String>,
+    /// Sign the exported results with this key file...
+    sign_key: Option<String>,
+    /// ...as this identity
+    signer: Option<String>,
+}
 
-    // ===-----------------------------------------------------------------------===
-    // Group Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Group Benchmarks ===");
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            command: Command::default(),
+            latency: false,
+            samples: false,
+            gzip: false,
+            time_budget: None,
+            deadline: Some(DEFAULT_BENCHMARK_DEADLINE),
+            max_cv_pct: 5.0,
+            reruns: 2,
+            rounds: 1,
+            max_drift_pct: sentinel::DEFAULT_MAX_DRIFT_PCT,
+            iterations: None,
+            min_iterations: 10,
+            clock: "wall".to_string(),
+            metrics: None,
+            table: TableOptions {
+                color: std::io::stdout().is_terminal() && std::env::var_os(NO_COLOR_VAR).is_none(),
+                ..TableOptions::default()
+            },
+            format: Format::default(),
+            criterion_baseline: criterion::DEFAULT_BASELINE.to_string(),
+            baseline: None,
+            save_baseline: None,
+            check_against: None,
+            max_regression_pct: compare::DEFAULT_MAX_REGRESSION_PCT,
+            thresholds: None,
+            history: false,
+            output_dir: results_dir(),
+            label: None,
+            history_db: None,
+            limit: DEFAULT_TREND_LIMIT,
+            engine: None,
+            plot: false,
+            brief: false,
+            aa_test: false,
+            weights: None,
+            against: None,
+            artifacts_dir: None,
+            normalize_by: None,
+            sign_key: None,
+            signer: None,
+        }
+    }
+}
 
-    run_benchmark(&timer, &mut all_results, "group_quantified", &patterns.group_quantified, &text_group_10000, 1000, BenchType::Search);  // Updated text size and iterations (50->1000)
-    run_benchmark(&timer, &mut all_results, "group_alternation", &patterns.group_alternation, &text_group_10000, 1000, BenchType::Search);  // Updated text size and iterations (50->1000)
+impl Options {
+    fn parse() -> Result<Self, String> {
+        let mut options = Options::default();
+        let mut positional = Vec::new();
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            // Accept both `--flag=value` and `--flag value`
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) if arg.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("{} requires a value", flag))
+            };
+            match flag.as_str() {
+                "--latency" => options.latency = true,
+                "--samples" => options.samples = true,
+                "--gzip" => options.gzip = true,
+                "--history" => options.history = true,
+                "--aa-test" => options.aa_test = true,
+                "--brief" => options.brief = true,
+                "--plot" => options.plot = true,
+                "--time-budget" => {
+                    let budget = value()?
+                        .parse()
+                        .ok()
+                        .filter(|seconds: &f64| *seconds > 0.0)
+                        .and_then(|seconds| std::time::Duration::try_from_secs_f64(seconds).ok())
+                        .ok_or_else(|| "--time-budget expects seconds".to_string())?;
+                    options.time_budget = Some(budget);
+                }
+                "--deadline" => {
+                    let deadline = value()?
+                        .parse()
+                        .ok()
+                        .and_then(|seconds| std::time::Duration::try_from_secs_f64(seconds).ok())
+                        .ok_or_else(|| "--deadline expects seconds".to_string())?;
+                    // 0 runs benchmarks inline, without a watchdog
+                    options.deadline = Some(deadline).filter(|deadline| !deadline.is_zero());
+                }
+                "--max-cv" => {
+                    options.max_cv_pct = value()?
+                        .trim_end_matches('%')
+                        .parse()
+                        .map_err(|_| "--max-cv expects a percentage".to_string())?;
+                }
+                "--reruns" => {
+                    options.reruns = value()?
+                        .parse()
+                        .map_err(|_| "--reruns expects a count".to_string())?;
+                }
+                "--rounds" => {
+                    options.rounds = value()?
+                        .parse()
+                        .map_err(|_| "--rounds expects a count".to_string())?;
+                }
+                "--max-drift" => {
+                    options.max_drift_pct = value()?
+                        .trim_end_matches('%')
+                        .parse()
+                        .map_err(|_| "--max-drift expects a percentage".to_string())?;
+                }
+                "--iterations" => {
+                    let iterations = value()?
+                        .parse()
+                        .map_err(|_| "--iterations expects a count".to_string())?;
+                    options.iterations = Some(iterations);
+                }
+                "--min-iterations" => {
+                    options.min_iterations = value()?
+                        .parse()
+                        .map_err(|_| "--min-iterations expects a count".to_string())?;
+                }
+                "--clock" => options.clock = value()?,
+                "--metrics" => options.metrics = Some(value()?),
+                "--criterion-baseline" => options.criterion_baseline = value()?,
+                "--output" => options.output_dir = PathBuf::from(value()?),
+                "--history-db" => options.history_db = Some(value()?),
+                "--label" => {
+                    let label = value()?;
+                    archive::validate_label(&label)?;
+                    options.label = Some(label);
+                }
+                "--engine" => options.engine = Some(value()?),
+                "--limit" => {
+                    options.limit = value()?
+                        .parse()
+                        .map_err(|_| "--limit expects a count".to_string())?;
+                }
+                "--sort" => {
+                    options.table.sort = match value()?.as_str() {
+                        "category" => SortKey::Category,
+                        "time" => SortKey::Time,
+                        "throughput" => SortKey::Throughput,
+                        "name" => SortKey::Name,
+                        other => {
+                            return Err(format!(
+                                "unknown --sort {:?} (expected category, time, throughput or name)",
+                                other
+                            ))
+                        }
+                    };
+                }
+                "--group" => {
+                    options.table.group = match value()?.as_str() {
+                        "category" => Grouping::Category,
+                        "family" => Grouping::Family,
+                        "none" => Grouping::None,
+                        other => {
+                            return Err(format!("unknown --group {:?} (expected category, family or none)", other))
+                        }
+                    };
+                }
+                "--format" => {
+                    options.format = match value()?.as_str() {
+                        "json" => Format::Json,
+                        "md" | "markdown" => Format::Markdown,
+                        "html" => Format::Html,
+                        "criterion" => Format::Criterion,
+                        "gbench" => Format::GoogleBenchmark,
+                        "junit" => Format::Junit,
+                        "pr-comment" => Format::PrComment,
+                        other => {
+                            return Err(format!(
+                                "unknown --format {:?} (expected json, md, html, criterion, gbench, junit or pr-comment)",
+                                other
+                            ))
+                        }
+                    };
+                }
+                "--baseline" => options.baseline = Some(value()?),
+                "--save-baseline" => {
+                    let name = value()?;
+                    archive::validate_baseline_name(&name)?;
+                    options.save_baseline = Some(name);
+                }
+                "--check-against" => options.check_against = Some(value()?),
+                "--thresholds" => options.thresholds = Some(value()?),
+                "--max-regression" => {
+                    options.max_regression_pct = value()?
+                        .trim_end_matches('%')
+                        .parse()
+                        .map_err(|_| "--max-regression expects a percentage".to_string())?;
+                }
+                "--weights" => options.weights = Some(value()?),
+                "--against" => options.against = Some(value()?),
+                "--artifacts-dir" => options.artifacts_dir = Some(PathBuf::from(value()?)),
+                "--normalize-by" => options.normalize_by = Some(value()?),
+                "--sign-key" => options.sign_key = Some(value()?),
+                "--signer" => options.signer = Some(value()?),
+                _ if arg.starts_with("--") => return Err(format!("unknown argument: {}", arg)),
+                _ => positional.push(arg),
+            }
+        }
+        options.command = match positional.as_slice() {
+            [] => Command::Run,
+            [cmd] if cmd == "lint" => Command::Lint,
+            [cmd] if cmd == "export-defs" => Command::ExportDefs,
+            [cmd, others @ ..] if cmd == "manifest" => Command::Manifest { others: others.to_vec() },
+            [cmd, key_path] if cmd == "keygen" => Command::Keygen { key_path: key_path.clone() },
+            [cmd, results] if cmd == "gate" => Command::Gate { results: results.clone() },
+            [cmd, results, name] if cmd == "promote" => Command::Promote {
+                results: results.clone(),
+                name: name.clone(),
+            },
+            [cmd, results] if cmd == "report" => Command::Report {
+                results: results.clone(),
+                baseline: None,
+            },
+            [cmd, results, baseline] if cmd == "report" => Command::Report {
+                results: results.clone(),
+                baseline: Some(baseline.clone()),
+            },
+            [cmd, output, runs @ ..] if cmd == "merge" && runs.len() >= 2 => Command::Merge {
+                output: output.clone(),
+                runs: runs.to_vec(),
+            },
+            [cmd, config, output] if cmd == "orchestrate" => Command::Orchestrate {
+                config: config.clone(),
+                output: output.clone(),
+            },
+            [cmd, history, benchmark] if cmd == "trend" => Command::Trend {
+                history: history.clone(),
+                benchmark: benchmark.clone(),
+            },
+            [cmd] if cmd == "compare" && options.against.is_some() => Command::Compare {
+                baseline: None,
+                candidate: latest_results(&options.output_dir, options.label.as_deref()).display().to_string(),
+            },
+            [cmd, candidate] if cmd == "compare" && options.against.is_some() => Command::Compare {
+                baseline: None,
+                candidate: candidate.clone(),
+            },
+            [cmd, _, _] if cmd == "compare" && options.against.is_some() => {
+                return Err("--against takes the place of the baseline file".to_string())
+            }
+            [cmd, baseline, candidate] if cmd == "compare" => Command::Compare {
+                baseline: Some(baseline.clone()),
+                candidate: candidate.clone(),
+            },
+            _ => return Err(format!("unknown command: {}", positional.join(" "))),
+        };
+        if options.iterations.is_some() && options.time_budget.is_some() {
+            // A wall-clock budget would skip a varying set of benchmarks
+            return Err("--iterations and --time-budget are mutually exclusive".to_string());
+        }
+        if options.sign_key.is_some() != options.signer.is_some() {
+            return Err("--sign-key and --signer must be given together".to_string());
+        }
+        if options.history && options.sign_key.is_some() {
+            // Signatures cover a whole file, which history keeps appending to
+            return Err("--history can't be combined with --sign-key".to_string());
+        }
+        if options.history && options.label.is_some() {
+            return Err("--label names the results file, which --history doesn't write".to_string());
+        }
+        let trend_db = matches!(&options.command, Command::Trend { history, .. } if !is_jsonl(history));
+        if !cfg!(feature = "sqlite") && (options.history_db.is_some() || trend_db) {
+            return Err(SQLITE_REQUIRED.to_string());
+        }
+        if (options.baseline.is_some() || options.save_baseline.is_some()) && !matches!(options.command, Command::Run) {
+            return Err("--baseline and --save-baseline only apply to a suite run".to_string());
+        }
+        if options.check_against.is_some() {
+            if !matches!(options.command, Command::Run | Command::Gate { .. }) {
+                return Err("--check-against only applies to a suite run or gate".to_string());
+            }
+            if options.baseline.is_some() {
+                return Err("--check-against already serves as --baseline".to_string());
+            }
+        }
+        if (options.against.is_some() || options.artifacts_dir.is_some() || options.normalize_by.is_some())
+            && !matches!(options.command, Command::Compare { .. })
+        {
+            return Err("--against, --artifacts-dir and --normalize-by only apply to compare".to_string());
+        }
+        if options.plot && !matches!(options.command, Command::Trend { .. }) {
+            return Err("--plot only applies to trend".to_string());
+        }
+        if options.format == Format::PrComment && !matches!(options.command, Command::Compare { .. }) {
+            return Err("--format=pr-comment only applies to compare".to_string());
+        }
+        if matches!(options.command, Command::Report { baseline: Some(_), .. }) && !options.brief {
+            return Err("report only takes a baseline with --brief".to_string());
+        }
+        if options.aa_test {
+            if !matches!(options.command, Command::Run) {
+                return Err("--aa-test only applies to a suite run".to_string());
+            }
+            if options.history
+                || options.history_db.is_some()
+                || options.sign_key.is_some()
+                || options.save_baseline.is_some()
+                || options.format != Format::Json
+            {
+                return Err(
+                    "--aa-test exports nothing, so it can't be combined with --history, --history-db, --sign-key, --save-baseline or --format"
+                        .to_string(),
+                );
+            }
+            // The significance of each spurious delta needs both runs' samples
+            options.samples = true;
+        }
+        Ok(options)
+    }
+}
 
-    // ===-----------------------------------------------------------------------===
-    // NEW: Optimization Showcase Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Optimization Showcase Benchmarks ===");
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let options = Options::parse()?;
+    match &options.command {
+        Command::Run if options.aa_test => run_aa_test(&options),
+        Command::Run => run_suite(&options),
+        Command::Compare { baseline, candidate } => run_compare(baseline.as_deref(), candidate, &options),
+        Command::Lint => run_lint(),
+        Command::ExportDefs => {
+            println!("{}", serde_json::to_string_pretty(&suite::export_definitions())?);
+            Ok(())
+        }
+        Command::Manifest { others } => run_manifest(others),
+        Command::Keygen { key_path } => {
+            let public_key = provenance::generate_key(key_path)?;
+            println!("Secret key written to {}\nPublic key: {}", key_path, public_key);
+            Ok(())
+        }
+        Command::Orchestrate { config, output } => run_orchestrate(config, output),
+        Command::Merge { output, runs } => run_merge(output, runs),
+        Command::Gate { results } => run_gate(results, &options),
+        Command::Promote { results, name } => {
+            let content = archive::read_to_string(results)?;
+            let compressed = results.ends_with(archive::GZIP_EXTENSION);
+            let path = archive::save_baseline(&options.output_dir, name, &content, compressed)?;
+            println!("Baseline {:?} saved to {}", name, path.display());
+            Ok(())
+        }
+        Command::Trend { history, benchmark } => {
+            run_trend(history, benchmark, &options)
+        }
+        Command::Report { results, baseline } => {
+            let results = BenchmarkResults::load(results)?;
+            if options.brief {
+                let baseline = baseline.as_deref().map(BenchmarkResults::load).transpose()?;
+                print!("{}", report::brief(&results, baseline.as_ref()));
+            } else {
+                print!("{}", report::markdown(&results));
+            }
+            Ok(())
+        }
+    }
+}
 
-    // Test case 1: Large alternation (8 branches) - benefits from increased branch limit (3→8)
-    let fruit_text = "I love eating apple and banana and cherry and date and elderberry and fig and grape with honey";
-    run_benchmark(&timer, &mut all_results, "large_8_alternations", &patterns.large_alternation, fruit_text, 1000, BenchType::Search);
+/// Print the suite's manifest, or diff it against each of `others`,
+/// exiting non-zero if any differs
+fn run_manifest(others: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let ours = Manifest::from_definitions("rust", &suite::all_benchmarks());
+    if others.is_empty() {
+        println!("{}", serde_json::to_string_pretty(&ours)?);
+        return Ok(());
+    }
+    let mut differ = false;
+    for path in others {
+        let theirs = Manifest::load(path)?;
+        let differences = manifest::diff(&ours, &theirs);
+        println!("=== {} vs {} ({}): {} differences ===", ours.engine, theirs.engine, path, differences.len());
+        for difference in &differences {
+            println!("  {}", difference);
+        }
+        differ |= !differences.is_empty();
+    }
+    if differ {
+        std::process::exit(1);
+    }
+    Ok(())
+}
 
-    // Test case 2: Deeply nested groups (depth 4) - benefits from increased depth tolerance (3→4)
-    let nested_text = "Testing deep nested patterns with abcdefgh characters";
-    run_benchmark(&timer, &mut all_results, "deep_nested_groups_depth4", &patterns.deep_nested, nested_text, 1000, BenchType::Search);
+/// Evaluate the criteria recorded in a results file, exiting non-zero if
+/// any benchmark broke them
+fn run_gate(path: &str, options: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    let results = BenchmarkResults::load(path)?;
+    let declared = results.flat().values().filter(|result| result.criteria.is_some()).count();
+    let violations = criteria::evaluate(&results);
+    print_violations(&violations);
+    println!("{} benchmarks declare criteria: {} violations", declared, violations.len());
+    let mut regressed = false;
+    if let Some(baseline) = &options.check_against {
+        let baseline = load_baseline(&options.output_dir, baseline)?;
+        let regressions = find_regressions(&baseline, &results, options)?;
+        print_regressions(&regressions);
+        regressed = !regressions.is_empty();
+    }
+    if !violations.is_empty() || regressed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
 
-    // Test case 3: Literal-heavy alternation - benefits from 80% threshold detection
-    let user_text = "Login attempts: user123 failed, admin456 success, guest789 failed, root000 success, test111 pending, demo222 active, sample333 inactive, client444 locked";
-    run_benchmark(&timer, &mut all_results, "literal_heavy_alternation", &patterns.literal_heavy, user_text, 1000, BenchType::Search);
+/// Load the results file or saved baseline `spec` names
+fn load_baseline(results_dir: &Path, spec: &str) -> Result<BenchmarkResults, Box<dyn std::error::Error>> {
+    let path = archive::resolve_baseline(results_dir, spec)?;
+    BenchmarkResults::load(&path.to_string_lossy())
+}
 
-    // Test case 4: Complex group with 5 children - benefits from increased children limit (3→5)
-    let mixed_text = "Found: hello123ab, world456cd, test789ef, demo012gh, sample345ij in the data";
-    run_benchmark(&timer, &mut all_results, "complex_group_5_children", &patterns.complex_group, mixed_text, 1000, BenchType::Search);
+/// Benchmarks of `results` slower than `baseline` by more than
+/// `--max-regression` or their `--thresholds` entry
+fn find_regressions(
+    baseline: &BenchmarkResults,
+    results: &BenchmarkResults,
+    options: &Options,
+) -> Result<Vec<compare::Regression>, Box<dyn std::error::Error>> {
+    let thresholds = options.thresholds.as_deref().map(compare::load_thresholds).transpose()?;
+    let comparisons = compare(baseline, results, DEFAULT_ALPHA);
+    Ok(compare::regressions(&comparisons, options.max_regression_pct, &thresholds.unwrap_or_default()))
+}
 
-    // ===-----------------------------------------------------------------------===
-    // Global Matching Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Global Matching Benchmarks ===");
+fn print_regressions(regressions: &[compare::Regression]) {
+    if regressions.is_empty() {
+        println!("No regressions against the baseline");
+        return;
+    }
+    println!("\n=== Regressions ===");
+    for regression in regressions {
+        println!("  {}", regression);
+    }
+}
 
-    run_benchmark(&timer, &mut all_results, "match_all_simple", &patterns.a, &text_10000, 200, BenchType::FindAll);  // Updated text size and iterations (10->200)
-    run_benchmark(&timer, &mut all_results, "match_all_pattern", &patterns.range_a_z, &text_10000, 200, BenchType::FindAll);  // Updated text size and iterations (10->200)
+/// The latest results file in `dir` for runs with `label`, gzipped or not
+fn latest_results(dir: &Path, label: Option<&str>) -> PathBuf {
+    dir.join(archive::latest_name(label))
+}
 
-    // ===-----------------------------------------------------------------------===
-    // Complex Pattern Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Complex Pattern Benchmarks ===");
+/// The results recorded at `git_ref` for `engine`, from the history or the
+/// results files in the output directory, its saved baselines and
+/// `--artifacts-dir`
+fn find_against(
+    git_ref: &str,
+    engine: &str,
+    options: &Options,
+) -> Result<artifacts::Artifact, Box<dyn std::error::Error>> {
+    let sha = artifacts::resolve_ref(git_ref)?;
+    let mut dirs: Vec<PathBuf> = options.artifacts_dir.iter().cloned().collect();
+    dirs.push(options.output_dir.clone());
+    dirs.push(options.output_dir.join(archive::BASELINES_DIR));
+    let history = options.output_dir.join(HISTORY_FILE);
+    artifacts::find(&sha, engine, &history, &dirs)?.ok_or_else(|| {
+        let searched: Vec<String> = dirs.iter().map(|dir| dir.display().to_string()).collect();
+        format!(
+            "no {} results recorded at {} ({}) in {} or {}",
+            engine,
+            git_ref,
+            &sha[..sha.len().min(10)],
+            history.display(),
+            searched.join(", ")
+        )
+        .into()
+    })
+}
 
-    run_benchmark(&timer, &mut all_results, "complex_email_extraction", &patterns.email, &email_text, 40, BenchType::FindAll);  // Increased from 2 to 40
-    run_benchmark(&timer, &mut all_results, "complex_number_extraction", &patterns.number, &number_text, 500, BenchType::FindAll);  // Increased from 25 to 500
+/// Compare two results files (the baseline given, or the one recorded at
+/// `--against`) and print per-benchmark deltas with their Mann-Whitney
+/// significance (requires runs made with `--samples`), plus the geomean and,
+/// given a weights file, the weighted overall score
+fn run_compare(baseline: Option<&str>, candidate: &str, options: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    let candidate_path = candidate;
+    let candidate = BenchmarkResults::load(candidate_path)?;
+    let (baseline_path, mut baseline) = match (baseline, &options.against) {
+        (Some(path), _) => (Some(PathBuf::from(path)), BenchmarkResults::load(path)?),
+        (None, Some(git_ref)) => {
+            let found = find_against(git_ref, &candidate.engine, options)?;
+            if options.format != Format::PrComment {
+                println!("Baseline for {}: {}", git_ref, found.source);
+            }
+            (found.path, found.results)
+        }
+        (None, None) => unreachable!("parse() requires a baseline file or --against"),
+    };
+    let calibration = options
+        .normalize_by
+        .as_deref()
+        .map(|name| compare::calibrate(&mut baseline, &candidate, name).map(|factor| (name, factor)))
+        .transpose()?;
+    if options.format == Format::PrComment {
+        print!("{}", report::pr_comment(&baseline, &candidate));
+        return Ok(());
+    }
 
-    // ===-----------------------------------------------------------------------===
-    // SIMD-Optimized Character Filtering Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== SIMD-Optimized Character Filtering Benchmarks ===");
+    for path in baseline_path.iter().map(|path| path.to_string_lossy()).chain([candidate_path.into()]) {
+        match provenance::verify_file(&path) {
+            Verification::Unsigned => println!("{}: unsigned", path),
+            Verification::Valid { signer, public_key } => {
+                println!("{}: signed by {} (key {})", path, signer, public_key)
+            }
+            Verification::Invalid { signer, reason } => {
+                println!("⚠ {}: INVALID signature claiming {}: {}", path, signer, reason)
+            }
+        }
+    }
+    println!();
 
-    let large_mixed_text = make_mixed_content_text(100000);  // Increased from 10000 to 100000
-    let xlarge_mixed_text = make_mixed_content_text(500000);  // Increased from 50000 to 500000
+    for (side, results) in [("baseline", &baseline), ("candidate", &candidate)] {
+        let build = results.build_info.as_ref().map_or("not recorded".to_string(), BuildInfo::summary);
+        println!("{} build: {}", side, build);
+    }
+    if let (Some(base), Some(cand)) = (&baseline.build_info, &candidate.build_info) {
+        if (&base.rustc, &base.opt_level, &base.rustflags) != (&cand.rustc, &cand.opt_level, &cand.rustflags) {
+            println!("⚠ built with different compilers or flags: deltas include the toolchain change");
+        }
+    }
+    if let Some((name, factor)) = calibration {
+        println!(
+            "Baseline times scaled by {:.3}x to the candidate's machine by {}; deltas are only loosely comparable",
+            factor, name
+        );
+    }
+    let mismatches = compare::input_mismatches(&baseline, &candidate);
+    if !mismatches.is_empty() {
+        println!("⚠ {} benchmarks ran on different inputs; their deltas aren't comparable:", mismatches.len());
+        for mismatch in &mismatches {
+            println!("  {}", mismatch);
+        }
+    }
+    println!();
+    let weights = options.weights.as_deref().map(compare::load_weights).transpose()?;
+    let comparisons = compare(&baseline, &candidate, DEFAULT_ALPHA);
+
+    let (regressions, improvements) = compare::top_changes(&comparisons, TOP_CHANGES);
+    print_top_changes("regressions", TOP_CHANGES, &regressions);
+    print_top_changes("improvements", TOP_CHANGES, &improvements);
+
+    println!("=== {} -> {} (alpha = {}) ===", baseline.engine, candidate.engine, DEFAULT_ALPHA);
+    println!("| name                      |  baseline (ns) | candidate (ns) |    delta |  p-value |     |");
+    println!("|---------------------------|----------------|----------------|----------|----------|-----|");
+    for c in &comparisons {
+        let p_value = c.p_value.map_or("-".to_string(), |p| format!("{:.4}", p));
+        println!(
+            "| {:<25} | {:>14.2} | {:>14.2} | {:>+7.1}% | {:>8} | {:<3} |",
+            c.name,
+            c.baseline_ns,
+            c.candidate_ns,
+            c.delta_pct,
+            p_value,
+            // Without samples, disjoint confidence intervals are the next best
+            match (c.significant, c.beyond_noise()) {
+                (true, _) => "***",
+                (false, true) if c.p_value.is_none() && c.ci_overlap.is_some() => "ci",
+                _ => "",
+            }
+        );
+    }
 
-    run_benchmark(&timer, &mut all_results, "simd_alphanumeric_large", &patterns.range_alnum, &large_mixed_text, 200, BenchType::IsMatch);  // Increased from 10 to 200
-    run_benchmark(&timer, &mut all_results, "simd_alphanumeric_xlarge", &patterns.range_alnum, &xlarge_mixed_text, 200, BenchType::IsMatch);  // Increased from 10 to 200
-    run_benchmark(&timer, &mut all_results, "simd_negated_alphanumeric", &patterns.negated_alnum, &large_mixed_text, 200, BenchType::IsMatch);  // Increased from 10 to 200
-    run_benchmark(&timer, &mut all_results, "simd_multi_char_class", &patterns.multi_char_class, &large_mixed_text, 200, BenchType::IsMatch);  // Increased from 10 to 200
+    let beyond_noise = comparisons.iter().filter(|c| c.beyond_noise()).count();
+    let untested = comparisons.iter().filter(|c| c.p_value.is_none()).count();
+    let unknown = comparisons.iter().filter(|c| c.p_value.is_none() && c.ci_overlap.is_none()).count();
+    println!(
+        "\n{} of {} deltas beyond noise (*** significant, ci: disjoint 95% confidence intervals)",
+        beyond_noise - unknown,
+        comparisons.len()
+    );
+    if untested > 0 {
+        println!("{} benchmarks lack sample data; re-run both sides with --samples", untested);
+    }
 
-    // ===-----------------------------------------------------------------------===
-    // Literal Optimization Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== Literal Optimization Benchmarks ===");
+    let fmt_ratio = |ratio: f64| format!("{:.3}x ({:+.1}%)", ratio, (ratio - 1.0) * 100.0);
+    if let Some(ratio) = compare::geomean_ratio(&comparisons) {
+        println!("Geomean candidate/baseline: {}", fmt_ratio(ratio));
+    }
+    if let Some(ratio) = weights
+        .as_ref()
+        .and_then(|weights| compare::weighted_geomean_ratio(&comparisons, weights))
+    {
+        println!("Weighted score candidate/baseline: {}", fmt_ratio(ratio));
+    }
+    let by_path = compare::geomean_ratio_by_path(&comparisons);
+    if !by_path.is_empty() {
+        println!("\nGeomean candidate/baseline by engine path:");
+        for (path, (ratio, count)) in &by_path {
+            println!("  {:<18} {} over {} benchmarks", path.name(), fmt_ratio(*ratio), count);
+        }
+    }
+    // Singleton families are already in the table above
+    let by_family: Vec<_> = compare::geomean_ratio_by_family(&comparisons)
+        .into_iter()
+        .filter(|(_, (_, count))| *count > 1)
+        .collect();
+    if !by_family.is_empty() {
+        println!("\nGeomean candidate/baseline by family:");
+        for (family, (ratio, count)) in &by_family {
+            println!("  {:<18} {} over {} benchmarks", family, fmt_ratio(*ratio), count);
+        }
+    }
+    Ok(())
+}
 
-    let medium_text = get_medium_text();
-    let long_text = get_long_text();
-    let email_long = get_email_long();
+/// Triage table of the (at most `n`) largest changes, with absolute times and
+/// sample counts
+fn print_top_changes(kind: &str, n: usize, changes: &[&compare::Comparison]) {
+    if changes.is_empty() {
+        return;
+    }
+    println!("=== Top {} {} ===", n, kind);
+    println!("| name                      |   baseline |  candidate |    delta | base iters | cand iters |");
+    println!("|---------------------------|-----------:|-----------:|---------:|-----------:|-----------:|");
+    for c in changes {
+        println!(
+            "| {:<25} | {:>10} | {:>10} | {:>+7.1}% | {:>10} | {:>10} |",
+            c.name,
+            format_ns(c.baseline_ns),
+            format_ns(c.candidate_ns),
+            c.delta_pct,
+            c.baseline_iterations,
+            c.candidate_iterations
+        );
+    }
+    println!();
+}
 
-    run_benchmark(&timer, &mut all_results, "literal_prefix_short", &patterns.literal_prefix_short, SHORT_TEXT, 1, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "literal_prefix_medium", &patterns.literal_prefix_medium, &medium_text, 1, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "literal_prefix_long", &patterns.literal_prefix_long, &long_text, 1, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "required_literal_short", &patterns.required_literal, EMAIL_TEXT, 1, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "required_literal_long", &patterns.required_literal, &email_long, 1, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "no_literal_baseline", &patterns.range_a_z, &medium_text, 1, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "alternation_common_prefix", &patterns.alt_common_prefix, &medium_text, 1, BenchType::FindAll);
+/// Run every engine listed in `config`, then write whatever they produced,
+/// with per-engine errors, to `output`
+fn run_orchestrate(config: &str, output: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let specs = orchestrator::load_specs(config)?;
+    let merged = orchestrator::orchestrate(&specs);
+
+    for spec in &specs {
+        match merged.errors.get(&spec.name) {
+            None => println!("✓ {}", spec.name),
+            Some(errors) => {
+                for error in errors {
+                    println!("✗ {}: {:?}", spec.name, error);
+                }
+            }
+        }
+    }
+    std::fs::write(output, serde_json::to_string_pretty(&merged)?)?;
+    println!("Merged results for {} of {} engines written to {}", merged.engines.len(), specs.len(), output);
+    Ok(())
+}
 
-    // ===-----------------------------------------------------------------------===
-    // US Phone Number Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== US Phone Number Benchmarks ===");
+/// Merge repeated runs into their median-of-runs results at `output`,
+/// gzipped if its name ends in `.gz`
+fn run_merge(output: &str, runs: &[String]) -> Result<(), Box<dyn std::error::Error>> {
+    let loaded = runs.iter().map(|run| BenchmarkResults::load(run)).collect::<Result<Vec<_>, _>>()?;
+    let merged = merge::merge(&loaded)?;
+    let content = serde_json::to_string_pretty(&merged)?;
+    archive::write(Path::new(output), &content, output.ends_with(archive::GZIP_EXTENSION))?;
+
+    let noisy = merged
+        .flat()
+        .values()
+        .filter(|result| result.stability_score.is_some_and(|score| score > stats::NOISY_STABILITY_SCORE))
+        .count();
+    println!("Median of {} runs written to {}", runs.len(), output);
+    if noisy > 0 {
+        println!(
+            "⚠ {} benchmarks differ by more than {:.0}% between runs",
+            noisy,
+            (stats::NOISY_STABILITY_SCORE - 1.0) * 100.0
+        );
+    }
+    Ok(())
+}
 
-    let phone_text = make_phone_test_data(1000);
+/// Run the suite once with `options`' timer settings, printing progress
+fn measure_suite(options: &Options) -> Result<BenchmarkResults, Box<dyn std::error::Error>> {
+    let mut environment = Environment::capture();
+    let mut timer = BenchmarkTimer::with_clock(clock::select(&options.clock)?)
+        .with_sample_retention(options.samples)
+        .with_noise_rerun(options.max_cv_pct / 100.0, options.reruns)
+        .with_rounds(options.rounds)
+        .with_min_outer_iterations(options.min_iterations)
+        .with_metrics(metric::select(options.metrics.as_deref().unwrap_or(""))?);
+    if options.latency {
+        timer = timer.with_latency_samples(LATENCY_SAMPLES);
+    }
+    let mut all_results = ResultCollector::new();
+    match options.iterations {
+        Some(iterations) => timer = timer.with_fixed_iterations(iterations),
+        // The sentinel is timed to a wall-clock target, so it only runs when
+        // the benchmarks do too
+        None => all_results = all_results.with_sentinel(Sentinel::new(options.max_drift_pct)),
+    }
+    if let Some(budget) = options.time_budget {
+        all_results = all_results.with_time_budget(budget);
+    }
+    if let Some(deadline) = options.deadline {
+        all_results = all_results.with_benchmark_deadline(deadline);
+    }
+    // An abandoned benchmark thread may still hold the timer, so it lives
+    // for the rest of the process
+    let timer: &'static Timer = Box::leak(Box::new(timer));
 
-    run_benchmark(&timer, &mut all_results, "simple_phone", &patterns.simple_phone, &phone_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "flexible_phone", &patterns.flexible_phone, &phone_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "multi_format_phone", &patterns.multi_format_phone, &phone_text, 50, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "phone_validation", &patterns.phone_validation, "555-123-4567", 500, BenchType::IsMatch);
+    println!("=== RUST REGEX BENCHMARKS ===");
+    println!();
 
-    // ===-----------------------------------------------------------------------===
-    // DFA-Optimized Phone Number Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== DFA-Optimized Phone Number Benchmarks ===");
+    all_results.checkpoint("start");
+    register_benchmarks(timer, &mut all_results)?;
+    all_results.checkpoint("end");
 
-    run_benchmark(&timer, &mut all_results, "dfa_simple_phone", &patterns.dfa_simple_phone, &phone_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "dfa_paren_phone", &patterns.dfa_paren_phone, &phone_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "dfa_dot_phone", &patterns.dfa_dot_phone, &phone_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "dfa_digits_only", &patterns.dfa_digits_only, &phone_text, 100, BenchType::FindAll);
+    environment.finish();
+    let stability = all_results.stability();
+    Ok(BenchmarkResults {
+        schema_version: SCHEMA_VERSION,
+        engine: "rust".to_string(),
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        results: all_results.results().clone(),
+        aggregates: Aggregates::from_results(all_results.results()),
+        environment: Some(environment),
+        build_info: Some(BuildInfo::current()),
+        clock: Some(format!("{} ({})", timer.clock().name(), timer.clock().unit())),
+        unstable_environment: stability.as_ref().is_some_and(|report| report.unstable()),
+        stability,
+        setup_costs: all_results.setup_costs().to_vec(),
+    })
+}
 
-    // National Phone Number Validation (Complex Pattern)
-    let national_phone_text = make_complex_pattern_test_data(500);
-    run_benchmark(&timer, &mut all_results, "national_phone_validation", &patterns.national_phone_validation, &national_phone_text, 10, BenchType::FindAll);
+fn run_suite(options: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    // Loaded up front so a bad path fails before the suite runs
+    let baseline = options
+        .baseline
+        .as_deref()
+        .or(options.check_against.as_deref())
+        .map(|spec| load_baseline(&options.output_dir, spec))
+        .transpose()?;
+    if let Some(path) = &options.thresholds {
+        compare::load_thresholds(path)?;
+    }
+    let benchmark_results = measure_suite(options)?;
+    let regressions = match &baseline {
+        Some(baseline) => find_regressions(baseline, &benchmark_results, options)?,
+        None => Vec::new(),
+    };
 
     // ===-----------------------------------------------------------------------===
-    // Quantifier Parser Optimization Benchmarks
+    // Results Summary
     // ===-----------------------------------------------------------------------===
-    println!("=== Quantifier Parser Optimization Benchmarks ===");
-
-    // Generate test data for quantifier-intensive patterns
-    let serial_number_text = "Serial: ABC1234-DEF5678-GHI9012 Model: XYZ123-ABC456-DEF789 Part: MNO345-PQR678-STU901 Code: VWX234-YZA567-BCD890 ".repeat(50);
-    let datetime_text = "2024-01-15 14:30:25.123 2024-02-28 09:45:30.456 2024-03-10 16:20:15.789 2024-04-05 11:35:40.012 ".repeat(100);
-    let structured_data_text = "Record: USER12345-DEPT678-LOC901-ID234 Status: ACTIVE567-FLAG890-CODE123 Transaction: TXN9876-AMT543-FEE210-TAX087 Reference: REF1357-NUM246-CHK802 ".repeat(75);
+    println!("\n=== Benchmark Results ===");
+    print_results_table(&benchmark_results, &options.table, baseline.as_ref());
+    print_variant_deltas(&benchmark_results.results);
+    print_metrics(&benchmark_results.results);
+    print_engine_summary(&benchmark_results.results);
 
-    // Single quantifier patterns (baseline)
-    run_benchmark(&timer, &mut all_results, "single_quantifier_digits", &patterns.single_quantifier_digits, &serial_number_text, 200, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "single_quantifier_alpha", &patterns.single_quantifier_alpha, &serial_number_text, 200, BenchType::FindAll);
+    if let Some(environment) = &benchmark_results.environment {
+        print_environment(environment);
+    }
+    if let Some(build_info) = &benchmark_results.build_info {
+        println!("Build: {}", build_info.summary());
+    }
+    if let Some(report) = &benchmark_results.stability {
+        print_stability(report);
+    }
 
-    // Multiple quantifier patterns - these benefit most from the optimization
-    run_benchmark(&timer, &mut all_results, "dual_quantifiers", &patterns.dual_quantifiers, &serial_number_text, 150, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "triple_quantifiers", &patterns.triple_quantifiers, &serial_number_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "quad_quantifiers", &patterns.quad_quantifiers, &serial_number_text, 100, BenchType::FindAll);
+    let violations = criteria::evaluate(&benchmark_results);
+    if !violations.is_empty() {
+        println!("\n=== Criteria Violations ===");
+        print_violations(&violations);
+    }
 
-    // Complex quantifier ranges {min,max} - stress test the parser optimization
-    run_benchmark(&timer, &mut all_results, "range_quantifiers", &patterns.range_quantifiers, &serial_number_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "mixed_range_quantifiers", &patterns.mixed_range_quantifiers, &serial_number_text, 75, BenchType::FindAll);
+    // Export to JSON
+    let output_dir = &options.output_dir;
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("failed to create {}: {}", output_dir.display(), e))?;
+    let label = options.label.as_deref();
+    // Per-sample timings make for files of hundreds of MB
+    let compress = options.gzip || options.samples;
+    let results_file = if options.history {
+        append_history(&benchmark_results, &output_dir.join(HISTORY_FILE))?;
+        None
+    } else {
+        let run_time = chrono::DateTime::parse_from_rfc3339(&benchmark_results.timestamp)
+            .map_or_else(|_| chrono::Utc::now(), |time| time.with_timezone(&chrono::Utc));
+        let extension = if compress { archive::GZIP_EXTENSION } else { "" };
+        let file = archive::timestamped_name(label, run_time) + extension;
+        // A single latest file whatever the format, as readers detect gzip
+        // by content: one left by an older run can't be read in its place
+        let latest = archive::latest_name(label);
+        export_json_results(&benchmark_results, &output_dir.join(&file), compress)?;
+        archive::copy_latest(output_dir, &file, &latest)?;
+        let stale = latest.clone() + archive::GZIP_EXTENSION;
+        for stale in [provenance::signature_path(&stale), stale] {
+            let stale = output_dir.join(stale);
+            if stale.exists() {
+                std::fs::remove_file(&stale).map_err(|e| format!("failed to remove {}: {}", stale.display(), e))?;
+            }
+        }
+        println!("Latest results: {}", output_dir.join(&latest).display());
+        Some((file, latest))
+    };
+    if let Some(path) = &options.history_db {
+        record_history_db(&benchmark_results, path)?;
+    }
+    if let Some(name) = &options.save_baseline {
+        let content = serde_json::to_string_pretty(&benchmark_results)?;
+        let path = archive::save_baseline(output_dir, name, &content, compress)?;
+        println!("Baseline {:?} saved to {}", name, path.display());
+    }
+    match options.format {
+        Format::Json => {}
+        Format::Markdown => {
+            let path = output_dir.join(MARKDOWN_FILE);
+            std::fs::write(&path, report::markdown(&benchmark_results))?;
+            println!("Markdown report written to: {}", path.display());
+        }
+        Format::Html => {
+            let path = output_dir.join(HTML_FILE);
+            std::fs::write(&path, report::html(&benchmark_results))?;
+            println!("HTML report written to: {}", path.display());
+        }
+        Format::GoogleBenchmark => {
+            let path = output_dir.join(GBENCH_FILE);
+            std::fs::write(&path, report::google_benchmark(&benchmark_results))?;
+            println!("Google Benchmark JSON written to: {}", path.display());
+        }
+        Format::Junit => {
+            let path = output_dir.join(JUNIT_FILE);
+            let xml = report::junit(&benchmark_results, &regressions);
+            std::fs::write(&path, xml)?;
+            println!("JUnit XML written to: {}", path.display());
+        }
+        Format::Criterion => {
+            let path = output_dir.join(CRITERION_DIR);
+            let written = criterion::export(&benchmark_results, &path, &options.criterion_baseline)?;
+            println!(
+                "Criterion baseline {:?} ({} benchmarks) written to: {}",
+                options.criterion_baseline,
+                written,
+                path.display()
+            );
+        }
+        Format::PrComment => unreachable!("parse() rejects --format=pr-comment outside compare"),
+    }
+    if let (Some(key_path), Some(signer), Some((file, latest))) = (&options.sign_key, &options.signer, &results_file) {
+        let results_path = output_dir.join(file);
+        let signature = provenance::sign_file(&results_path.to_string_lossy(), key_path, signer)?;
+        // Keep the latest copy's signature next to it, for `compare`
+        archive::copy_latest(output_dir, &provenance::signature_path(file), &provenance::signature_path(latest))?;
+        println!("Signed by {} (key {})", signature.signer, signature.public_key);
+    }
 
-    // DateTime patterns with many quantifiers
-    run_benchmark(&timer, &mut all_results, "datetime_quantifiers", &patterns.datetime_quantifiers, &datetime_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "flexible_datetime", &patterns.flexible_datetime, &datetime_text, 100, BenchType::FindAll);
+    if options.check_against.is_some() {
+        // After exporting, so the regressed run can still be inspected
+        print_regressions(&regressions);
+        if !regressions.is_empty() {
+            std::process::exit(1);
+        }
+    }
+    Ok(())
+}
 
-    // High quantifier density patterns - maximum parser stress
-    run_benchmark(&timer, &mut all_results, "dense_quantifiers", &patterns.dense_quantifiers, &structured_data_text, 50, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "ultra_dense_quantifiers", &patterns.ultra_dense_quantifiers, &structured_data_text, 25, BenchType::FindAll);
+/// Run the suite twice as if the runs were two engines and report the
+/// distribution of the deltas between them, all of which are noise
+fn run_aa_test(options: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    let first = measure_suite(options)?;
+    println!();
+    let second = measure_suite(options)?;
+    let comparisons = compare(&first, &second, DEFAULT_ALPHA);
+    let Some(report) = AaReport::new(&comparisons, aa::DEFAULT_MAX_SPURIOUS_PCT) else {
+        return Err("no benchmark was measured in both runs".into());
+    };
 
-    // Nested quantifiers within groups
-    run_benchmark(&timer, &mut all_results, "grouped_quantifiers", &patterns.grouped_quantifiers, &serial_number_text, 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "alternation_quantifiers", &patterns.alternation_quantifiers, &structured_data_text, 75, BenchType::FindAll);
+    println!("\n=== A/A Test: {} benchmarks, identical binary ===", report.compared);
+    println!("Median |delta|: {:.2}%", report.median_abs_pct);
+    println!("p95 |delta|:    {:.2}%", report.p95_abs_pct);
+    println!("Max |delta|:    {:.2}%", report.max_abs_pct);
+    println!(
+        "Significant at alpha = {}: {} (all false positives)",
+        DEFAULT_ALPHA, report.significant
+    );
+    println!("\n| abs delta    | benchmarks |");
+    println!("|--------------|------------|");
+    let mut lower = 0.0;
+    for (bucket, count) in report.buckets.iter().enumerate() {
+        let label = match aa::DELTA_BUCKETS_PCT.get(bucket) {
+            Some(upper) => format!("{:.0}-{:.0}%", lower, upper),
+            None => format!("> {:.0}%", lower),
+        };
+        println!("| {:<12} | {:>10} |", label, count);
+        lower = aa::DELTA_BUCKETS_PCT.get(bucket).copied().unwrap_or(lower);
+    }
 
-    // ===-----------------------------------------------------------------------===
-    // OPTIMIZATION SHOWCASE: Quantifier Parser Improvements
-    // ===-----------------------------------------------------------------------===
-    println!("=== Quantifier Optimization Showcase Benchmarks ===");
+    if report.passed() {
+        println!(
+            "\n✓ No spurious delta above {:.0}%: single-run deltas of that size are credible",
+            report.threshold_pct
+        );
+    } else {
+        println!(
+            "\n⚠ {} benchmarks moved more than {:.0}% between identical runs; a single-run delta below {:.1}% is noise:",
+            report.over_threshold.len(),
+            report.threshold_pct,
+            report.max_abs_pct
+        );
+        for (name, delta_pct) in &report.over_threshold {
+            println!("  {:<25} {:>+7.1}%", name, delta_pct);
+        }
+    }
+    Ok(())
+}
 
-    // Optimization test data for quantifier stress testing
-    let optimization_test_text = "Transaction: TXN12345-DEPT678-LOC90123-ID4567 Status: ACTIVE12-FLAG890-CODE1234 Reference: REF13579-NUM24680-CHK80246 Product: PROD123-CAT456-TYPE789-SUB012 ".repeat(100);
+/// Register the suite without timing anything and report definition rot
+fn run_lint() -> Result<(), Box<dyn std::error::Error>> {
+    let suite = suite::export_definitions();
+    let categories: Vec<String> = suite.categories.iter().map(|category| category.name.clone()).collect();
 
-    // Most significant optimization cases from analysis
-    run_benchmark(&timer, &mut all_results, "optimize_range_quantifier", &patterns.optimize_range_quantifier, &("aaaabbbbccccdddd".repeat(500)), 1000, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "optimize_multiple_quantifiers", &patterns.optimize_multiple_quantifiers, &optimization_test_text, 200, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "optimize_phone_quantifiers", &patterns.optimize_phone_quantifiers, &("Call 555-123-4567 or 800-555-1234 or 900-876-5432 for help. ".repeat(200)), 300, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "optimize_large_quantifiers", &patterns.optimize_large_quantifiers, &(format!("PREFIX{}{}SUFFIX ", "A".repeat(15), "1".repeat(20)).repeat(50)), 100, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "optimize_extreme_quantifiers", &patterns.optimize_extreme_quantifiers, &("abcccddddeeeeeffffffggggggghhhhhhhhSEPARATOR".repeat(20)), 500, BenchType::FindAll);
+    let issues = lint::lint(&suite.benchmarks, &categories);
+    for issue in &issues {
+        println!("{}", issue);
+    }
+    let errors = issues
+        .iter()
+        .filter(|issue| issue.severity() == lint::Severity::Error)
+        .count();
+    println!(
+        "\n{} benchmarks in {} categories: {} errors, {} warnings",
+        suite.benchmarks.len(),
+        categories.len(),
+        errors,
+        issues.len() - errors
+    );
+    if errors > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
 
-    // ===-----------------------------------------------------------------------===
-    // is_match (Bool-only) Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== is_match (Bool-only) Benchmarks ===");
 
-    let text_digits_10000 = "0123456789".repeat(1000) + "abcdefghijklmnopqrstuvwxyz";
-    run_benchmark(&timer, &mut all_results, "is_match_lowercase", &patterns.range_a_z, &text_range_10000, 1000, BenchType::IsMatchBool);
-    run_benchmark(&timer, &mut all_results, "is_match_digits", &patterns.range_0_9, &text_digits_10000, 1000, BenchType::IsMatchBool);
-    run_benchmark(&timer, &mut all_results, "is_match_alphanumeric", &patterns.range_alnum, &text_range_10000, 1000, BenchType::IsMatchBool);
-    run_benchmark(&timer, &mut all_results, "is_match_predefined_digits", &patterns.predefined_digits, &text_digits_10000, 1000, BenchType::IsMatchBool);
-    run_benchmark(&timer, &mut all_results, "is_match_predefined_word", &patterns.predefined_word, &text_range_10000, 1000, BenchType::IsMatchBool);
+fn print_results_table(results: &BenchmarkResults, table: &TableOptions, baseline: Option<&BenchmarkResults>) {
+    print!("{}", summary::table(results, table, baseline));
 
-    // ===-----------------------------------------------------------------------===
-    // sub (replacement) Benchmarks
-    // ===-----------------------------------------------------------------------===
-    println!("=== sub (replacement) Benchmarks ===");
+    let mut sorted_results: Vec<_> = results.results.values().flatten().collect();
+    sorted_results.sort_by_key(|(name, result)| registration_order(name, result));
 
-    let short_text_20 = "hello world this is a test with hello again and hello there".repeat(20);
-    let whitespace_text = "  hello   world   foo   bar   baz  ".repeat(100);
-    let short_text_100 = "hello world this is a test with hello again and hello there".repeat(100);
+    let rejected: Vec<String> = sorted_results
+        .iter()
+        .filter_map(|(name, result)| Some(format!("{} ({})", name, result.compile_error.as_ref()?)))
+        .collect();
+    if !rejected.is_empty() {
+        println!("\nPatterns rejected at compile time: {}", rejected.join(", "));
+    }
 
-    let sub_hello = Regex::new("hello")?;
-    let sub_phone = Regex::new(r"\d{3}-\d{3}-\d{4}")?;
-    let sub_digits = Regex::new("[0-9]+")?;
-    let sub_whitespace = Regex::new(r"\s+")?;
+    let reran: Vec<String> = sorted_results
+        .iter()
+        .filter(|(_, result)| result.reran > 0)
+        .map(|(name, result)| format!("{} ({}x)", name, result.reran))
+        .collect();
+    if !reran.is_empty() {
+        println!("\nRe-ran noisy benchmarks: {}", reran.join(", "));
+    }
 
-    run_sub_benchmark(&timer, &mut all_results, "sub_literal", &sub_hello, "REPLACED", &short_text_20, 100);
-    run_sub_benchmark(&timer, &mut all_results, "sub_digits", &sub_phone, "XXX-XXX-XXXX", &phone_text, 10);
-    run_sub_benchmark(&timer, &mut all_results, "sub_char_class", &sub_digits, "#", &phone_text, 10);
-    run_sub_benchmark(&timer, &mut all_results, "sub_whitespace", &sub_whitespace, " ", &whitespace_text, 50);
-    run_sub_benchmark(&timer, &mut all_results, "sub_limited_count", &sub_hello, "HI", &short_text_100, 100);
+    let mut noisy: Vec<(&str, f64, usize)> = sorted_results
+        .iter()
+        .filter_map(|(name, result)| {
+            let score = result.stability_score.filter(|&score| score > stats::NOISY_STABILITY_SCORE)?;
+            Some((name.as_str(), score, result.round_medians_ns.as_ref().map_or(0, Vec::len)))
+        })
+        .collect();
+    if !noisy.is_empty() {
+        noisy.sort_by(|a, b| b.1.total_cmp(&a.1));
+        println!("\n=== Noisy Benchmarks (round max/min > {:.2}) ===", stats::NOISY_STABILITY_SCORE);
+        println!("| name                      | stability |  rounds |");
+        println!("|---------------------------|-----------|---------|");
+        for (name, score, rounds) in noisy {
+            println!("| {:<25} | {:>9.3} | {:>7} |", name, score, rounds);
+        }
+    }
 
-    // Group-reference substitution benchmarks
-    let phone_numbers = "Call 6502530000 or 4155551234 today. ".repeat(100);
-    let sub_group_phone = Regex::new(r"(\d{3})(\d{3})(\d{4})")?;
-    let sub_group_date = Regex::new(r"(\d{4})-(\d{2})-(\d{2})")?;
-    let sub_group_word = Regex::new(r"(\w+) (\w+)")?;
+    let bimodal: Vec<&str> = sorted_results
+        .iter()
+        .filter(|(_, result)| result.bimodal)
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !bimodal.is_empty() {
+        println!("\nWarning: bimodal timing distributions (median may mislead): {}", bimodal.join(", "));
+    }
 
-    run_sub_benchmark(&timer, &mut all_results, "sub_group_phone_fmt", &sub_group_phone, "$1-$2-$3", &phone_numbers, 10);
-    run_sub_benchmark(&timer, &mut all_results, "sub_group_date_fmt", &sub_group_date, "$2/$3/$1", &("Event on 2026-04-12 and 2025-12-25 and 2024-01-01. ".repeat(50)), 20);
-    run_sub_benchmark(&timer, &mut all_results, "sub_group_word_swap", &sub_group_word, "$2 $1", &("hello world foo bar baz qux ".repeat(50)), 20);
+    if sorted_results.iter().any(|(_, result)| result.latency.is_some()) {
+        println!("\n=== Single-call Latency (ns) ===");
+        println!("| name                      |      p50 |      p99 |    p99.9 |        max |");
+        println!("|---------------------------|----------|----------|----------|------------|");
+        for (name, result) in &sorted_results {
+            if let Some(latency) = &result.latency {
+                println!(
+                    "| {:<25} | {:>8.0} | {:>8.0} | {:>8.0} | {:>10.0} |",
+                    name, latency.p50_ns, latency.p99_ns, latency.p999_ns, latency.max_ns
+                );
+            }
+        }
+    }
+}
 
-    // ===-----------------------------------------------------------------------===
-    // Sparse Match Benchmarks (long text, rare matches)
-    // ===-----------------------------------------------------------------------===
-    println!("=== Sparse Match Benchmarks ===");
+/// One column per metric recorded with `--metrics`, per-op metrics as
+/// values per operation
+fn print_metrics(results: &CategorizedResults) {
+    let mut rows: Vec<(&String, &BenchmarkResult)> = results
+        .values()
+        .flatten()
+        .filter(|(_, result)| result.metrics.is_some())
+        .collect();
+    let Some(names) = rows
+        .first()
+        .and_then(|(_, result)| result.metrics.as_ref())
+        .map(|metrics| metrics.keys().cloned().collect::<Vec<_>>())
+    else {
+        return;
+    };
+    rows.sort_by_key(|(name, result)| registration_order(name, result));
 
-    let filler = "The quick brown fox jumps over the lazy dog. ".repeat(40);
-    let mut sparse_phone_text = String::new();
-    for _ in 0..20 {
-        sparse_phone_text.push_str(&filler);
-        sparse_phone_text.push_str("Call 555-123-4567 now. ");
+    println!("\n=== Metrics ===");
+    print!("| name                      |");
+    for name in &names {
+        print!(" {:>14} |", name);
+    }
+    print!("\n|---------------------------|");
+    for _ in &names {
+        print!("----------------|");
     }
+    println!();
+    for (bench, result) in rows {
+        print!("| {:<25} |", bench);
+        for name in &names {
+            match result.metrics.as_ref().and_then(|metrics| metrics.get(name)) {
+                Some(metric) => print!(" {:>14.1} |", metric.value),
+                None => print!(" {:>14} |", "-"),
+            }
+        }
+        println!();
+    }
+}
 
-    let sparse_phone_pat = Regex::new(r"\d{3}-\d{3}-\d{4}")?;
-    run_benchmark(&timer, &mut all_results, "sparse_phone_findall", &sparse_phone_pat, &sparse_phone_text, 5, BenchType::FindAll);
+/// Each variant's time against the first-registered variant of its family,
+/// e.g. the Unicode overhead of the parallel corpora
+fn print_variant_deltas(results: &CategorizedResults) {
+    let mut members: Vec<(&String, &BenchmarkResult, &Variant)> = results
+        .values()
+        .flatten()
+        .filter(|(_, result)| result.is_measured())
+        .filter_map(|(name, result)| Some((name, result, result.variant.as_ref()?)))
+        .collect();
+    if members.is_empty() {
+        return;
+    }
+    members.sort_by_key(|(name, result, _)| registration_order(name, result));
+
+    println!("\n=== Variant Deltas ===");
+    println!("| family                    | variant  |    time (ns) |  vs first |");
+    println!("|---------------------------|----------|--------------|-----------|");
+    let mut baselines: Vec<(&str, f64)> = Vec::new();
+    for (_, result, variant) in members {
+        let baseline_ns = match baselines.iter().find(|(family, _)| *family == variant.family) {
+            Some(&(_, baseline_ns)) => baseline_ns,
+            None => {
+                baselines.push((&variant.family, result.time_ns));
+                result.time_ns
+            }
+        };
+        println!(
+            "| {:<25} | {:<8} | {:>12.1} | {:>+8.1}% |",
+            variant.family,
+            variant.name,
+            result.time_ns,
+            (result.time_ns / baseline_ns - 1.0) * 100.0
+        );
+    }
+}
 
-    let sparse_paren_phone_pat = Regex::new(r"\(\d{3}\)\s\d{3}-\d{4}")?;
-    let sparse_search_text = format!("{}(555) 123-4567{}", filler.repeat(50), filler.repeat(50));
-    run_benchmark(&timer, &mut all_results, "sparse_phone_search", &sparse_paren_phone_pat, &sparse_search_text, 5, BenchType::Search);
+/// Count benchmarks per execution strategy, so comparisons against the Mojo
+/// DFA/NFA routing can tell when Rust fell back to a slower engine
+fn print_engine_summary(results: &CategorizedResults) {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for result in results.values().flat_map(|benches| benches.values()) {
+        let engine = result.engine.clone().unwrap_or_else(|| "unknown".to_string());
+        match counts.iter_mut().find(|(name, _)| *name == engine) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((engine, 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
 
-    let sparse_email_pat = Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}")?;
-    let sparse_email_text = format!("{filler}Contact admin@example.com for details. ").repeat(10);
-    run_benchmark(&timer, &mut all_results, "sparse_email_findall", &sparse_email_pat, &sparse_email_text, 5, BenchType::FindAll);
+    println!("\nRust Engine Usage Summary:");
+    println!("==========================");
+    for (engine, count) in counts {
+        println!("{:<26} {} patterns", format!("{}:", engine), count);
+    }
+}
 
-    let sparse_flex_phone_pat = Regex::new(r"\(?\d{3}\)?[\s.-]?\d{3}[\s.-]?\d{4}")?;
-    let mut sparse_flex_text = String::new();
-    for _ in 0..10 {
-        sparse_flex_text.push_str(&filler);
-        sparse_flex_text.push_str("Reach us at (555) 123-4567 today. ");
+fn print_environment(environment: &Environment) {
+    let fmt_load = |load: Option<[f64; 3]>| load.map_or("?".to_string(), |l| format!("{:.2}", l[0]));
+    let fmt_freq = |freq: Option<f64>| freq.map_or("?".to_string(), |f| format!("{:.0}", f));
+    println!(
+        "\nEnvironment: {} ({} cores, governor {}), load {} -> {}, {} -> {} MHz",
+        environment.cpu_model.as_deref().unwrap_or("unknown CPU"),
+        environment.logical_cores,
+        environment.cpu_governor.as_deref().unwrap_or("n/a"),
+        fmt_load(environment.load_average_before),
+        fmt_load(environment.load_average_after),
+        fmt_freq(environment.cpu_freq_mhz_before),
+        fmt_freq(environment.cpu_freq_mhz_after),
+    );
+    if let Some(image) = &environment.container_image {
+        println!("Container: {}", image);
     }
-    run_benchmark(&timer, &mut all_results, "sparse_flex_phone_findall", &sparse_flex_phone_pat, &sparse_flex_text, 2, BenchType::FindAll);
+}
 
-    // ===-----------------------------------------------------------------------===
-    // Many-State DFA Benchmarks (NANPA)
-    // ===-----------------------------------------------------------------------===
-    println!("=== Many-State DFA Benchmarks (NANPA) ===");
+/// Sentinel readings, with a warning when the machine drifted mid-run
+fn print_stability(report: &sentinel::StabilityReport) {
+    let readings: Vec<String> = report
+        .readings
+        .iter()
+        .map(|r| format!("{} {:.0} ns", r.stage, r.time_ns))
+        .collect();
+    println!("Sentinel: {} (drift {:.1}%)", readings.join(", "), report.drift_pct);
+    if report.unstable() {
+        println!(
+            "⚠ Sentinel drifted more than {:.0}% (thermal throttling or background load?); \
+             results are flagged unstable_environment",
+            report.max_drift_pct
+        );
+    }
+}
 
-    let nanpa_pat = Regex::new(r"(?:2(?:0[1-35-9]|1[02-9]|2[03-57-9]|3[1459]|4[08]|5[1-46]|6[0279]|7[0269]|8[13])|3(?:0[1-47-9]|1[02-9]|2[0135-79]|3[0-24679]|4[167]|5[0-2]|6[01349]|8[056])|4(?:0[124-9]|1[02-579]|2[3-5]|3[0245]|4[023578]|58|6[349]|7[0589]|8[04])|5(?:0[1-47-9]|1[0235-8]|20|3[0149]|4[01]|5[179]|6[1-47]|7[0-5]|8[0256])|6(?:0[1-35-9]|1[024-9]|2[03689]|3[016]|4[0156]|5[01679]|6[0-279]|78|8[0-29])|7(?:0[1-46-8]|1[2-9]|2[04-8]|3[0-247]|4[037]|5[47]|6[02359]|7[0-59]|8[156])|8(?:0[1-68]|1[02-8]|2[0168]|3[0-2589]|4[03578]|5[046-9]|6[02-5]|7[028])|9(?:0[1346-9]|1[02-9]|2[0589]|3[0146-8]|4[01357-9]|5[12469]|7[0-389]|8[04-69]))[2-9]\d{6}")?;
-    let nanpa_text = "Call 6502530000 or 2125551234 or 9175559876. ".repeat(50);
-    run_benchmark(&timer, &mut all_results, "nanpa_findall", &nanpa_pat, &nanpa_text, 2, BenchType::FindAll);
-    run_benchmark(&timer, &mut all_results, "nanpa_search", &nanpa_pat, &nanpa_text, 50, BenchType::Search);
-    run_benchmark(&timer, &mut all_results, "nanpa_match_first", &nanpa_pat, "6502530000", 500, BenchType::IsMatch);
+/// Criteria violations, one per line
+fn print_violations(violations: &[criteria::Violation]) {
+    for violation in violations {
+        println!("✗ {}", violation);
+    }
+}
 
-    // ===-----------------------------------------------------------------------===
-    // Results Summary
-    // ===-----------------------------------------------------------------------===
-    println!("\n=== Benchmark Results ===");
-    print_results_table(&all_results);
+fn append_history(benchmark_results: &BenchmarkResults, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let git_sha = history::git_sha();
+    history::append(&path.to_string_lossy(), benchmark_results, git_sha.as_deref())?;
 
-    // Export to JSON
-    export_json_results(&all_results)?;
+    println!("\n=== BENCHMARK COMPLETE ===");
+    println!("Run at {} appended to: {}", git_sha.as_deref().unwrap_or("unknown commit"), path.display());
 
     Ok(())
 }
 
-#[derive(Debug)]
-enum BenchType {
-    IsMatch,
-    IsMatchBool,
-    Search,
-    FindAll,
-}
-
-struct CompiledPatterns {
-    hello: Regex,
-    dot_star: Regex,
-    a_star: Regex,
-    a_plus: Regex,
-    a_question: Regex,
-    a: Regex,
-    range_a_z: Regex,
-    range_0_9: Regex,
-    range_alnum: Regex,
-    negated_alnum: Regex,
-    multi_char_class: Regex,
-    anchor_start: Regex,
-    anchor_end: Regex,
-    alt_simple: Regex,
-    alt_words: Regex,
-    alt_common_prefix: Regex,
-    group_quantified: Regex,
-    group_alternation: Regex,
-    email: Regex,
-    number: Regex,
-    literal_prefix_short: Regex,
-    literal_prefix_medium: Regex,
-    literal_prefix_long: Regex,
-    required_literal: Regex,
-    simple_phone: Regex,
-    flexible_phone: Regex,
-    multi_format_phone: Regex,
-    phone_validation: Regex,
-    large_alternation: Regex,
-    deep_nested: Regex,
-    literal_heavy: Regex,
-    complex_group: Regex,
-    dfa_simple_phone: Regex,
-    dfa_paren_phone: Regex,
-    dfa_dot_phone: Regex,
-    dfa_digits_only: Regex,
-    national_phone_validation: Regex,
-    // Quantifier parser optimization patterns
-    single_quantifier_digits: Regex,
-    single_quantifier_alpha: Regex,
-    dual_quantifiers: Regex,
-    triple_quantifiers: Regex,
-    quad_quantifiers: Regex,
-    range_quantifiers: Regex,
-    mixed_range_quantifiers: Regex,
-    datetime_quantifiers: Regex,
-    flexible_datetime: Regex,
-    dense_quantifiers: Regex,
-    ultra_dense_quantifiers: Regex,
-    grouped_quantifiers: Regex,
-    alternation_quantifiers: Regex,
-    // Optimization showcase patterns
-    optimize_range_quantifier: Regex,
-    optimize_multiple_quantifiers: Regex,
-    optimize_phone_quantifiers: Regex,
-    optimize_large_quantifiers: Regex,
-    optimize_extreme_quantifiers: Regex,
-    // Predefined character classes
-    predefined_digits: Regex,
-    predefined_word: Regex,
-}
-
-fn make_phone_test_data(num_phones: usize) -> String {
-    let phone_patterns = [
-        "555-123-4567",
-        "(555) 123-4567",
-        "555.123.4567",
-        "5551234567",
-        "+1-555-123-4567",
-        "1-555-123-4568",
-        "(555)123-4569",
-        "555 123 4570"
-    ];
-    let filler_text = " Contact us at ";
-    let extra_text = " or email support@company.com for assistance. ";
-
-    let mut result = String::new();
-    for i in 0..num_phones {
-        result.push_str(filler_text);
-        let pattern_idx = i % phone_patterns.len();
-        result.push_str(phone_patterns[pattern_idx]);
-        result.push_str(extra_text);
-    }
-
-    result
-}
-
-fn make_complex_pattern_test_data(num_entries: usize) -> String {
-    // Generate test data for US national phone number validation
-    let complex_patterns = [
-        "305200123456",     // Matches first alternation
-        "505601234567",     // Matches first alternation
-        "274212345678",     // Matches second alternation
-        "305912345678",     // Matches second alternation
-        "212345672890",     // Matches third alternation
-        "312345672890",     // Matches third alternation
-        "412345672890",     // Matches third alternation
-        "512345672890",     // Matches third alternation
-        "1234567890",       // Should NOT match
-        "30520",            // Should NOT match (too short)
-    ];
-    let filler_text = " ID: ";
-    let extra_text = " Status: ACTIVE ";
-
-    let mut result = String::new();
-    for i in 0..num_entries {
-        result.push_str(filler_text);
-        let pattern_idx = i % complex_patterns.len();
-        result.push_str(complex_patterns[pattern_idx]);
-        result.push_str(extra_text);
-    }
-
-    result
-}
-
-fn create_all_patterns() -> Result<CompiledPatterns, Box<dyn std::error::Error>> {
-    Ok(CompiledPatterns {
-        hello: Regex::new("hello")?,
-        dot_star: Regex::new(".*")?,
-        a_star: Regex::new("a*")?,
-        a_plus: Regex::new("a+")?,
-        a_question: Regex::new("a?")?,
-        a: Regex::new("a")?,
-        range_a_z: Regex::new("[a-z]+")?,
-        range_0_9: Regex::new("[0-9]+")?,
-        range_alnum: Regex::new("[a-zA-Z0-9]+")?,
-        negated_alnum: Regex::new("[^a-zA-Z0-9]+")?,
-        multi_char_class: Regex::new("[a-z]+[0-9]+")?,
-        anchor_start: Regex::new("^abc")?,
-        anchor_end: Regex::new("xyz$")?,
-        alt_simple: Regex::new("a|b|c")?,
-        alt_words: Regex::new("abc|def|ghi")?,
-        alt_common_prefix: Regex::new("(hello|help|helicopter)")?,
-        group_quantified: Regex::new("(abc)+")?,
-        group_alternation: Regex::new("(a|b)*")?,
-        email: Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}")?,
-        number: Regex::new(r"\d+\.?\d*")?,
-        literal_prefix_short: Regex::new("hello.*world")?,
-        literal_prefix_medium: Regex::new("hello.*")?,
-        literal_prefix_long: Regex::new("hello.*")?,
-        required_literal: Regex::new(r".*@example\.com")?,
-        simple_phone: Regex::new(r"\d{3}-\d{3}-\d{4}")?,
-        flexible_phone: Regex::new(r"\(?\d{3}\)?[\s.-]?\d{3}[\s.-]?\d{4}")?,
-        multi_format_phone: Regex::new(r"\(?\d{3}\)?[\s.-]\d{3}[\s.-]\d{4}|\d{3}-\d{3}-\d{4}|\d{10}")?,
-        phone_validation: Regex::new(r"^\+?1?[\s.-]?\(?([2-9]\d{2})\)?[\s.-]?([2-9]\d{2})[\s.-]?(\d{4})$")?,
-        large_alternation: Regex::new(r"(apple|banana|cherry|date|elderberry|fig|grape|honey)")?,
-        deep_nested: Regex::new(r"(?:(?:(?:a|b)|(?:c|d))|(?:(?:e|f)|(?:g|h)))")?,
-        literal_heavy: Regex::new(r"(user123|admin456|guest789|root000|test111|demo222|sample333|client444)")?,
-        complex_group: Regex::new(r"(hello|world|test|demo|sample)[0-9]{3}[a-z]{2}")?,
-        dfa_simple_phone: Regex::new(r"[0-9]{3}-[0-9]{3}-[0-9]{4}")?,
-        dfa_paren_phone: Regex::new(r"\([0-9]{3}\) [0-9]{3}-[0-9]{4}")?,
-        dfa_dot_phone: Regex::new(r"[0-9]{3}\.[0-9]{3}\.[0-9]{4}")?,
-        dfa_digits_only: Regex::new(r"[0-9]{10}")?,
-        national_phone_validation: Regex::new(r"(?:3052(?:0[0-8]|[1-9]\d)|5056(?:[0-35-9]\d|4[0-68]))\d{4}|(?:2742|305[3-9]|472[247-9]|505[2-57-9]|983[2-47-9])\d{6}|(?:2(?:0[1-35-9]|1[02-9]|2[03-57-9]|3[1459]|4[08]|5[1-46]|6[0279]|7[0269]|8[13])|3(?:0[1-47-9]|1[02-9]|2[0135-79]|3[0-24679]|4[167]|5[0-2]|6[01349]|8[056])|4(?:0[124-9]|1[02-579]|2[3-5]|3[0245]|4[023578]|58|6[349]|7[0589]|8[04])|5(?:0[1-47-9]|1[0235-8]|20|3[0149]|4[01]|5[179]|6[1-47]|7[0-5]|8[0256])|6(?:0[1-35-9]|1[024-9]|2[03689]|3[016]|4[0156]|5[01679]|6[0-279]|78|8[0-29])|7(?:0[1-46-8]|1[2-9]|2[04-8]|3[0-247]|4[037]|5[47]|6[02359]|7[0-59]|8[156])|8(?:0[1-68]|1[02-8]|2[0168]|3[0-2589]|4[03578]|5[046-9]|6[02-5]|7[028])|9(?:0[1346-9]|1[02-9]|2[0589]|3[0146-8]|4[01357-9]|5[12469]|7[0-389]|8[04-69]))[2-9]\d{6}")?,
-        // Quantifier parser optimization patterns
-        single_quantifier_digits: Regex::new(r"[0-9]{4}")?,
-        single_quantifier_alpha: Regex::new(r"[A-Z]{3}")?,
-        dual_quantifiers: Regex::new(r"[A-Z]{3}[0-9]{4}")?,
-        triple_quantifiers: Regex::new(r"[A-Z]{3}[0-9]{4}-[A-Z]{3}[0-9]{3}")?,
-        quad_quantifiers: Regex::new(r"[A-Z]{3}[0-9]{4}-[A-Z]{3}[0-9]{3}-[A-Z]{3}[0-9]{3}")?,
-        range_quantifiers: Regex::new(r"[A-Z]{2,4}[0-9]{3,5}")?,
-        mixed_range_quantifiers: Regex::new(r"[A-Z]{1,3}-[0-9]{2,4}-[A-Z]{2,3}[0-9]{3,4}")?,
-        datetime_quantifiers: Regex::new(r"[0-9]{4}-[0-9]{2}-[0-9]{2} [0-9]{2}:[0-9]{2}:[0-9]{2}\.[0-9]{3}")?,
-        flexible_datetime: Regex::new(r"[0-9]{4}-[0-9]{1,2}-[0-9]{1,2} [0-9]{1,2}:[0-9]{2}:[0-9]{2}")?,
-        dense_quantifiers: Regex::new(r"[A-Z]{2}[0-9]{5}-[A-Z]{4}[0-9]{3}-[A-Z]{3}[0-9]{3}-[A-Z]{2}[0-9]{3}")?,
-        ultra_dense_quantifiers: Regex::new(r"[A-Z]{1,2}[0-9]{3,5}-[A-Z]{2,4}[0-9]{2,4}-[A-Z]{1,3}[0-9]{2,4}-[A-Z]{2,3}[0-9]{2,3}")?,
-        grouped_quantifiers: Regex::new(r"([A-Z]{3}[0-9]{4})-([A-Z]{3}[0-9]{3})")?,
-        alternation_quantifiers: Regex::new(r"([A-Z]{2,3}[0-9]{3,4})|([0-9]{4}-[A-Z]{3})")?,
-        // Optimization showcase patterns
-        optimize_range_quantifier: Regex::new(r"a{2,4}")?,
-        optimize_multiple_quantifiers: Regex::new(r"[A-Z]{3}[0-9]{4}-[A-Z]{3}[0-9]{3}-[A-Z]{2}[0-9]{2}")?,
-        optimize_phone_quantifiers: Regex::new(r"[0-9]{3}-[0-9]{3}-[0-9]{4}")?,
-        optimize_large_quantifiers: Regex::new(r"[A-Z]{10,20}[0-9]{15,25}")?,
-        optimize_extreme_quantifiers: Regex::new(r"a{1}b{2}c{3}d{4}e{5}f{6}g{7}h{8}")?,
-        // Predefined character classes
-        predefined_digits: Regex::new(r"\d+")?,
-        predefined_word: Regex::new(r"\w+")?,
-    })
-}
-
-fn run_benchmark(
-    timer: &BenchmarkTimer,
-    results: &mut HashMap<String, BenchmarkResult>,
-    name: &str,
-    pattern: &Regex,
-    text: &str,
-    inner_iterations: usize,
-    bench_type: BenchType,
-) {
-    let result = match bench_type {
-        BenchType::IsMatch => {
-            // Use find() instead of is_match() to compute match boundaries,
-            // matching what Mojo's match_first returns (start + end positions)
-            timer.bench_function(|| {
-                for _ in 0..inner_iterations {
-                    black_box(pattern.find(black_box(text)));
-                }
-            })
-        }
-        BenchType::IsMatchBool => {
-            timer.bench_function(|| {
-                for _ in 0..inner_iterations {
-                    black_box(pattern.is_match(black_box(text)));
-                }
-            })
-        }
-        BenchType::Search => {
-            timer.bench_function(|| {
-                for _ in 0..inner_iterations {
-                    black_box(pattern.find(black_box(text)));
-                }
-            })
-        }
-        BenchType::FindAll => {
-            timer.bench_function(|| {
-                for _ in 0..inner_iterations {
-                    let matches: Vec<_> = pattern.find_iter(black_box(text)).collect();
-                    black_box(matches.len());
-                }
-            })
-        }
-    };
-
-    // Adjust time per operation by dividing by inner iterations
-    let adjusted_result = BenchmarkResult {
-        time_ns: result.time_ns / inner_iterations as f64,
-        time_ms: result.time_ms / inner_iterations as f64,
-        iterations: result.iterations * inner_iterations as u64,
-    };
+fn export_json_results(
+    benchmark_results: &BenchmarkResults,
+    path: &Path,
+    compress: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json_content = serde_json::to_string_pretty(benchmark_results)?;
+    archive::write(path, &json_content, compress)?;
 
-    results.insert(name.to_string(), adjusted_result);
-    println!("✓ {}", name);
-}
-
-fn run_sub_benchmark(
-    timer: &BenchmarkTimer,
-    results: &mut HashMap<String, BenchmarkResult>,
-    name: &str,
-    pattern: &Regex,
-    repl: &str,
-    text: &str,
-    inner_iterations: usize,
-) {
-    let result = timer.bench_function(|| {
-        for _ in 0..inner_iterations {
-            let replaced = pattern.replace_all(black_box(text), repl);
-            black_box(&replaced);
-        }
-    });
-
-    let adjusted_result = BenchmarkResult {
-        time_ns: result.time_ns / inner_iterations as f64,
-        time_ms: result.time_ms / inner_iterations as f64,
-        iterations: result.iterations * inner_iterations as u64,
-    };
+    println!("\n=== BENCHMARK COMPLETE ===");
+    println!("Results exported to: {}", path.display());
 
-    results.insert(name.to_string(), adjusted_result);
-    println!("✓ {}", name);
+    Ok(())
 }
 
-fn print_results_table(results: &HashMap<String, BenchmarkResult>) {
-    println!("| name                      | med (ms)              | iters  |");
-    println!("|---------------------------|-----------------------|--------|");
+#[cfg(feature = "sqlite")]
+fn record_history_db(benchmark_results: &BenchmarkResults, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let git_sha = history::git_sha();
+    let written = history_db::HistoryDb::open(path)?.insert(benchmark_results, git_sha.as_deref())?;
+    println!(
+        "{} results at {} recorded in: {}",
+        written,
+        git_sha.as_deref().unwrap_or("unknown commit"),
+        path
+    );
+    Ok(())
+}
 
-    let mut sorted_results: Vec<_> = results.iter().collect();
-    sorted_results.sort_by_key(|(name, _)| name.as_str());
+#[cfg(not(feature = "sqlite"))]
+fn record_history_db(_: &BenchmarkResults, _: &str) -> Result<(), Box<dyn std::error::Error>> {
+    Err(SQLITE_REQUIRED.into())
+}
 
-    for (name, result) in sorted_results {
-        println!("| {:<25} | {:>21.17} | {:>6} |",
-                 name, result.time_ms, result.iterations);
-    }
+/// Whether `trend` reads `path` as a `--history` JSON Lines file rather
+/// than a SQLite database
+fn is_jsonl(path: &str) -> bool {
+    path.ends_with(".jsonl")
 }
 
-fn export_json_results(results: &HashMap<String, BenchmarkResult>) -> Result<(), Box<dyn std::error::Error>> {
-    let benchmark_results = BenchmarkResults {
-        engine: "rust".to_string(),
-        timestamp: chrono::Utc::now().to_rfc3339(),
-        results: results.clone(),
+/// Print `benchmark`'s latest results from either history, oldest first,
+/// each with its change from the one before, then a sparkline of the times,
+/// their linear drift per run and per week, and the steps slower than
+/// `--max-regression`; with `--plot`, also chart them
+fn run_trend(history: &str, benchmark: &str, options: &Options) -> Result<(), Box<dyn std::error::Error>> {
+    let (engine, limit) = (options.engine.as_deref(), options.limit);
+    let points = if is_jsonl(history) {
+        trend::from_history(&history::load(history)?, benchmark, engine, limit)
+    } else {
+        load_trend_db(history, benchmark, engine, limit)?
     };
+    if points.is_empty() {
+        return Err(format!("no results for {} in {}", benchmark, history).into());
+    }
 
-    std::fs::create_dir_all("../results")?;
+    println!("=== {} over the last {} runs ===", benchmark, points.len());
+    println!("| commit        | engine     | timestamp           |      time (ns) |    delta |");
+    println!("|---------------|------------|---------------------|----------------|----------|");
+    let mut previous: Option<f64> = None;
+    for point in &points {
+        let delta = previous.map_or("-".to_string(), |prev| format!("{:+.1}%", (point.time_ns / prev - 1.0) * 100.0));
+        // Keep a `-dirty` suffix visible after shortening the sha
+        let commit = match point.git_sha.split_once('-') {
+            Some((sha, suffix)) => format!("{}-{}", &sha[..sha.len().min(7)], suffix),
+            None => point.git_sha[..point.git_sha.len().min(7)].to_string(),
+        };
+        // Date and time to the second is plenty to tell runs apart
+        let timestamp = point.timestamp.get(..19).unwrap_or(&point.timestamp);
+        println!(
+            "| {:<13} | {:<10} | {:<19} | {:>14.2} | {:>8} |",
+            commit, point.engine, timestamp, point.time_ns, delta
+        );
+        previous = Some(point.time_ns);
+    }
 
-    let json_content = serde_json::to_string_pretty(&benchmark_results)?;
-    std::fs::write("../results/rust_results.json", json_content)?;
+    let times: Vec<f64> = points.iter().map(|point| point.time_ns).collect();
+    println!();
+    println!("{} (oldest to latest)", trend::sparkline(&times));
+    if let Some(drift) = trend::drift(&points) {
+        let per_week = drift.pct_per_week.map_or(String::new(), |pct| format!(", {:+.2}% per week", pct));
+        println!("Drift: {:+.2}% per run{}", drift.pct_per_run, per_week);
+    }
+    let regressions = trend::step_regressions(&points, options.max_regression_pct);
+    if !regressions.is_empty() {
+        let commits: Vec<&str> = regressions
+            .iter()
+            .map(|&i| &points[i].git_sha[..points[i].git_sha.len().min(7)])
+            .collect();
+        println!("Slower by more than {:.1}% at: {}", options.max_regression_pct, commits.join(", "));
+    }
+    if options.plot {
+        let dir = options.output_dir.join(plot::PLOTS_DIR);
+        std::fs::create_dir_all(&dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+        let path = dir.join(format!("trend_{}.svg", plot::file_stem(benchmark)));
+        let title = format!("{} over the last {} runs", benchmark, points.len());
+        std::fs::write(&path, plot::time_series(&title, &points, &regressions))
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+        println!("Chart written to {}", path.display());
+    }
+    Ok(())
+}
 
-    println!("\n=== BENCHMARK COMPLETE ===");
-    println!("Results exported to: ../results/rust_results.json");
+/// `benchmark`'s latest results from a `--history-db` database
+#[cfg(feature = "sqlite")]
+fn load_trend_db(
+    database: &str,
+    benchmark: &str,
+    engine: Option<&str>,
+    limit: usize,
+) -> Result<Vec<trend::TrendPoint>, Box<dyn std::error::Error>> {
+    history_db::HistoryDb::open(database)?.trend(benchmark, engine, limit)
+}
 
-    Ok(())
+#[cfg(not(feature = "sqlite"))]
+fn load_trend_db(
+    _: &str,
+    _: &str,
+    _: Option<&str>,
+    _: usize,
+) -> Result<Vec<trend::TrendPoint>, Box<dyn std::error::Error>> {
+    Err(SQLITE_REQUIRED.into())
 }
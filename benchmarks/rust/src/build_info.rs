@@ -0,0 +1,93 @@
+//! How the harness binary was built and from which checkout
+//!
+//! Compiler, target, profile and dependency versions are baked in by
+//! `build.rs`; the git state is read when the run starts, from the
+//! checkout the harness was built from, wherever it's launched.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuildInfo {
+    /// `git describe --always --tags` of `HEAD`
+    pub git_describe: Option<String>,
+    /// Full hash of `HEAD`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git_sha: Option<String>,
+    /// The checkout had uncommitted changes to tracked files
+    pub git_dirty: bool,
+    /// `rustc -V`
+    pub rustc: String,
+    pub target: String,
+    /// Cargo profile (`release` or `debug`) and its opt-level
+    pub profile: String,
+    pub opt_level: String,
+    /// Enabled Cargo features of the harness, sorted
+    pub features: Vec<String>,
+    /// `RUSTFLAGS`, e.g. `-C target-cpu=native`
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub rustflags: String,
+    /// Locked versions of the benchmarked crates
+    pub regex_version: String,
+    pub regex_automata_version: String,
+}
+
+impl BuildInfo {
+    /// Build info of this binary, with the current git state
+    pub fn current() -> Self {
+        let features = env!("BENCH_BUILD_FEATURES");
+        BuildInfo {
+            git_describe: git(&["describe", "--always", "--tags"]),
+            git_sha: git(&["rev-parse", "HEAD"]),
+            git_dirty: git_dirty(),
+            rustc: env!("BENCH_BUILD_RUSTC").to_string(),
+            target: env!("BENCH_BUILD_TARGET").to_string(),
+            profile: env!("BENCH_BUILD_PROFILE").to_string(),
+            opt_level: env!("BENCH_BUILD_OPT_LEVEL").to_string(),
+            features: features.split(',').filter(|f| !f.is_empty()).map(str::to_string).collect(),
+            rustflags: env!("BENCH_BUILD_RUSTFLAGS").to_string(),
+            regex_version: env!("BENCH_BUILD_REGEX_VERSION").to_string(),
+            regex_automata_version: env!("BENCH_BUILD_REGEX_AUTOMATA_VERSION").to_string(),
+        }
+    }
+
+    /// One-line summary, e.g. for comparing two results files
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{}{} | {} | {} {} opt-level={} | regex {}",
+            self.git_describe.as_deref().unwrap_or("unknown commit"),
+            if self.git_dirty { "-dirty" } else { "" },
+            self.rustc,
+            self.target,
+            self.profile,
+            self.opt_level,
+            self.regex_version
+        );
+        if !self.features.is_empty() {
+            summary.push_str(&format!(" | features: {}", self.features.join(",")));
+        }
+        if !self.rustflags.is_empty() {
+            summary.push_str(&format!(" | RUSTFLAGS: {}", self.rustflags));
+        }
+        summary
+    }
+}
+
+/// Trimmed stdout of a successful `git` command, run in the checkout the
+/// harness was built from
+pub(crate) fn git(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Whether tracked files have uncommitted changes
+pub(crate) fn git_dirty() -> bool {
+    git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|status| !status.is_empty())
+}
@@ -0,0 +1,281 @@
+//! Measurement clocks for `BenchmarkTimer`
+//!
+//! The timer only ever subtracts two readings, so any monotonic counter
+//! works: wall time, process CPU time, the cycle counter, retired
+//! instructions, or a mock driven by the code under test. With a clock
+//! other than `wall` or `cpu`, the `*_ns` fields of the results are in that
+//! clock's unit.
+
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::time::Instant;
+
+/// Names accepted by [`select`]
+pub const CLOCK_NAMES: &[&str] = &["wall", "cpu", "tsc", "instructions"];
+
+/// A monotonic counter the timer measures with
+pub trait Clock: Debug {
+    /// Current reading; only differences between readings are meaningful
+    fn now(&self) -> u64;
+
+    /// Short name recorded with the results
+    fn name(&self) -> &'static str;
+
+    /// Unit of a reading
+    fn unit(&self) -> &'static str {
+        "ns"
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for Box<C> {
+    fn now(&self) -> u64 {
+        (**self).now()
+    }
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+    fn unit(&self) -> &'static str {
+        (**self).unit()
+    }
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> u64 {
+        (**self).now()
+    }
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+    fn unit(&self) -> &'static str {
+        (**self).unit()
+    }
+}
+
+/// Monotonic wall time (`Instant`), the default
+#[derive(Debug, Clone)]
+pub struct WallClock {
+    origin: Instant,
+}
+
+impl WallClock {
+    pub fn new() -> Self {
+        WallClock {
+            origin: Instant::now(),
+        }
+    }
+}
+
+impl Default for WallClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for WallClock {
+    fn now(&self) -> u64 {
+        self.origin.elapsed().as_nanos() as u64
+    }
+    fn name(&self) -> &'static str {
+        "wall"
+    }
+}
+
+/// CPU time consumed by this process, which ignores time spent descheduled
+#[cfg(unix)]
+#[derive(Debug, Clone, Default)]
+pub struct CpuClock;
+
+#[cfg(unix)]
+impl Clock for CpuClock {
+    fn now(&self) -> u64 {
+        let mut ts = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        // SAFETY: `ts` is a valid, writable timespec
+        unsafe { libc::clock_gettime(libc::CLOCK_PROCESS_CPUTIME_ID, &mut ts) };
+        ts.tv_sec as u64 * 1_000_000_000 + ts.tv_nsec as u64
+    }
+    fn name(&self) -> &'static str {
+        "cpu"
+    }
+}
+
+/// Hardware cycle counter (see [`crate::cycles`])
+#[derive(Debug, Clone, Default)]
+pub struct TscClock;
+
+impl Clock for TscClock {
+    fn now(&self) -> u64 {
+        crate::cycles::read().unwrap_or(0)
+    }
+    fn name(&self) -> &'static str {
+        "tsc"
+    }
+    fn unit(&self) -> &'static str {
+        "cycles"
+    }
+}
+
+/// Retired user-space instructions, via `perf_event_open`
+///
+/// Nearly noise-free, which makes small code-generation changes visible,
+/// but blind to cache misses and branch mispredictions. Hardware counters
+/// follow a single thread, so each thread reading the clock gets its own.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Default)]
+pub struct InstructionsClock;
+
+#[cfg(target_os = "linux")]
+impl InstructionsClock {
+    /// Fails if the counter can't be opened (no PMU, or perf_event_paranoid)
+    pub fn new() -> Result<Self, String> {
+        PerfCounter::open_instructions().map(|_| InstructionsClock)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Clock for InstructionsClock {
+    fn now(&self) -> u64 {
+        thread_local! {
+            static COUNTER: Option<PerfCounter> = PerfCounter::open_instructions().ok();
+        }
+        COUNTER.with(|counter| counter.as_ref().map_or(0, PerfCounter::read))
+    }
+    fn name(&self) -> &'static str {
+        "instructions"
+    }
+    fn unit(&self) -> &'static str {
+        "instructions"
+    }
+}
+
+/// A `perf_event_open` counter for the calling thread
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+struct PerfCounter {
+    fd: libc::c_int,
+}
+
+#[cfg(target_os = "linux")]
+impl PerfCounter {
+    fn open_instructions() -> Result<Self, String> {
+        const PERF_TYPE_HARDWARE: u32 = 0;
+        const PERF_COUNT_HW_INSTRUCTIONS: u64 = 1;
+        const EXCLUDE_KERNEL: u64 = 1 << 5;
+        const EXCLUDE_HV: u64 = 1 << 6;
+        const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+        /// Leading fields of `struct perf_event_attr`; the rest stays zeroed
+        #[repr(C)]
+        struct PerfEventAttr {
+            type_: u32,
+            size: u32,
+            config: u64,
+            sample_period: u64,
+            sample_type: u64,
+            read_format: u64,
+            flags: u64,
+            rest: [u64; 10],
+        }
+
+        let attr = PerfEventAttr {
+            type_: PERF_TYPE_HARDWARE,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config: PERF_COUNT_HW_INSTRUCTIONS,
+            sample_period: 0,
+            sample_type: 0,
+            read_format: 0,
+            flags: EXCLUDE_KERNEL | EXCLUDE_HV,
+            rest: [0; 10],
+        };
+        // SAFETY: `attr` outlives the call and is laid out as the kernel expects
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_perf_event_open,
+                &attr as *const PerfEventAttr,
+                0,  // this thread
+                -1, // any CPU
+                -1, // no group
+                PERF_FLAG_FD_CLOEXEC,
+            )
+        };
+        if fd < 0 {
+            return Err(format!(
+                "perf_event_open failed: {} (check /proc/sys/kernel/perf_event_paranoid)",
+                std::io::Error::last_os_error()
+            ));
+        }
+        Ok(PerfCounter {
+            fd: fd as libc::c_int,
+        })
+    }
+
+    fn read(&self) -> u64 {
+        let mut count = 0u64;
+        // SAFETY: reads exactly 8 bytes into `count`
+        unsafe { libc::read(self.fd, &mut count as *mut u64 as *mut libc::c_void, 8) };
+        count
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for PerfCounter {
+    fn drop(&mut self) {
+        // SAFETY: `fd` was opened by `open_instructions` and is closed only here
+        unsafe { libc::close(self.fd) };
+    }
+}
+
+/// Clock that only moves when told to, for deterministic tests of the timer
+///
+/// Each reading also advances it by `step`, which models the cost of
+/// reading the clock itself.
+#[derive(Debug, Default)]
+pub struct MockClock {
+    now: Cell<u64>,
+    step: u64,
+}
+
+impl MockClock {
+    pub fn new(step: u64) -> Self {
+        MockClock {
+            now: Cell::new(0),
+            step,
+        }
+    }
+
+    /// Move the clock forward, e.g. from inside a benchmarked closure
+    pub fn advance(&self, ticks: u64) {
+        self.now.set(self.now.get() + ticks);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> u64 {
+        let now = self.now.get();
+        self.now.set(now + self.step);
+        now
+    }
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+}
+
+/// Clock for a `--clock` name (see [`CLOCK_NAMES`])
+pub fn select(name: &str) -> Result<Box<dyn Clock + Send + Sync>, String> {
+    match name {
+        "wall" => Ok(Box::new(WallClock::new())),
+        #[cfg(unix)]
+        "cpu" => Ok(Box::new(CpuClock)),
+        "tsc" if crate::cycles::SUPPORTED => Ok(Box::new(TscClock)),
+        "tsc" => Err("the tsc clock needs --features cycles on x86_64 or aarch64".to_string()),
+        #[cfg(target_os = "linux")]
+        "instructions" => Ok(Box::new(InstructionsClock::new()?)),
+        _ => Err(format!(
+            "unsupported clock {:?} on this platform (choose from {})",
+            name,
+            CLOCK_NAMES.join(", ")
+        )),
+    }
+}
@@ -0,0 +1,335 @@
+//! Benchmark-by-benchmark comparison of two result sets
+
+use crate::engine_path::EnginePath;
+use crate::stats::{confidence_interval, mann_whitney_u};
+use crate::{family, Aggregates, BenchmarkResult, BenchmarkResults};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// Default significance level for flagging a delta
+pub const DEFAULT_ALPHA: f64 = 0.01;
+
+/// Default slowdown (percent) past which a benchmark counts as regressed
+pub const DEFAULT_MAX_REGRESSION_PCT: f64 = 10.0;
+
+/// Comparison of one benchmark present in both result sets
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub name: String,
+    /// Registration index in the baseline, if it was numbered
+    pub index: Option<usize>,
+    /// Category the benchmark was recorded under in the baseline
+    pub category: String,
+    /// Mojo engine path the benchmark exercises, from either side
+    pub engine_path: Option<EnginePath>,
+    /// Family of the benchmark (see [`family`])
+    pub family: String,
+    pub baseline_ns: f64,
+    pub candidate_ns: f64,
+    /// Samples each side took
+    pub baseline_iterations: u64,
+    pub candidate_iterations: u64,
+    /// Relative change of the candidate median, in percent (positive = slower)
+    pub delta_pct: f64,
+    /// Mann-Whitney U p-value, when both sides carry sample data
+    pub p_value: Option<f64>,
+    /// Whether the delta is statistically significant at the chosen alpha
+    pub significant: bool,
+    /// Whether the two runs' 95% confidence intervals of the mean overlap,
+    /// when both recorded a sample summary
+    pub ci_overlap: Option<bool>,
+}
+
+/// Compare every benchmark present in both `baseline` and `candidate`, in
+/// the baseline's registration order
+pub fn compare(
+    baseline: &BenchmarkResults,
+    candidate: &BenchmarkResults,
+    alpha: f64,
+) -> Vec<Comparison> {
+    let candidate_flat = candidate.flat();
+    let mut comparisons: Vec<Comparison> = baseline
+        .flat()
+        .into_iter()
+        .filter(|(_, base)| base.is_measured())
+        .filter_map(|(name, base)| {
+            let cand = candidate_flat.get(name).filter(|cand| cand.is_measured())?;
+            let p_value = match (&base.samples, &cand.samples) {
+                (Some(a), Some(b)) => mann_whitney_u(a, b),
+                _ => None,
+            };
+            let interval = |result: &BenchmarkResult| result.summary.as_ref().and_then(confidence_interval);
+            let ci_overlap = match (interval(base), interval(cand)) {
+                (Some((base_low, base_high)), Some((cand_low, cand_high))) => {
+                    Some(base_low <= cand_high && cand_low <= base_high)
+                }
+                _ => None,
+            };
+            let delta_pct = if base.time_ns > 0.0 {
+                (cand.time_ns / base.time_ns - 1.0) * 100.0
+            } else {
+                0.0
+            };
+            let category = baseline.category_of(name).unwrap_or(crate::UNCATEGORIZED);
+            Some(Comparison {
+                name: name.to_string(),
+                index: base.index,
+                category: category.to_string(),
+                engine_path: base.engine_path.or(cand.engine_path),
+                family: family(category, name, base).to_string(),
+                baseline_ns: base.time_ns,
+                candidate_ns: cand.time_ns,
+                baseline_iterations: base.iterations,
+                candidate_iterations: cand.iterations,
+                delta_pct,
+                p_value,
+                significant: p_value.is_some_and(|p| p < alpha),
+                ci_overlap,
+            })
+        })
+        .collect();
+    comparisons.sort_by(|a, b| {
+        (a.index.unwrap_or(usize::MAX), &a.name).cmp(&(b.index.unwrap_or(usize::MAX), &b.name))
+    });
+    comparisons
+}
+
+/// Benchmarks the two sides ran on different inputs (pattern, haystack
+/// size or operation), one description each, by name. Only fields both
+/// sides recorded are checked.
+pub fn input_mismatches(baseline: &BenchmarkResults, candidate: &BenchmarkResults) -> Vec<String> {
+    let candidate_flat = candidate.flat();
+    let mut mismatches: Vec<String> = baseline
+        .flat()
+        .into_iter()
+        .filter_map(|(name, base)| {
+            let cand = candidate_flat.get(name)?;
+            let mut differences = Vec::new();
+            if let (Some(a), Some(b)) = (&base.pattern, &cand.pattern) {
+                if a != b {
+                    differences.push(format!("pattern {:?} vs {:?}", a, b));
+                }
+            }
+            if let (Some(a), Some(b)) = (base.haystack_len, cand.haystack_len) {
+                if a != b {
+                    differences.push(format!("haystack {} vs {} bytes", a, b));
+                }
+            }
+            if let (Some(a), Some(b)) = (&base.operation, &cand.operation) {
+                if a != b {
+                    differences.push(format!("operation {} vs {}", a, b));
+                }
+            }
+            (!differences.is_empty()).then(|| format!("{}: {}", name, differences.join(", ")))
+        })
+        .collect();
+    mismatches.sort();
+    mismatches
+}
+
+impl Comparison {
+    /// Candidate time over baseline time (below 1.0 = faster)
+    pub fn ratio(&self) -> Option<f64> {
+        (self.baseline_ns > 0.0 && self.candidate_ns > 0.0).then(|| self.candidate_ns / self.baseline_ns)
+    }
+
+    /// Whether the delta stands out from the noise: significant by
+    /// Mann-Whitney when both runs kept samples, otherwise with disjoint
+    /// confidence intervals. Files recording neither (e.g. other harnesses')
+    /// can't tell, and count every delta.
+    pub fn beyond_noise(&self) -> bool {
+        match (self.p_value, self.ci_overlap) {
+            (Some(_), _) => self.significant,
+            (None, Some(overlap)) => !overlap,
+            (None, None) => true,
+        }
+    }
+}
+
+/// Scale `baseline`'s times to `candidate`'s machine by how long each took
+/// on the `calibration` benchmark, so runs from different hardware can be
+/// loosely compared: deltas then read relative to that benchmark, which
+/// itself shows none. Returns the factor the baseline's times were
+/// multiplied by.
+pub fn calibrate(
+    baseline: &mut BenchmarkResults,
+    candidate: &BenchmarkResults,
+    calibration: &str,
+) -> Result<f64, String> {
+    let time_of = |results: &BenchmarkResults, side: &str| {
+        let flat = results.flat();
+        flat.get(calibration)
+            .filter(|result| result.is_measured() && result.time_ns > 0.0)
+            .map(|result| result.time_ns)
+            .ok_or_else(|| format!("the {} has no timing of calibration benchmark {:?}", side, calibration))
+    };
+    let factor = time_of(candidate, "candidate")? / time_of(baseline, "baseline")?;
+    for result in baseline.results.values_mut().flat_map(|benches| benches.values_mut()) {
+        if !result.is_measured() {
+            continue;
+        }
+        result.time_ns *= factor;
+        result.time_ms *= factor;
+        result.throughput_mb_s = result.throughput_mb_s.map(|mb_s| mb_s / factor);
+        result.tokens_per_sec = result.tokens_per_sec.map(|rate| rate / factor);
+        for times in [&mut result.samples, &mut result.round_medians_ns].into_iter().flatten() {
+            times.iter_mut().for_each(|time| *time *= factor);
+        }
+        result.summary = result.summary.as_ref().map(|summary| summary.scaled(1.0 / factor));
+        result.histogram = result.histogram.as_ref().map(|histogram| histogram.scaled(1.0 / factor));
+    }
+    baseline.aggregates = Aggregates::from_results(&baseline.results);
+    Ok(factor)
+}
+
+/// The `n` largest slowdowns and the `n` largest speedups, each largest
+/// first
+pub fn top_changes(comparisons: &[Comparison], n: usize) -> (Vec<&Comparison>, Vec<&Comparison>) {
+    let mut regressions: Vec<_> = comparisons.iter().filter(|c| c.delta_pct > 0.0).collect();
+    regressions.sort_by(|a, b| b.delta_pct.total_cmp(&a.delta_pct));
+    regressions.truncate(n);
+    let mut improvements: Vec<_> = comparisons.iter().filter(|c| c.delta_pct < 0.0).collect();
+    improvements.sort_by(|a, b| a.delta_pct.total_cmp(&b.delta_pct));
+    improvements.truncate(n);
+    (regressions, improvements)
+}
+
+/// Per-benchmark regression limits (percent), overriding the default for
+/// benchmarks known to be noisier or to trade speed for something else. A
+/// key ending in `*` covers every benchmark with that prefix.
+pub type Thresholds = HashMap<String, f64>;
+
+/// Load regression thresholds from a `.toml` file of `name = percent` pairs,
+/// or from a JSON object such as `{"dfa_dot_phone": 25.0}`
+pub fn load_thresholds(path: &str) -> Result<Thresholds, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path, e))?;
+    let thresholds: Thresholds = if path.ends_with(".toml") {
+        toml::from_str(&content).map_err(|e| format!("failed to parse {}: {}", path, e))?
+    } else {
+        serde_json::from_str(&content).map_err(|e| format!("failed to parse {}: {}", path, e))?
+    };
+    if let Some((name, max)) = thresholds.iter().find(|(_, max)| !max.is_finite() || **max < 0.0) {
+        return Err(format!("{}: threshold for {} must be a non-negative percentage, got {}", path, name, max).into());
+    }
+    Ok(thresholds)
+}
+
+/// `name`'s entry in `thresholds`: its own, else the longest `prefix*`
+/// pattern matching it
+pub fn threshold_for(thresholds: &Thresholds, name: &str) -> Option<f64> {
+    thresholds.get(name).copied().or_else(|| {
+        thresholds
+            .iter()
+            .filter_map(|(key, max)| Some((key.strip_suffix('*')?, *max)))
+            .filter(|(prefix, _)| name.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, max)| max)
+    })
+}
+
+/// A benchmark slower than its threshold allows
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub name: String,
+    pub delta_pct: f64,
+    /// Threshold it broke (percent)
+    pub max_pct: f64,
+    pub baseline_ns: f64,
+    pub candidate_ns: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:+.1}% vs baseline ({:.1} -> {:.1} ns, max +{:.1}%)",
+            self.name, self.delta_pct, self.baseline_ns, self.candidate_ns, self.max_pct
+        )
+    }
+}
+
+/// Benchmarks slower by more than their threshold (from `thresholds`, else
+/// `max_pct`) and [beyond noise](Comparison::beyond_noise), in comparison
+/// order: a large delta on a high-variance benchmark alone doesn't fail a
+/// gate.
+pub fn regressions(comparisons: &[Comparison], max_pct: f64, thresholds: &Thresholds) -> Vec<Regression> {
+    comparisons
+        .iter()
+        .filter_map(|c| {
+            let max_pct = threshold_for(thresholds, &c.name).unwrap_or(max_pct);
+            (c.delta_pct > max_pct && c.beyond_noise()).then(|| Regression {
+                name: c.name.clone(),
+                delta_pct: c.delta_pct,
+                max_pct,
+                baseline_ns: c.baseline_ns,
+                candidate_ns: c.candidate_ns,
+            })
+        })
+        .collect()
+}
+
+/// Relative importance of each category in the overall score; categories
+/// missing from the map weigh 1.0
+pub type CategoryWeights = HashMap<String, f64>;
+
+/// Load category weights from a JSON object such as
+/// `{"global": 3.0, "is_match": 0.5}`
+pub fn load_weights(path: &str) -> Result<CategoryWeights, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path, e))?;
+    let weights: CategoryWeights = serde_json::from_str(&content)
+        .map_err(|e| format!("failed to parse {}: {}", path, e))?;
+    if let Some((category, weight)) = weights.iter().find(|(_, w)| !w.is_finite() || **w < 0.0) {
+        return Err(format!("{}: weight for {} must be a non-negative number, got {}", path, category, weight).into());
+    }
+    Ok(weights)
+}
+
+/// Geometric mean of the candidate/baseline ratios, every benchmark counting
+/// equally
+pub fn geomean_ratio(comparisons: &[Comparison]) -> Option<f64> {
+    weighted_geomean_ratio(comparisons, &CategoryWeights::new())
+}
+
+/// Geometric mean of the candidate/baseline ratios with each benchmark
+/// weighted by its category, so the score tracks a real workload mix
+/// rather than how many micro-benchmarks each category happens to have
+pub fn weighted_geomean_ratio(comparisons: &[Comparison], weights: &CategoryWeights) -> Option<f64> {
+    let (log_sum, weight_sum) = comparisons
+        .iter()
+        .filter_map(|c| Some((c.ratio()?, weights.get(&c.category).copied().unwrap_or(1.0))))
+        .fold((0.0, 0.0), |(log_sum, weight_sum), (ratio, weight)| {
+            (log_sum + weight * ratio.ln(), weight_sum + weight)
+        });
+    (weight_sum > 0.0).then(|| (log_sum / weight_sum).exp())
+}
+
+/// Geomean candidate/baseline ratio and benchmark count of each tagged
+/// engine path, so a regression shows up against the subsystem it hit
+pub fn geomean_ratio_by_path(comparisons: &[Comparison]) -> BTreeMap<EnginePath, (f64, usize)> {
+    geomean_ratio_by(comparisons, |c| c.engine_path)
+}
+
+/// Geomean candidate/baseline ratio and benchmark count of each family
+/// (`literal`, `dfa`, `simd`, ...), so a regression of a whole path stands
+/// out from one of a single benchmark
+pub fn geomean_ratio_by_family(comparisons: &[Comparison]) -> BTreeMap<String, (f64, usize)> {
+    geomean_ratio_by(comparisons, |c| Some(c.family.clone()))
+}
+
+fn geomean_ratio_by<K: Ord>(
+    comparisons: &[Comparison],
+    key: impl Fn(&Comparison) -> Option<K>,
+) -> BTreeMap<K, (f64, usize)> {
+    let mut groups: BTreeMap<K, Vec<Comparison>> = BTreeMap::new();
+    for c in comparisons {
+        if let Some(key) = key(c) {
+            groups.entry(key).or_default().push(c.clone());
+        }
+    }
+    groups
+        .into_iter()
+        .filter_map(|(key, comparisons)| Some((key, (geomean_ratio(&comparisons)?, comparisons.len()))))
+        .collect()
+}
@@ -0,0 +1,345 @@
+//! Cross-engine comparison of the Rust, Mojo and Python harnesses' results
+//!
+//! Usage: compare_results [--results-dir DIR] [--rust FILE] [--mojo FILE] [--python FILE] [--engine NAME=FILE]...
+//!                        [--scoreboard] [--strict] [--format table|csv] [--plots]
+//!
+//! Loads `rust_results.json`, `mojo_results.json` and `python_results.json`
+//! from the results directory (or the files given), joins them by benchmark
+//! name and prints each benchmark's times with the Mojo/Rust and
+//! Mojo/Python ratios, then the geomean of each ratio. A ratio above 1 means
+//! Mojo is slower. With three or more engines, a matrix of the geomean
+//! speedup of every engine over every other follows. `--engine` adds more
+//! engines (e.g. `pcre2=pcre2_results.json`) as extra columns. Benchmarks
+//! the engines report different match counts for are flagged and left out
+//! of every geomean, since one of them computed the wrong answer. Benchmarks
+//! some engines lack are listed at the end; with `--strict` they fail the
+//! run, so harnesses can't drift apart unnoticed.
+//!
+//! `--format csv` prints the times and every pairwise ratio as CSV instead,
+//! for spreadsheets, with the notes on mismatched and missing benchmarks on
+//! stderr.
+//!
+//! `--plots` also writes SVG charts to `plots/` in the results directory: a
+//! grouped bar chart per category and a scatter plot of each ratio.
+//!
+//! With `--scoreboard` it instead prints every engine's time relative to the
+//! fastest engine on each benchmark (1.00x), then each engine's win count
+//! and geomean.
+
+use mojo_regex_rust_bench::cross_engine::{self, Row};
+use mojo_regex_rust_bench::plot;
+use mojo_regex_rust_bench::summary::format_ns;
+use mojo_regex_rust_bench::{results_dir, BenchmarkResults};
+use std::path::{Path, PathBuf};
+
+/// Built-in engines in column order; each one's results default to
+/// `<engine>_results.json` in the results directory, as do `--engine` ones
+const ENGINES: [&str; 3] = ["rust", "mojo", "python"];
+
+const RUST: usize = 0;
+const MOJO: usize = 1;
+const PYTHON: usize = 2;
+
+/// Ratios printed, as (numerator, denominator) engine indices
+const RATIOS: [(usize, usize); 2] = [(MOJO, RUST), (MOJO, PYTHON)];
+
+/// Every pairwise ratio of the built-in engines, for CSV output; each
+/// `--engine` adds its ratios over the engines before it
+const CSV_RATIOS: [(usize, usize); 3] = [(MOJO, RUST), (MOJO, PYTHON), (PYTHON, RUST)];
+
+const USAGE: &str = "usage: compare_results [--results-dir DIR] [--rust FILE] [--mojo FILE] [--python FILE] \
+                     [--engine NAME=FILE]... [--scoreboard] [--strict] [--format table|csv] [--plots]";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut results_dir = results_dir();
+    let mut names: Vec<String> = ENGINES.iter().map(|engine| engine.to_string()).collect();
+    let mut files: Vec<Option<PathBuf>> = vec![None; ENGINES.len()];
+    let mut scoreboard = false;
+    let mut strict = false;
+    let mut csv = false;
+    let mut plots = false;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--scoreboard" => {
+                scoreboard = true;
+                continue;
+            }
+            "--strict" => {
+                strict = true;
+                continue;
+            }
+            "--plots" => {
+                plots = true;
+                continue;
+            }
+            _ => {}
+        }
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        let value = match inline.or_else(|| args.next()) {
+            Some(value) if flag.starts_with("--") => value,
+            _ => return Err(USAGE.into()),
+        };
+        match flag.as_str() {
+            "--results-dir" => results_dir = PathBuf::from(value),
+            "--rust" => files[RUST] = Some(PathBuf::from(value)),
+            "--mojo" => files[MOJO] = Some(PathBuf::from(value)),
+            "--python" => files[PYTHON] = Some(PathBuf::from(value)),
+            "--engine" => {
+                let (name, file) = value
+                    .split_once('=')
+                    .filter(|(name, file)| !name.is_empty() && !file.is_empty())
+                    .ok_or_else(|| format!("--engine expects NAME=FILE, got {:?}", value))?;
+                match names.iter().position(|known| known == name) {
+                    Some(engine) => files[engine] = Some(PathBuf::from(file)),
+                    None => {
+                        names.push(name.to_string());
+                        files.push(Some(PathBuf::from(file)));
+                    }
+                }
+            }
+            "--format" => {
+                csv = match value.as_str() {
+                    "table" => false,
+                    "csv" => true,
+                    other => return Err(format!("unknown --format {:?} (expected table or csv)", other).into()),
+                }
+            }
+            _ => return Err(format!("unknown argument: {}\n{}", flag, USAGE).into()),
+        }
+    }
+
+    if csv && scoreboard {
+        return Err("--scoreboard is only printed as a table".into());
+    }
+
+    // A missing harness leaves its column empty rather than failing the rest
+    let mut loaded: Vec<Option<BenchmarkResults>> = Vec::new();
+    for (engine, file) in names.iter().zip(files) {
+        let path = file.unwrap_or_else(|| results_dir.join(format!("{}_results.json", engine)));
+        if !path.exists() {
+            eprintln!("No {} results at {}", engine, path.display());
+            loaded.push(None);
+            continue;
+        }
+        loaded.push(Some(BenchmarkResults::load(&path.to_string_lossy())?));
+    }
+    if loaded[MOJO].is_none() || loaded.iter().flatten().count() < 2 {
+        return Err("need the Mojo results and at least one of Rust's or Python's".into());
+    }
+
+    let engines: Vec<Option<&BenchmarkResults>> = loaded.iter().map(Option::as_ref).collect();
+    let rows = cross_engine::join(&engines);
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    if csv {
+        let mut ratios = CSV_RATIOS.to_vec();
+        ratios.extend((ENGINES.len()..names.len()).flat_map(|extra| (0..extra).map(move |other| (extra, other))));
+        print!("{}", cross_engine::csv(&rows, &names, &ratios));
+    } else if scoreboard {
+        print_scoreboard(&rows, &names);
+    } else {
+        print_table(&rows, &names);
+        println!();
+        for (numerator, denominator) in RATIOS {
+            if loaded[denominator].is_none() {
+                continue;
+            }
+            let label = format!("{}/{}", title(names[numerator]), title(names[denominator]));
+            match cross_engine::geomean_ratio(&rows, numerator, denominator) {
+                Some((ratio, count)) => println!("{} geomean: {:.3}x over {} benchmarks", label, ratio, count),
+                None => println!("{} geomean: no benchmarks in common", label),
+            }
+        }
+        if loaded.iter().flatten().count() >= 3 {
+            print_matrix(&rows, &names, &engines);
+        }
+    }
+
+    if plots {
+        let dir = results_dir.join(plot::PLOTS_DIR);
+        let written = write_plots(&dir, &rows, &names, &engines)?;
+        let note = format!("{} charts written to {}", written, dir.display());
+        if csv {
+            eprintln!("{}", note);
+        } else {
+            println!("\n{}", note);
+        }
+    }
+
+    // Timing a wrong answer is meaningless: the geomeans already leave these out
+    let disagreeing: Vec<&Row> = rows.iter().filter(|row| row.disagrees()).collect();
+    if !disagreeing.is_empty() {
+        let mut note = format!(
+            "✗ {} benchmarks report different match counts across engines, left out of the geomeans:",
+            disagreeing.len()
+        );
+        for row in disagreeing {
+            let counts: Vec<String> = (row.match_counts.iter().zip(&names))
+                .filter_map(|(count, name)| Some(format!("{} {}", name, (*count)?)))
+                .collect();
+            note += &format!("\n  {}: {}", row.name, counts.join(", "));
+        }
+        if csv {
+            eprintln!("{}", note);
+        } else {
+            println!("\n{}", note);
+        }
+    }
+
+    let missing = cross_engine::missing(&engines);
+    for (engine, lacking) in missing.iter().enumerate().filter(|(_, lacking)| !lacking.is_empty()) {
+        let note = format!(
+            "⚠ {} lacks {} benchmarks other engines have: {}",
+            title(names[engine]),
+            lacking.len(),
+            lacking.join(", ")
+        );
+        // Keep the CSV on stdout clean
+        if csv {
+            eprintln!("{}", note);
+        } else {
+            println!("\n{}", note);
+        }
+    }
+    let mismatched = missing.iter().map(Vec::len).sum::<usize>();
+    if strict && mismatched > 0 {
+        return Err(format!("--strict: {} benchmarks missing from some engine", mismatched).into());
+    }
+    Ok(())
+}
+
+fn title(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map(|first| first.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+fn print_table(rows: &[Row], names: &[&str]) {
+    let fmt_time = |time: Option<f64>| time.map_or("-".to_string(), format_ns);
+    let fmt_ratio = |ratio: Option<f64>| ratio.map_or("-".to_string(), |r| format!("{:.2}x", r));
+    let mut header = format!("| {:<35} |", "name");
+    let mut rule = format!("|{}|", "-".repeat(37));
+    for name in names {
+        header += &format!(" {:>10} |", name);
+        rule += "-----------:|";
+    }
+    for &(n, d) in &RATIOS {
+        header += &format!(" {:>11} |", format!("{}/{}", title(names[n]), title(names[d])));
+        rule += "------------:|";
+    }
+    println!("{}\n{}", header, rule);
+    for row in rows {
+        let mut line = format!("| {:<35} |", row.name);
+        for &time in &row.times_ns {
+            line += &format!(" {:>10} |", fmt_time(time));
+        }
+        for &(n, d) in &RATIOS {
+            line += &format!(" {:>11} |", fmt_ratio(row.ratio(n, d)));
+        }
+        println!("{}", line);
+    }
+}
+
+/// Geomean speedup of each engine (rows) over each other (columns)
+fn print_matrix(rows: &[Row], names: &[&str], engines: &[Option<&BenchmarkResults>]) {
+    let present: Vec<usize> = (0..names.len()).filter(|&engine| engines[engine].is_some()).collect();
+    let matrix = cross_engine::speedup_matrix(rows, names.len());
+    println!("\nGeomean speedup of each row's engine over each column's (above 1.00x: faster)");
+    let mut header = format!("| {:<8} |", "");
+    let mut rule = "|----------|".to_string();
+    for &engine in &present {
+        header += &format!(" {:>8} |", names[engine]);
+        rule += "---------:|";
+    }
+    println!("{}\n{}", header, rule);
+    for &i in &present {
+        let mut line = format!("| {:<8} |", names[i]);
+        for &j in &present {
+            let cell = matrix[i][j].map_or("-".to_string(), |speedup| format!("{:.2}x", speedup));
+            line += &format!(" {:>8} |", cell);
+        }
+        println!("{}", line);
+    }
+}
+
+fn print_scoreboard(rows: &[Row], names: &[&str]) {
+    let fmt_score = |score: Option<f64>| score.map_or("-".to_string(), |s| format!("{:.2}x", s));
+    let mut header = format!("| {:<35} |", "name");
+    let mut rule = format!("|{}|", "-".repeat(37));
+    for name in names {
+        header += &format!(" {:>8} |", name);
+        rule += "---------:|";
+    }
+    println!("{}\n{}", header, rule);
+    for row in rows {
+        // Nothing to rank with a single engine
+        let Some(normalized) = row.normalized() else { continue };
+        let mut line = format!("| {:<35} |", row.name);
+        for &score in &normalized {
+            line += &format!(" {:>8} |", fmt_score(score));
+        }
+        println!("{}", line);
+    }
+
+    println!();
+    for (engine, score) in cross_engine::scoreboard(rows).iter().enumerate() {
+        if score.contested == 0 {
+            continue;
+        }
+        println!(
+            "{:<7} {:>3} wins of {:>3}, geomean {} of the fastest",
+            title(names[engine]),
+            score.wins,
+            score.contested,
+            fmt_score(score.geomean)
+        );
+    }
+}
+
+/// Write a grouped bar chart per category and a scatter plot per ratio to
+/// `dir`; returns how many files were written
+fn write_plots(
+    dir: &Path,
+    rows: &[Row],
+    names: &[&str],
+    engines: &[Option<&BenchmarkResults>],
+) -> Result<usize, Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("failed to create {}: {}", dir.display(), e))?;
+    let write = |name: String, svg: String| {
+        let path = dir.join(name);
+        std::fs::write(&path, svg).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+    };
+    let categories = cross_engine::categories(engines);
+    // Categories in the order their first benchmark appears in the rows
+    let mut grouped: Vec<(&str, Vec<&Row>)> = Vec::new();
+    for row in rows.iter().filter(|row| row.times_ns.iter().any(Option::is_some)) {
+        let category = categories.get(&row.name).map_or("other", String::as_str);
+        match grouped.iter_mut().find(|(name, _)| *name == category) {
+            Some((_, members)) => members.push(row),
+            None => grouped.push((category, vec![row])),
+        }
+    }
+    let mut written = 0;
+    for (category, members) in &grouped {
+        // Category names come from the results files: keep them path-safe
+        write(format!("bars_{}.svg", plot::file_stem(category)), plot::grouped_bars(category, members, names))?;
+        written += 1;
+    }
+    for (numerator, denominator) in RATIOS {
+        if engines[numerator].is_none() || engines[denominator].is_none() {
+            continue;
+        }
+        let title = format!(
+            "{}/{} by {} time",
+            title(names[numerator]),
+            title(names[denominator]),
+            names[denominator]
+        );
+        let svg = plot::ratio_scatter(&title, rows, numerator, denominator);
+        write(format!("ratio_{}_{}.svg", names[numerator], names[denominator]), svg)?;
+        written += 1;
+    }
+    Ok(written)
+}
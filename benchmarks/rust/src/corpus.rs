@@ -0,0 +1,54 @@
+//! Parallel haystacks in several scripts with identical match counts
+//!
+//! Every block is a language-specific filler sentence followed by the same
+//! ASCII payload. The fillers contain no digits, `@` or the payload's
+//! literal, so patterns aimed at the payload find exactly the same matches
+//! in each language and the time difference is the cost of scanning the
+//! surrounding script.
+
+/// Script of the filler text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// ASCII only
+    English,
+    /// Accented Latin-1 range
+    European,
+    /// Mostly CJK ideographs and Hangul (3-byte UTF-8)
+    Cjk,
+}
+
+/// Every language, English (the baseline) first
+pub const LANGUAGES: [Language; 3] = [Language::English, Language::European, Language::Cjk];
+
+/// Shared by every language; carries all the matches
+const PAYLOAD: &str = " invoice 20240117 sent to billing@example.com; ";
+
+impl Language {
+    /// Benchmark name suffix and variant label
+    pub fn suffix(self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::European => "eu",
+            Language::Cjk => "cjk",
+        }
+    }
+
+    fn filler(self) -> &'static str {
+        match self {
+            Language::English => {
+                "The quick brown fox jumps over the lazy dog while the committee reviews the plan."
+            }
+            Language::European => {
+                "Le garçon très âgé mangeait une crème brûlée près du château; Müller grüßte Ødegård."
+            }
+            Language::Cjk => "東京の会議では新しい計画について議論しました。北京和上海的团队也参加了讨论。서울 팀도 함께했습니다.",
+        }
+    }
+}
+
+/// `blocks` repetitions of `language`'s filler, each followed by the
+/// shared payload
+pub fn parallel_text(language: Language, blocks: usize) -> String {
+    let block = format!("{}{}", language.filler(), PAYLOAD);
+    block.repeat(blocks)
+}
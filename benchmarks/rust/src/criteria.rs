@@ -0,0 +1,154 @@
+//! Non-timing success criteria a benchmark can declare
+//!
+//! Criteria are stored with each result, so the `gate` command can enforce
+//! memory and throughput budgets from a results file alone, the same way
+//! `compare` guards latency.
+
+use crate::{BenchmarkResult, BenchmarkResults};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Budgets a benchmark must stay within; unset fields aren't checked
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Criteria {
+    /// Most heap allocations allowed per operation, once warmed up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_allocations: Option<f64>,
+    /// Largest process peak RSS allowed while the benchmark runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_peak_rss_bytes: Option<u64>,
+    /// Lowest haystack throughput allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_throughput_mb_s: Option<f64>,
+}
+
+impl Criteria {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_allocations(mut self, allocations: f64) -> Self {
+        self.max_allocations = Some(allocations);
+        self
+    }
+
+    pub fn max_peak_rss_mib(mut self, mib: u64) -> Self {
+        self.max_peak_rss_bytes = Some(mib * 1024 * 1024);
+        self
+    }
+
+    pub fn min_throughput_mb_s(mut self, mb_s: f64) -> Self {
+        self.min_throughput_mb_s = Some(mb_s);
+        self
+    }
+}
+
+/// A criterion a benchmark failed, or couldn't be checked against
+#[derive(Debug, Clone, PartialEq)]
+pub enum Violation {
+    TooManyAllocations { name: String, allocations: f64, max: f64 },
+    PeakRssTooHigh { name: String, bytes: u64, max: u64 },
+    ThroughputTooLow { name: String, mb_s: f64, min: f64 },
+    /// The operation reported the wrong number of matches, or a number that
+    /// varied between samples (`expected` is `None` if none was declared)
+    WrongMatchCount { name: String, observed: usize, expected: Option<usize> },
+    /// The result lacks the measurement a criterion needs (e.g. the
+    /// counting allocator wasn't installed, or the benchmark timed out)
+    Unmeasured { name: String, criterion: &'static str },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::TooManyAllocations { name, allocations, max } => {
+                write!(f, "{}: {:.1} allocations per op (max {})", name, allocations, max)
+            }
+            Violation::PeakRssTooHigh { name, bytes, max } => write!(
+                f,
+                "{}: peak RSS {:.1} MiB (max {:.1} MiB)",
+                name,
+                *bytes as f64 / (1024.0 * 1024.0),
+                *max as f64 / (1024.0 * 1024.0)
+            ),
+            Violation::ThroughputTooLow { name, mb_s, min } => {
+                write!(f, "{}: {:.1} MB/s (min {:.1} MB/s)", name, mb_s, min)
+            }
+            Violation::WrongMatchCount { name, observed, expected: Some(expected) } => {
+                write!(f, "{}: reported {} matches (expected {})", name, observed, expected)
+            }
+            Violation::WrongMatchCount { name, observed, expected: None } => {
+                write!(f, "{}: match count varied between samples (as low as {})", name, observed)
+            }
+            Violation::Unmeasured { name, criterion } => {
+                write!(f, "{}: {} was not measured", name, criterion)
+            }
+        }
+    }
+}
+
+/// Check one result against its declared criteria, and that it reported
+/// the right number of matches. Skipped benchmarks are exempt; a timed-out
+/// one fails every criterion it declared.
+pub fn check(name: &str, result: &BenchmarkResult) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    if let (Some(false), Some(observed)) = (result.correct, result.observed_matches) {
+        violations.push(Violation::WrongMatchCount {
+            name: name.to_string(),
+            observed,
+            expected: result.expected_matches,
+        });
+    }
+    let Some(criteria) = &result.criteria else {
+        return violations;
+    };
+    if result.skipped {
+        return violations;
+    }
+    let unmeasured = |criterion| Violation::Unmeasured {
+        name: name.to_string(),
+        criterion,
+    };
+    if let Some(max) = criteria.max_allocations {
+        match result.allocations {
+            Some(allocations) if allocations > max => violations.push(Violation::TooManyAllocations {
+                name: name.to_string(),
+                allocations,
+                max,
+            }),
+            Some(_) => {}
+            None => violations.push(unmeasured("max_allocations")),
+        }
+    }
+    if let Some(max) = criteria.max_peak_rss_bytes {
+        match result.peak_rss_bytes {
+            Some(bytes) if bytes > max => violations.push(Violation::PeakRssTooHigh {
+                name: name.to_string(),
+                bytes,
+                max,
+            }),
+            Some(_) => {}
+            None => violations.push(unmeasured("max_peak_rss_bytes")),
+        }
+    }
+    if let Some(min) = criteria.min_throughput_mb_s {
+        match result.throughput_mb_s {
+            Some(mb_s) if mb_s < min => violations.push(Violation::ThroughputTooLow {
+                name: name.to_string(),
+                mb_s,
+                min,
+            }),
+            Some(_) => {}
+            None => violations.push(unmeasured("min_throughput_mb_s")),
+        }
+    }
+    violations
+}
+
+/// Every criterion violation in `results`, sorted by benchmark name
+pub fn evaluate(results: &BenchmarkResults) -> Vec<Violation> {
+    let mut flat: Vec<_> = results.flat().into_iter().collect();
+    flat.sort_by_key(|(name, _)| *name);
+    flat.into_iter()
+        .flat_map(|(name, result)| check(name, result))
+        .collect()
+}
@@ -0,0 +1,132 @@
+//! Results in Criterion's on-disk layout, so `critcmp` and other Criterion
+//! tooling can read them
+//!
+//! Each benchmark gets `<dir>/<category>/<name>/<baseline>/benchmark.json`
+//! and `estimates.json`, as `cargo bench` would write under
+//! `target/criterion`. Criterion bootstraps its confidence intervals; these
+//! use the normal approximation from the recorded moments instead, and
+//! collapse to the point estimate when the moments weren't recorded.
+
+use crate::{BenchmarkResult, BenchmarkResults};
+use serde::Serialize;
+use std::path::Path;
+
+/// Baseline directory Criterion writes the latest run to
+pub const DEFAULT_BASELINE: &str = "new";
+
+/// Confidence level of the exported intervals
+const CONFIDENCE_LEVEL: f64 = 0.95;
+const Z_95: f64 = 1.959_964;
+
+#[derive(Serialize)]
+struct Benchmark<'a> {
+    group_id: &'a str,
+    function_id: &'a str,
+    value_str: Option<&'a str>,
+    throughput: Option<Throughput>,
+    full_id: String,
+    directory_name: String,
+    title: String,
+}
+
+#[derive(Serialize)]
+enum Throughput {
+    Bytes(u64),
+}
+
+#[derive(Serialize)]
+struct ConfidenceInterval {
+    confidence_level: f64,
+    lower_bound: f64,
+    upper_bound: f64,
+}
+
+#[derive(Serialize)]
+struct Estimate {
+    confidence_interval: ConfidenceInterval,
+    point_estimate: f64,
+    standard_error: f64,
+}
+
+impl Estimate {
+    fn new(point_estimate: f64, standard_error: f64) -> Self {
+        Estimate {
+            confidence_interval: ConfidenceInterval {
+                confidence_level: CONFIDENCE_LEVEL,
+                lower_bound: point_estimate - Z_95 * standard_error,
+                upper_bound: point_estimate + Z_95 * standard_error,
+            },
+            point_estimate,
+            standard_error,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Estimates {
+    mean: Estimate,
+    median: Estimate,
+    median_abs_dev: Estimate,
+    slope: Option<Estimate>,
+    std_dev: Estimate,
+}
+
+/// Criterion's directory-name sanitizing
+fn directory_name(id: &str) -> String {
+    id.chars()
+        .map(|c| if "?\"/\\*<>:|".contains(c) { '_' } else { c })
+        .collect()
+}
+
+fn estimates(result: &BenchmarkResult) -> Estimates {
+    let (mean, std_dev, count) = match &result.summary {
+        Some(summary) => (summary.mean_ns, summary.std_dev_ns, summary.count.max(1) as f64),
+        None => (result.time_ns, 0.0, 1.0),
+    };
+    let standard_error = std_dev / count.sqrt();
+    // Median absolute deviation needs the samples; without them it's unknown
+    let median_abs_dev = result.samples.as_ref().map_or(0.0, |samples| {
+        let mut deviations: Vec<f64> = samples.iter().map(|t| (t - result.time_ns).abs()).collect();
+        crate::median(&mut deviations)
+    });
+    Estimates {
+        mean: Estimate::new(mean, standard_error),
+        // The median's standard error is about 1.25x the mean's for normal data
+        median: Estimate::new(result.time_ns, 1.2533 * standard_error),
+        median_abs_dev: Estimate::new(median_abs_dev, 0.0),
+        slope: None,
+        std_dev: Estimate::new(std_dev, 0.0),
+    }
+}
+
+/// Write every measured benchmark in `results` under `dir` as baseline
+/// `baseline`; returns how many were written
+pub fn export(results: &BenchmarkResults, dir: &Path, baseline: &str) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut written = 0;
+    for (category, benches) in &results.results {
+        for (name, result) in benches.iter().filter(|(_, result)| result.is_measured()) {
+            let full_id = format!("{}/{}", category, name);
+            let directory = format!("{}/{}", directory_name(category), directory_name(name));
+            // Throughput is recorded in MB/s; Criterion wants bytes per iteration
+            let bytes = result
+                .throughput_mb_s
+                .map(|mb_s| (mb_s * result.time_ns / 1000.0).round() as u64);
+            let benchmark = Benchmark {
+                group_id: category,
+                function_id: name,
+                value_str: None,
+                throughput: bytes.map(Throughput::Bytes),
+                title: full_id.clone(),
+                full_id,
+                directory_name: directory.clone(),
+            };
+
+            let path = dir.join(&directory).join(baseline);
+            std::fs::create_dir_all(&path).map_err(|e| format!("failed to create {}: {}", path.display(), e))?;
+            std::fs::write(path.join("benchmark.json"), serde_json::to_string(&benchmark)?)?;
+            std::fs::write(path.join("estimates.json"), serde_json::to_string(&estimates(result))?)?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
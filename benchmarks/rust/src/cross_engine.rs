@@ -0,0 +1,177 @@
+//! Joining the results of several engine harnesses (Rust, Mojo, Python) by
+//! benchmark name, for the `compare_results` binary
+
+use crate::{registration_order, stats, BenchmarkResults};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// One benchmark's time on each engine, `None` where that engine didn't
+/// measure it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub name: String,
+    pub times_ns: Vec<Option<f64>>,
+    /// Matches each engine reported while timed, where it records them
+    pub match_counts: Vec<Option<usize>>,
+}
+
+impl Row {
+    /// Time on engine `numerator` over time on engine `denominator`, when
+    /// both measured it
+    pub fn ratio(&self, numerator: usize, denominator: usize) -> Option<f64> {
+        let (n, d) = (self.times_ns[numerator]?, self.times_ns[denominator]?);
+        (d > 0.0).then_some(n / d)
+    }
+
+    /// Whether two engines reported different match counts, making the
+    /// timing comparison meaningless: one of them computed the wrong answer
+    pub fn disagrees(&self) -> bool {
+        let mut counts = self.match_counts.iter().flatten();
+        counts.next().is_some_and(|first| counts.any(|count| count != first))
+    }
+
+    /// Each engine's time over the fastest engine's (1.0 for the fastest),
+    /// when at least two engines measured it
+    pub fn normalized(&self) -> Option<Vec<Option<f64>>> {
+        let measured: Vec<f64> = self.times_ns.iter().flatten().copied().collect();
+        let fastest = measured.iter().copied().fold(f64::INFINITY, f64::min);
+        (measured.len() >= 2 && fastest > 0.0)
+            .then(|| self.times_ns.iter().map(|time| time.map(|time| time / fastest)).collect())
+    }
+}
+
+/// One engine's standing across the benchmarks at least two engines measured
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    /// Benchmarks it was the fastest on (ties count for each engine)
+    pub wins: usize,
+    /// Benchmarks it measured among those
+    pub contested: usize,
+    /// Geometric mean of its [`Row::normalized`] times
+    pub geomean: Option<f64>,
+}
+
+/// A [`Score`] per engine, in `rows`' engine order, leaving out rows whose
+/// engines [disagree](Row::disagrees)
+pub fn scoreboard(rows: &[Row]) -> Vec<Score> {
+    let normalized: Vec<Vec<Option<f64>>> =
+        rows.iter().filter(|row| !row.disagrees()).filter_map(Row::normalized).collect();
+    let engines = rows.first().map_or(0, |row| row.times_ns.len());
+    (0..engines)
+        .map(|engine| {
+            let times: Vec<f64> = normalized.iter().filter_map(|row| row[engine]).collect();
+            Score {
+                wins: times.iter().filter(|&&time| time == 1.0).count(),
+                contested: times.len(),
+                geomean: stats::geomean(times.iter().copied()),
+            }
+        })
+        .collect()
+}
+
+/// Every benchmark at least one of `engines` recorded, with a time per
+/// engine in the order given (`None` for an engine without results), sorted
+/// by the first engine's registration order (benchmarks it lacks last, by
+/// name)
+pub fn join(engines: &[Option<&BenchmarkResults>]) -> Vec<Row> {
+    let flats: Vec<_> = engines
+        .iter()
+        .map(|results| results.map(BenchmarkResults::flat).unwrap_or_default())
+        .collect();
+    let mut names: Vec<&str> = flats.iter().flat_map(|flat| flat.keys().copied()).collect();
+    names.sort_unstable();
+    names.dedup();
+    let first = flats.first();
+    names.sort_by_key(|name| match first.and_then(|flat| flat.get(name)) {
+        Some(result) => (false, registration_order(name, result)),
+        None => (true, (usize::MAX, *name)),
+    });
+    names
+        .into_iter()
+        .map(|name| Row {
+            name: name.to_string(),
+            times_ns: flats
+                .iter()
+                .map(|flat| flat.get(name).filter(|result| result.is_measured()).map(|result| result.time_ns))
+                .collect(),
+            match_counts: flats.iter().map(|flat| flat.get(name)?.observed_matches).collect(),
+        })
+        .collect()
+}
+
+/// Each benchmark's category, as the first of `engines` recording it files it
+pub fn categories(engines: &[Option<&BenchmarkResults>]) -> HashMap<String, String> {
+    let mut categories = HashMap::new();
+    for results in engines.iter().flatten() {
+        for (category, benches) in &results.results {
+            for name in benches.keys() {
+                categories.entry(name.clone()).or_insert_with(|| category.clone());
+            }
+        }
+    }
+    categories
+}
+
+/// For each of `engines`, the benchmarks some other engine recorded but it
+/// didn't (even as skipped), sorted; empty for an engine without results
+pub fn missing(engines: &[Option<&BenchmarkResults>]) -> Vec<Vec<String>> {
+    let flats: Vec<_> = engines.iter().map(|results| results.map(BenchmarkResults::flat)).collect();
+    let mut all: Vec<&str> = flats.iter().flatten().flat_map(|flat| flat.keys().copied()).collect();
+    all.sort_unstable();
+    all.dedup();
+    flats
+        .iter()
+        .map(|flat| match flat {
+            Some(flat) => all.iter().filter(|name| !flat.contains_key(*name)).map(|name| name.to_string()).collect(),
+            None => Vec::new(),
+        })
+        .collect()
+}
+
+/// Geometric mean of [`Row::ratio`] over the rows both engines measured
+/// and no engines [disagree](Row::disagrees) on, and how many those were
+pub fn geomean_ratio(rows: &[Row], numerator: usize, denominator: usize) -> Option<(f64, usize)> {
+    let ratios: Vec<f64> = rows
+        .iter()
+        .filter(|row| !row.disagrees())
+        .filter_map(|row| row.ratio(numerator, denominator))
+        .collect();
+    Some((stats::geomean(ratios.iter().copied())?, ratios.len()))
+}
+
+/// Geomean speedup of every engine over every other among `engines`
+/// engines: `matrix[i][j]` is the geomean of engine j's time over engine
+/// i's, so above 1 means engine i is faster; `None` on the diagonal and for
+/// pairs without benchmarks in common
+pub fn speedup_matrix(rows: &[Row], engines: usize) -> Vec<Vec<Option<f64>>> {
+    (0..engines)
+        .map(|i| {
+            (0..engines)
+                .map(|j| if i == j { None } else { geomean_ratio(rows, j, i).map(|(ratio, _)| ratio) })
+                .collect()
+        })
+        .collect()
+}
+
+/// `rows` as CSV for spreadsheets: the name, each engine's time in ns, then
+/// each `ratios` pair (numerator/denominator); unmeasured cells are empty
+pub fn csv(rows: &[Row], engines: &[&str], ratios: &[(usize, usize)]) -> String {
+    let mut header = vec!["name".to_string()];
+    header.extend(engines.iter().map(|engine| format!("{}_ns", engine)));
+    header.extend(ratios.iter().map(|&(n, d)| format!("{}/{}", engines[n], engines[d])));
+    let mut out = header.join(",") + "\n";
+    let cell = |value: Option<f64>| value.map_or(String::new(), |value| value.to_string());
+    for row in rows {
+        // RFC 4180: quote fields with separators, doubling inner quotes
+        let name = if row.name.contains([',', '"', '\n']) {
+            format!("\"{}\"", row.name.replace('"', "\"\""))
+        } else {
+            row.name.clone()
+        };
+        let mut cells = vec![name];
+        cells.extend(row.times_ns.iter().map(|&time| cell(time)));
+        cells.extend(ratios.iter().map(|&(n, d)| cell(row.ratio(n, d))));
+        let _ = writeln!(out, "{}", cells.join(","));
+    }
+    out
+}
@@ -0,0 +1,39 @@
+//! Hardware cycle counter backend for the optional `cycles` feature
+//!
+//! Reads `rdtsc` on x86_64 and `cntvct_el0` on aarch64. Note that
+//! `cntvct_el0` ticks at a fixed system-counter frequency rather than the
+//! core clock, so aarch64 "cycles" are counter ticks.
+
+/// Whether this build can read a hardware cycle counter
+pub const SUPPORTED: bool = cfg!(all(
+    feature = "cycles",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+));
+
+/// Read the current cycle counter, or `None` when unsupported
+#[cfg(all(feature = "cycles", target_arch = "x86_64"))]
+#[inline(always)]
+pub fn read() -> Option<u64> {
+    // SAFETY: rdtsc is available on every x86_64 CPU
+    Some(unsafe { core::arch::x86_64::_rdtsc() })
+}
+
+/// Read the current cycle counter, or `None` when unsupported
+#[cfg(all(feature = "cycles", target_arch = "aarch64"))]
+#[inline(always)]
+pub fn read() -> Option<u64> {
+    let ticks: u64;
+    // SAFETY: cntvct_el0 is readable from EL0 on all supported OSes
+    unsafe { core::arch::asm!("mrs {}, cntvct_el0", out(reg) ticks) };
+    Some(ticks)
+}
+
+/// Read the current cycle counter, or `None` when unsupported
+#[cfg(not(all(
+    feature = "cycles",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+#[inline(always)]
+pub fn read() -> Option<u64> {
+    None
+}
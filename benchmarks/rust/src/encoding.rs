@@ -0,0 +1,54 @@
+//! Legacy text encodings for the alternative-encoding benchmarks, and raw
+//! byte haystacks for the non-UTF-8 ones
+
+/// Accented European text whose every character is representable in Latin-1
+const EUROPEAN_BASE: &str = "Señor José Müller ordered crème brûlée at 12:30 in Ålesund; \
+the naïve façade of Ørsted's café reopened on 14/02. ";
+
+/// Generate `length` characters of accented European text
+pub fn make_european_text(length: usize) -> String {
+    EUROPEAN_BASE.chars().cycle().take(length).collect()
+}
+
+/// Encode as Latin-1 (ISO-8859-1); characters above U+00FF become `?`
+pub fn to_latin1(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'))
+        .collect()
+}
+
+/// Decode Latin-1 bytes to UTF-8 (each byte is the code point of the same value)
+pub fn from_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
+
+/// Encode as little-endian UTF-16
+pub fn to_utf16le(text: &str) -> Vec<u8> {
+    text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+/// Decode little-endian UTF-16 to UTF-8, replacing unpaired surrogates
+pub fn from_utf16le(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Bytes between log records that aren't valid UTF-8: lone continuation
+/// bytes, a truncated sequence and bytes never used in UTF-8
+const BINARY_JUNK: &[u8] = b"\xff\xfe\x00\x80\xc3\x28\xa0\xa1\xc0\xaf";
+
+/// Generate `num_records` ASCII log records, each followed by a run of
+/// invalid UTF-8, as in binary logs or memory dumps
+pub fn make_binary_log(num_records: usize) -> Vec<u8> {
+    const LEVELS: [&str; 4] = ["INFO", "DEBUG", "WARN", "ERROR"];
+    let mut bytes = Vec::new();
+    for i in 0..num_records {
+        let record = format!("id={} user=user{} level={}\n", 10000 + i, i % 50, LEVELS[i % LEVELS.len()]);
+        bytes.extend_from_slice(record.as_bytes());
+        bytes.extend_from_slice(BINARY_JUNK);
+    }
+    bytes
+}
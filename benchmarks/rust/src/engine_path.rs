@@ -0,0 +1,42 @@
+//! Which path through the Mojo engine a benchmark is designed to exercise
+//!
+//! The tag is declared by the suite, not observed: it says what a benchmark
+//! is there to measure, so regressions can be grouped by engine subsystem
+//! rather than by name prefixes such as `dfa_`.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnginePath {
+    /// Patterns the optimizer classifies SIMPLE, run on the DFA alone
+    PureDfa,
+    /// MEDIUM patterns: DFA where possible, NFA for the rest
+    Hybrid,
+    /// COMPLEX patterns on the backtracking NFA
+    NfaBacktracker,
+    /// Character-class scans vectorized with SIMD
+    SimdCharClass,
+    /// Literal prefix / required-literal prefiltering
+    LiteralOptimizer,
+}
+
+impl EnginePath {
+    /// Name used in results files
+    pub fn name(self) -> &'static str {
+        match self {
+            EnginePath::PureDfa => "pure_dfa",
+            EnginePath::Hybrid => "hybrid",
+            EnginePath::NfaBacktracker => "nfa_backtracker",
+            EnginePath::SimdCharClass => "simd_char_class",
+            EnginePath::LiteralOptimizer => "literal_optimizer",
+        }
+    }
+}
+
+impl fmt::Display for EnginePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,99 @@
+//! Machine state captured alongside a run (load, CPU model and frequency)
+//!
+//! Everything is read from `/proc` and `/sys`, so on other platforms the
+//! Linux-specific fields are simply left empty.
+
+use serde::{Deserialize, Serialize};
+
+/// Set to the pinned image digest by `run_comparison_with_rust.sh
+/// --container` when it runs the harness inside a container; `bench_engine
+/// orchestrate` doesn't isolate its harnesses
+pub const CONTAINER_IMAGE_VAR: &str = "BENCH_CONTAINER_IMAGE";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Environment {
+    pub os: String,
+    pub arch: String,
+    pub logical_cores: usize,
+    pub cpu_model: Option<String>,
+    pub cpu_governor: Option<String>,
+    /// 1/5/15-minute load averages at the start of the run
+    pub load_average_before: Option<[f64; 3]>,
+    pub load_average_after: Option<[f64; 3]>,
+    /// Mean current frequency across cores, in MHz
+    pub cpu_freq_mhz_before: Option<f64>,
+    pub cpu_freq_mhz_after: Option<f64>,
+    /// Container image (`name@sha256:...`) the run was isolated in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_image: Option<String>,
+}
+
+impl Environment {
+    /// Capture the machine state at the start of a run
+    pub fn capture() -> Self {
+        Environment {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            logical_cores: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            cpu_model: cpuinfo_values("model name").into_iter().next(),
+            cpu_governor: read_trimmed("/sys/devices/system/cpu/cpu0/cpufreq/scaling_governor"),
+            load_average_before: load_average(),
+            load_average_after: None,
+            cpu_freq_mhz_before: cpu_freq_mhz(),
+            cpu_freq_mhz_after: None,
+            container_image: std::env::var(CONTAINER_IMAGE_VAR).ok().filter(|image| !image.is_empty()),
+        }
+    }
+
+    /// Record the end-of-run load and frequency
+    pub fn finish(&mut self) {
+        self.load_average_after = load_average();
+        self.cpu_freq_mhz_after = cpu_freq_mhz();
+    }
+}
+
+fn read_trimmed(path: &str) -> Option<String> {
+    std::fs::read_to_string(path).ok().map(|s| s.trim().to_string())
+}
+
+fn load_average() -> Option<[f64; 3]> {
+    let content = read_trimmed("/proc/loadavg")?;
+    let mut fields = content.split_whitespace().map(|f| f.parse::<f64>().ok());
+    Some([fields.next()??, fields.next()??, fields.next()??])
+}
+
+/// Values of every `key : value` line in /proc/cpuinfo
+fn cpuinfo_values(key: &str) -> Vec<String> {
+    let content = std::fs::read_to_string("/proc/cpuinfo").unwrap_or_default();
+    content
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(k, _)| k.trim() == key)
+        .map(|(_, v)| v.trim().to_string())
+        .collect()
+}
+
+/// Mean scaling_cur_freq across cores, falling back to /proc/cpuinfo
+/// "cpu MHz" where cpufreq isn't exposed (e.g. most VMs)
+fn cpu_freq_mhz() -> Option<f64> {
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let mut khz: Vec<f64> = (0..cores)
+        .filter_map(|cpu| {
+            read_trimmed(&format!("/sys/devices/system/cpu/cpu{}/cpufreq/scaling_cur_freq", cpu))?
+                .parse()
+                .ok()
+        })
+        .collect();
+    if khz.is_empty() {
+        khz = cpuinfo_values("cpu MHz")
+            .iter()
+            .filter_map(|mhz| mhz.parse::<f64>().ok())
+            .map(|mhz| mhz * 1000.0)
+            .collect();
+    }
+    if khz.is_empty() {
+        return None;
+    }
+    Some(khz.iter().sum::<f64>() / khz.len() as f64 / 1000.0)
+}
@@ -0,0 +1,48 @@
+//! Stand-in engine harness for exercising the orchestrator's failure handling
+//!
+//! Usage: fake_engine <mode> <results.json>
+//!   ok            write results for benchmarks a, b and c
+//!   wrong-counts  write results for benchmark a only
+//!   malformed     write a results file that isn't JSON
+//!   crash         print to stderr and exit with status 3
+//!   hang          never exit
+
+use serde_json::json;
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [mode, output] = args.as_slice() else {
+        eprintln!("usage: fake_engine <ok|wrong-counts|malformed|crash|hang> <results.json>");
+        std::process::exit(2);
+    };
+
+    let write_results = |names: &[&str]| {
+        let results: serde_json::Map<String, serde_json::Value> = names
+            .iter()
+            .map(|name| (name.to_string(), json!({"time_ns": 100.0, "time_ms": 0.0001, "iterations": 10})))
+            .collect();
+        let content = json!({
+            "engine": "fake",
+            "timestamp": "1970-01-01T00:00:00Z",
+            "results": {"fake": results},
+        });
+        std::fs::write(output, content.to_string()).expect("write results");
+    };
+
+    match mode.as_str() {
+        "ok" => write_results(&["a", "b", "c"]),
+        "wrong-counts" => write_results(&["a"]),
+        "malformed" => std::fs::write(output, "{\"engine\": \"fake\", \"results\": [").expect("write results"),
+        "crash" => {
+            eprintln!("fake engine: simulated crash");
+            std::process::exit(3);
+        }
+        "hang" => loop {
+            std::thread::sleep(std::time::Duration::from_secs(60));
+        },
+        _ => {
+            eprintln!("unknown mode: {}", mode);
+            std::process::exit(2);
+        }
+    }
+}
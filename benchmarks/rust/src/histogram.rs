@@ -0,0 +1,70 @@
+//! Log-spaced (HDR-style) latency histograms
+
+use serde::{Deserialize, Serialize};
+
+/// Default buckets per power of two (~9% relative bucket width)
+pub const DEFAULT_SUB_BUCKETS: u32 = 8;
+
+/// Histogram of timings with logarithmically spaced buckets
+///
+/// Bucket `i` covers `[2^(i/s), 2^((i+1)/s))` ns, where `s` is
+/// `sub_buckets`. Only non-empty buckets are stored, as `[index, count]`
+/// pairs in ascending index order, which keeps the JSON compact.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    pub sub_buckets: u32,
+    pub counts: Vec<(i32, u64)>,
+}
+
+impl Histogram {
+    /// Build a histogram of `samples` (ns) with the default resolution
+    pub fn from_samples(samples: &[f64]) -> Self {
+        let mut histogram = Histogram {
+            sub_buckets: DEFAULT_SUB_BUCKETS,
+            counts: Vec::new(),
+        };
+        for &sample in samples {
+            histogram.record(sample, 1);
+        }
+        histogram
+    }
+
+    fn bucket_index(&self, value_ns: f64) -> i32 {
+        // Sub-picosecond values (and zero) land in the lowest bucket
+        (value_ns.max(1e-3).log2() * self.sub_buckets as f64).floor() as i32
+    }
+
+    /// Add `count` observations of `value_ns`
+    pub fn record(&mut self, value_ns: f64, count: u64) {
+        let index = self.bucket_index(value_ns);
+        match self.counts.binary_search_by_key(&index, |&(i, _)| i) {
+            Ok(pos) => self.counts[pos].1 += count,
+            Err(pos) => self.counts.insert(pos, (index, count)),
+        }
+    }
+
+    /// Lower and upper bound (ns) of bucket `index`
+    pub fn bucket_bounds(&self, index: i32) -> (f64, f64) {
+        let s = self.sub_buckets as f64;
+        (2f64.powf(index as f64 / s), 2f64.powf((index + 1) as f64 / s))
+    }
+
+    /// Total number of observations
+    pub fn total(&self) -> u64 {
+        self.counts.iter().map(|&(_, count)| count).sum()
+    }
+
+    /// Histogram of every observation divided by `divisor`, re-bucketed by
+    /// each bucket's geometric midpoint (accurate to one bucket)
+    pub fn scaled(&self, divisor: f64) -> Self {
+        let mut scaled = Histogram {
+            sub_buckets: self.sub_buckets,
+            counts: Vec::with_capacity(self.counts.len()),
+        };
+        for &(index, count) in &self.counts {
+            let (lo, hi) = self.bucket_bounds(index);
+            scaled.record((lo * hi).sqrt() / divisor, count);
+        }
+        scaled
+    }
+}
@@ -0,0 +1,65 @@
+//! Append-only JSON Lines history of runs, for trend analysis without an
+//! external database
+//!
+//! Each line is one run's complete results plus the git commit it was run
+//! at, so the file can be sliced by commit, date or engine with nothing
+//! more than `jq`.
+
+use crate::build_info::{git, git_dirty};
+use crate::BenchmarkResults;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+/// One line of the history file
+#[derive(Debug, Deserialize)]
+pub struct HistoryRecord {
+    /// `HEAD` at run time, suffixed `-dirty` if the tree had local changes
+    pub git_sha: Option<String>,
+    #[serde(flatten)]
+    pub results: BenchmarkResults,
+}
+
+/// Serialized form of [`HistoryRecord`], borrowing the results
+#[derive(Serialize)]
+struct RecordRef<'a> {
+    git_sha: Option<&'a str>,
+    #[serde(flatten)]
+    results: &'a BenchmarkResults,
+}
+
+/// The current commit, if run inside a git checkout
+pub fn git_sha() -> Option<String> {
+    let sha = git(&["rev-parse", "HEAD"])?;
+    Some(if git_dirty() { format!("{}-dirty", sha) } else { sha })
+}
+
+/// Append a record of `results` at `git_sha` to `path` as a single line,
+/// creating the file if needed
+pub fn append(
+    path: &str,
+    results: &BenchmarkResults,
+    git_sha: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut line = serde_json::to_string(&RecordRef { git_sha, results })?;
+    line.push('\n');
+    std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(line.as_bytes()))
+        .map_err(|e| format!("failed to append to {}: {}", path, e))?;
+    Ok(())
+}
+
+/// Every record in `path`, oldest first
+pub fn load(path: &str) -> Result<Vec<HistoryRecord>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(number, line)| {
+            serde_json::from_str(line).map_err(|e| format!("{}:{}: {}", path, number + 1, e).into())
+        })
+        .collect()
+}
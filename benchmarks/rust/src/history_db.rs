@@ -0,0 +1,111 @@
+//! SQLite history of per-benchmark results (`sqlite` feature)
+//!
+//! Unlike the JSON Lines history, which keeps whole runs, this stores one
+//! row per benchmark keyed by (git sha, engine, benchmark), so a single
+//! benchmark's trend across commits is one indexed query. Re-running at the
+//! same commit replaces that commit's rows.
+
+use crate::trend::UNKNOWN_SHA;
+use crate::BenchmarkResults;
+use rusqlite::{params, Connection};
+
+pub use crate::trend::TrendPoint;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS results (
+    git_sha TEXT NOT NULL,
+    engine TEXT NOT NULL,
+    benchmark TEXT NOT NULL,
+    category TEXT NOT NULL,
+    timestamp TEXT NOT NULL,
+    time_ns REAL NOT NULL,
+    iterations INTEGER NOT NULL,
+    throughput_mb_s REAL,
+    clock TEXT,
+    PRIMARY KEY (git_sha, engine, benchmark)
+);
+CREATE INDEX IF NOT EXISTS results_by_benchmark ON results (benchmark, engine, timestamp);
+";
+
+/// An open history database
+#[derive(Debug)]
+pub struct HistoryDb {
+    connection: Connection,
+}
+
+impl HistoryDb {
+    /// Open the database at `path`, creating it and its table if needed
+    pub fn open(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let connection = Connection::open(path).map_err(|e| format!("failed to open {}: {}", path, e))?;
+        connection
+            .execute_batch(SCHEMA)
+            .map_err(|e| format!("failed to create the history schema in {}: {}", path, e))?;
+        Ok(HistoryDb { connection })
+    }
+
+    /// Record every measured benchmark of `results` at `git_sha`, replacing
+    /// rows from an earlier run of the same engine at the same commit;
+    /// returns how many rows were written
+    pub fn insert(
+        &mut self,
+        results: &BenchmarkResults,
+        git_sha: Option<&str>,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let git_sha = git_sha.unwrap_or(UNKNOWN_SHA);
+        let transaction = self.connection.transaction()?;
+        let mut written = 0;
+        {
+            let mut statement = transaction.prepare(
+                "INSERT OR REPLACE INTO results
+                 (git_sha, engine, benchmark, category, timestamp, time_ns, iterations, throughput_mb_s, clock)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+            for (category, benches) in &results.results {
+                for (name, result) in benches.iter().filter(|(_, result)| result.is_measured()) {
+                    statement.execute(params![
+                        git_sha,
+                        results.engine,
+                        name,
+                        category,
+                        results.timestamp,
+                        result.time_ns,
+                        result.iterations as i64,
+                        result.throughput_mb_s,
+                        results.clock,
+                    ])?;
+                    written += 1;
+                }
+            }
+        }
+        transaction.commit()?;
+        Ok(written)
+    }
+
+    /// The latest `limit` results of `benchmark`, optionally for one engine
+    /// only, oldest first
+    pub fn trend(
+        &self,
+        benchmark: &str,
+        engine: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<TrendPoint>, Box<dyn std::error::Error>> {
+        let mut statement = self.connection.prepare(
+            "SELECT git_sha, engine, timestamp, time_ns, throughput_mb_s FROM results
+             WHERE benchmark = ?1 AND (?2 IS NULL OR engine = ?2)
+             ORDER BY timestamp DESC LIMIT ?3",
+        )?;
+        let mut points = statement
+            .query_map(params![benchmark, engine, limit as i64], |row| {
+                Ok(TrendPoint {
+                    git_sha: row.get(0)?,
+                    engine: row.get(1)?,
+                    timestamp: row.get(2)?,
+                    time_ns: row.get(3)?,
+                    throughput_mb_s: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        points.reverse();
+        Ok(points)
+    }
+}
@@ -1,22 +1,926 @@
 //! Library functions for Rust regex benchmarks
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::build_info::BuildInfo;
+use crate::clock::{Clock, WallClock};
+use crate::criteria::Criteria;
+use crate::engine_path::EnginePath;
+use crate::environment::Environment;
+use crate::histogram::Histogram;
+use crate::metric::{Metric, MetricValue};
+use crate::sentinel::{Sentinel, StabilityReport};
+use crate::stats::{OnlineStats, Reservoir, SampleSummary};
+
+pub mod aa;
+pub mod archive;
+pub mod artifacts;
+pub mod build_info;
+pub mod clock;
+pub mod compare;
+pub mod corpus;
+pub mod criterion;
+pub mod criteria;
+pub mod cross_engine;
+pub mod cycles;
+pub mod encoding;
+pub mod engine_path;
+pub mod environment;
+pub mod histogram;
+pub mod history;
+#[cfg(feature = "sqlite")]
+pub mod history_db;
+pub mod lint;
+pub mod manifest;
+pub mod memory;
+pub mod merge;
+pub mod metric;
+pub mod orchestrator;
+pub mod plot;
+pub mod provenance;
+pub mod report;
+pub mod sentinel;
+pub mod stats;
+pub mod strategy;
+pub mod suite;
+pub mod summary;
+pub mod trend;
 
 /// Benchmark result data structure
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct BenchmarkResult {
+    /// Harnesses that only wrote one of `time_ns`/`time_ms` get the other
+    /// derived from it on load. Left out of skipped and timed-out results,
+    /// which have no timing, so no consumer mistakes a 0 for a measurement
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub time_ns: f64,
+    #[serde(default, skip_serializing_if = "is_zero")]
     pub time_ms: f64,
+    #[serde(default)]
     pub iterations: u64,
+    /// Pattern source, for benchmarks driven by a single pattern
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Haystack size in bytes, for benchmarks that scan one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub haystack_len: Option<usize>,
+    /// Operation timed (`FindAll`, `Search`, `Sub`, `Custom`, ...), as in
+    /// the suite definitions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    /// Operations per timed call; times are already divided by it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inner_iterations: Option<usize>,
+    /// Position in the suite's registration order, which keeps related
+    /// benchmarks together in tables and charts across runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<usize>,
+    /// Median cycle-counter ticks per operation (`cycles` feature only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycles: Option<f64>,
+    /// First call on a freshly compiled regex, before its lazy DFA is built
+    /// (the same as `phases.first_ns`, which it predates)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cold_time_ns: Option<f64>,
+    /// Compile and first-call cost, for benchmarks driven by a single
+    /// pattern; the steady state is `time_ns`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phases: Option<Phases>,
+    /// Cycles per haystack byte, filled in once the haystack is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cycles_per_byte: Option<f64>,
+    /// Haystack bytes per second at the median time, in MB/s
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throughput_mb_s: Option<f64>,
+    /// Tokens per second at the median time, for tokenizers whose match
+    /// count is their token count (see [`ResultCollector::counts_tokens`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens_per_sec: Option<f64>,
+    /// Heap allocations per warmed-up operation (counting allocator only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<f64>,
+    /// Process peak RSS while the benchmark ran, in bytes (Linux only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_rss_bytes: Option<u64>,
+    /// Non-timing budgets the benchmark declared (see [`criteria`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub criteria: Option<Criteria>,
+    /// Mojo engine path the benchmark is designed to exercise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_path: Option<EnginePath>,
+    /// Which member of a family of controlled variants this benchmark is
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant: Option<Variant>,
+    /// Every metric attached to the timer, by name, from the kept round
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<BTreeMap<String, MetricValue>>,
+    /// Single-call latency distribution (latency mode only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency: Option<LatencyStats>,
+    /// Per-operation time of every sample in ns (sample retention only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub samples: Option<Vec<f64>>,
+    /// Log-spaced distribution of per-operation sample times
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub histogram: Option<Histogram>,
+    /// Mean, standard deviation, min and max of the per-operation sample times
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<SampleSummary>,
+    /// Sample distribution has two modes, so the median is misleading
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub bimodal: bool,
+    /// Timed samples (outer iterations) taken in the kept round
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub outer_iterations: Option<u64>,
+    /// Coefficient of variation of the samples in the kept round
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cv: Option<f64>,
+    /// Extra rounds run because the first round's CV was too high
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub reran: u32,
+    /// Median per-operation time of every round, in the order run (only
+    /// when more than one round ran)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round_medians_ns: Option<Vec<f64>>,
+    /// Max / min of `round_medians_ns`; 1.0 means the rounds agreed exactly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability_score: Option<f64>,
+    /// Not measured because the suite time budget ran out
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub skipped: bool,
+    /// Abandoned because it ran past the per-benchmark deadline
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub timed_out: bool,
+    /// Error the engine reported when compiling the pattern, for benchmarks
+    /// that measure compiling (or rejecting) hostile patterns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_error: Option<String>,
+    /// Execution strategy the engine used, when it can be introspected
+    /// (same key the Mojo harness uses for its DFA/NFA routing)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine: Option<String>,
+    /// Matches the operation must report, when declared (see
+    /// [`ResultCollector::expect_matches`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_matches: Option<usize>,
+    /// Matches the operation reported while timed; the smallest count seen
+    /// if it varied between samples
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub observed_matches: Option<usize>,
+    /// Every timed sample reported the same count, and it equals
+    /// `expected_matches` if one was declared
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correct: Option<bool>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Benchmarks of one family differ in a single controlled input (e.g. the
+/// script of the haystack), so their times can be compared directly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    pub family: String,
+    pub name: String,
+}
+
+/// Cost of the phases of using a pattern before it's warmed up, read from
+/// the selected clock like `time_ns`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Phases {
+    /// One `Regex::new`
+    pub compile_ns: f64,
+    /// First call on the freshly compiled regex, before its lazy DFA is built
+    pub first_ns: f64,
+}
+
+/// Distribution of single-call latencies, one call per sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyStats {
+    pub samples: usize,
+    pub min_ns: f64,
+    pub p50_ns: f64,
+    pub p90_ns: f64,
+    pub p99_ns: f64,
+    pub p999_ns: f64,
+    pub max_ns: f64,
+    /// Median cost of an empty timed region, included in every sample
+    pub clock_overhead_ns: f64,
+}
+
+impl BenchmarkResult {
+    /// Placeholder for a benchmark that was not measured
+    pub fn skipped() -> BenchmarkResult {
+        BenchmarkResult {
+            skipped: true,
+            ..Default::default()
+        }
+    }
+
+    /// Placeholder for a benchmark abandoned at its deadline
+    pub fn timed_out() -> BenchmarkResult {
+        BenchmarkResult {
+            timed_out: true,
+            ..Default::default()
+        }
+    }
+
+    /// Whether this result holds a timing (it was neither skipped nor
+    /// abandoned)
+    pub fn is_measured(&self) -> bool {
+        !self.skipped && !self.timed_out
+    }
+
+    /// Convert a result measured over `inner_iterations` calls per closure
+    /// invocation into a per-operation result
+    pub fn per_op(&self, inner_iterations: usize) -> BenchmarkResult {
+        let n = inner_iterations as f64;
+        BenchmarkResult {
+            time_ns: self.time_ns / n,
+            time_ms: self.time_ms / n,
+            iterations: self.iterations * inner_iterations as u64,
+            cycles: self.cycles.map(|c| c / n),
+            cycles_per_byte: self.cycles_per_byte.map(|c| c / n),
+            samples: self
+                .samples
+                .as_ref()
+                .map(|samples| samples.iter().map(|t| t / n).collect()),
+            histogram: self.histogram.as_ref().map(|h| h.scaled(n)),
+            summary: self.summary.as_ref().map(|s| s.scaled(n)),
+            round_medians_ns: self
+                .round_medians_ns
+                .as_ref()
+                .map(|medians| medians.iter().map(|t| t / n).collect()),
+            metrics: self.metrics.as_ref().map(|metrics| {
+                metrics
+                    .iter()
+                    .map(|(name, metric)| {
+                        let value = if metric.per_op { metric.value / n } else { metric.value };
+                        (name.clone(), MetricValue { value, ..metric.clone() })
+                    })
+                    .collect()
+            }),
+            ..self.clone()
+        }
+    }
+
+    /// Fill in `cycles_per_byte` and `throughput_mb_s` for a haystack of
+    /// `haystack_len` bytes
+    pub fn with_haystack_len(mut self, haystack_len: usize) -> BenchmarkResult {
+        if haystack_len > 0 {
+            self.cycles_per_byte = self.cycles.map(|c| c / haystack_len as f64);
+            if self.time_ns > 0.0 {
+                // bytes per ns is GB/s
+                self.throughput_mb_s = Some(haystack_len as f64 / self.time_ns * 1000.0);
+            }
+        }
+        self
+    }
 }
 
-/// Complete benchmark results structure matching Python format
+/// Results grouped by category, then by benchmark name
+pub type CategorizedResults = HashMap<String, HashMap<String, BenchmarkResult>>;
+
+/// Geometric means of the measured times, per category and overall
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Aggregates {
+    /// Over the matching benchmarks only: compile times are a cost of
+    /// another kind, geomeaned in their own categories
+    pub geomean_ns: f64,
+    pub categories: BTreeMap<String, f64>,
+}
+
+impl Aggregates {
+    /// Geomeans over every measured benchmark; `None` if no matching one was
+    pub fn from_results(results: &CategorizedResults) -> Option<Self> {
+        let measured = |benches: &HashMap<String, BenchmarkResult>| -> Vec<f64> {
+            benches
+                .values()
+                .filter(|result| result.is_measured())
+                .map(|result| result.time_ns)
+                .collect()
+        };
+        let matching = |benches: &HashMap<String, BenchmarkResult>| -> Vec<f64> {
+            benches
+                .values()
+                .filter(|result| result.is_measured() && result.operation.as_deref() != Some("Compile"))
+                .map(|result| result.time_ns)
+                .collect()
+        };
+        let categories = results
+            .iter()
+            .filter_map(|(category, benches)| Some((category.clone(), stats::geomean(measured(benches))?)))
+            .collect();
+        Some(Aggregates {
+            geomean_ns: stats::geomean(results.values().flat_map(matching))?,
+            categories,
+        })
+    }
+}
+
+/// Sort key placing benchmarks in registration order; results from files
+/// that predate numbering fall back to alphabetical order after the rest
+pub fn registration_order<'a>(name: &'a str, result: &BenchmarkResult) -> (usize, &'a str) {
+    (result.index.unwrap_or(usize::MAX), name)
+}
+
+/// Overrides the default results directory (`--output` takes precedence)
+pub const OUTPUT_DIR_VAR: &str = "BENCH_OUTPUT_DIR";
+
+/// Directory results are written to and read from: `$BENCH_OUTPUT_DIR`, or
+/// `benchmarks/results` of the checkout the harness was built from,
+/// wherever it's invoked from
+pub fn results_dir() -> std::path::PathBuf {
+    let manifest_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+    std::env::var_os(OUTPUT_DIR_VAR)
+        .filter(|dir| !dir.is_empty())
+        .map_or_else(|| manifest_dir.parent().unwrap_or(manifest_dir).join("results"), Into::into)
+}
+
+/// Name prefixes that make up a family across categories, e.g. every
+/// `dfa_` benchmark whichever section registered it
+pub const FAMILY_PREFIXES: [&str; 5] = ["literal_", "dfa_", "simd_", "phone_", "group_"];
+
+/// Family a benchmark belongs to: its variant family if it declared one,
+/// then the [`FAMILY_PREFIXES`] entry its name starts with (`dfa_simple_phone`
+/// is in `dfa`), otherwise its category
+pub fn family<'a>(category: &'a str, name: &'a str, result: &'a BenchmarkResult) -> &'a str {
+    if let Some(variant) = &result.variant {
+        return &variant.family;
+    }
+    FAMILY_PREFIXES
+        .iter()
+        .find(|prefix| name.starts_with(*prefix))
+        .map_or(category, |prefix| prefix.trim_end_matches('_'))
+}
+
+/// Category assigned to entries loaded from a legacy flat results file
+pub const UNCATEGORIZED: &str = "uncategorized";
+
+/// Layout version of results files written by this build. Bump it on any
+/// change an older reader would misread; fields that are merely added don't
+/// need a bump as long as they default when missing.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// Version assumed for files written before `schema_version` existed,
+/// including the Python and Mojo harnesses' flat results
+pub const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+fn legacy_schema_version() -> u32 {
+    LEGACY_SCHEMA_VERSION
+}
+
+/// Complete benchmark results structure
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BenchmarkResults {
+    /// Layout version this file was written with (see [`SCHEMA_VERSION`])
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
     pub engine: String,
+    #[serde(default)]
     pub timestamp: String,
-    pub results: HashMap<String, BenchmarkResult>,
+    #[serde(deserialize_with = "deserialize_results")]
+    pub results: CategorizedResults,
+    /// Machine load and CPU state during the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Environment>,
+    /// Commit, compiler, build flags and regex version of the harness
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_info: Option<BuildInfo>,
+    /// Per-category and overall geomeans of `results`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aggregates: Option<Aggregates>,
+    /// Measurement clock and its unit, e.g. `wall (ns)`; times are in that unit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<String>,
+    /// Sentinel drift across the run (see [`sentinel`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<StabilityReport>,
+    /// The sentinel drifted past its threshold, e.g. from thermal throttling
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub unstable_environment: bool,
+    /// Untimed setup each category ran (see [`ResultCollector::setup`]), in
+    /// the order it ran
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub setup_costs: Vec<SetupCost>,
+}
+
+impl BenchmarkResults {
+    /// Results of `engine` at the current schema version, with no timestamp
+    /// or run metadata
+    pub fn new(engine: &str, results: CategorizedResults) -> Self {
+        BenchmarkResults {
+            schema_version: SCHEMA_VERSION,
+            engine: engine.to_string(),
+            timestamp: String::new(),
+            results,
+            environment: None,
+            build_info: None,
+            aggregates: None,
+            clock: None,
+            stability: None,
+            unstable_environment: false,
+            setup_costs: Vec::new(),
+        }
+    }
+
+    /// Load a results file written by any of the harnesses, at any schema
+    /// version up to [`SCHEMA_VERSION`], plain or gzipped
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = archive::read_to_string(path)?;
+        let results: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("failed to parse {}: {}", path, e))?;
+        if results.schema_version > SCHEMA_VERSION {
+            return Err(format!(
+                "{} uses results schema {}, but this build only reads up to {}",
+                path, results.schema_version, SCHEMA_VERSION
+            )
+            .into());
+        }
+        Ok(results)
+    }
+
+    /// Flat name -> result view, for consumers that predate categories
+    pub fn flat(&self) -> HashMap<&str, &BenchmarkResult> {
+        self.results
+            .values()
+            .flat_map(|benches| benches.iter().map(|(name, r)| (name.as_str(), r)))
+            .collect()
+    }
+
+    /// Category a benchmark was recorded under
+    pub fn category_of(&self, name: &str) -> Option<&str> {
+        self.results
+            .iter()
+            .find(|(_, benches)| benches.contains_key(name))
+            .map(|(category, _)| category.as_str())
+    }
+}
+
+/// Accept both the nested layout and the flat name -> result map written by
+/// older Rust runs and by the Python/Mojo harnesses
+fn deserialize_results<'de, D>(deserializer: D) -> Result<CategorizedResults, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Layout {
+        Nested(CategorizedResults),
+        Flat(HashMap<String, BenchmarkResult>),
+    }
+
+    let mut results = match Layout::deserialize(deserializer)? {
+        Layout::Nested(results) => results,
+        Layout::Flat(flat) => HashMap::from([(UNCATEGORIZED.to_string(), flat)]),
+    };
+    for result in results.values_mut().flat_map(|benches| benches.values_mut()) {
+        if result.time_ns == 0.0 {
+            result.time_ns = result.time_ms * 1e6;
+        } else if result.time_ms == 0.0 {
+            result.time_ms = result.time_ns / 1e6;
+        }
+    }
+    Ok(results)
+}
+
+/// Static description of a registered benchmark, as seen by a dry run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchDef {
+    /// Position in the suite's registration order, counting feature-gated
+    /// benchmarks whether or not this build has them
+    pub index: usize,
+    pub name: String,
+    pub category: String,
+    /// Pattern source, if the benchmark is driven by a single pattern
+    pub pattern: Option<String>,
+    /// Operation performed (`FindAll`, `Search`, `Sub`, `Custom`, ...)
+    pub operation: String,
+    pub haystack_len: usize,
+    /// Operations per timed call
+    pub inner_iterations: usize,
+    /// What the haystack looks like (see [`describe_haystack`]), when the
+    /// benchmark runs over a single text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub haystack: Option<String>,
+    /// Matches the pattern finds in the haystack, when countable
+    pub match_count: Option<usize>,
+    /// Mojo engine path the benchmark is designed to exercise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_path: Option<EnginePath>,
+    /// Times a scan that fails on purpose, so zero matches are expected
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub expects_no_match: bool,
+    /// Non-default `RegexBuilder` options the pattern was built with (e.g.
+    /// `unicode(false)`), which its source alone doesn't show
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub builder: Option<String>,
+    /// Fingerprint of the haystack's contents (see [`haystack_digest`]), to
+    /// tell apart haystacks of the same length and description
+    #[serde(skip)]
+    pub haystack_digest: Option<u64>,
+}
+
+/// Time a category spent in [`ResultCollector::setup`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupCost {
+    pub category: String,
+    pub label: String,
+    pub time_ns: f64,
+}
+
+/// Cleanups registered by the current category, run when it ends
+#[derive(Default)]
+struct Teardowns(Vec<Box<dyn FnOnce()>>);
+
+impl Teardowns {
+    fn run(&mut self) {
+        while let Some(teardown) = self.0.pop() {
+            teardown();
+        }
+    }
+}
+
+/// A collector dropped mid-category (e.g. by an early `?`) still cleans up
+impl Drop for Teardowns {
+    fn drop(&mut self) {
+        self.run();
+    }
+}
+
+impl std::fmt::Debug for Teardowns {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Teardowns({})", self.0.len())
+    }
+}
+
+/// Collects results under the category of the section currently running
+#[derive(Debug, Default)]
+pub struct ResultCollector {
+    category: String,
+    categories: Vec<String>,
+    category_titles: HashMap<String, String>,
+    engine_path: Option<EnginePath>,
+    expects_no_match: bool,
+    builder: Option<String>,
+    category_deadline: Option<std::time::Duration>,
+    setup_costs: Vec<SetupCost>,
+    teardowns: Teardowns,
+    results: CategorizedResults,
+    deadline: Option<std::time::Instant>,
+    benchmark_deadline: Option<std::time::Duration>,
+    dry_run: bool,
+    next_index: usize,
+    definitions: Vec<BenchDef>,
+    sentinel: Option<Sentinel>,
+}
+
+impl ResultCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A collector that only records benchmark definitions; runners must
+    /// check [`ResultCollector::is_dry_run`] and call `define` instead of timing
+    pub fn dry_run() -> Self {
+        ResultCollector {
+            dry_run: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Record a benchmark definition under the current category
+    #[allow(clippy::too_many_arguments)]
+    pub fn define(
+        &mut self,
+        name: &str,
+        pattern: Option<&str>,
+        operation: &str,
+        haystack_len: usize,
+        inner_iterations: usize,
+        haystack: Option<&str>,
+        match_count: Option<usize>,
+    ) {
+        self.definitions.push(BenchDef {
+            index: self.next_index,
+            name: name.to_string(),
+            category: self.category.clone(),
+            pattern: pattern.map(str::to_string),
+            operation: operation.to_string(),
+            haystack_len,
+            inner_iterations,
+            haystack: haystack.map(describe_haystack),
+            match_count,
+            engine_path: self.engine_path,
+            expects_no_match: self.expects_no_match,
+            builder: self.builder.clone(),
+            haystack_digest: haystack.map(haystack_digest),
+        });
+        self.next_index += 1;
+    }
+
+    pub fn definitions(&self) -> &[BenchDef] {
+        &self.definitions
+    }
+
+    /// Every category started so far, in order
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    /// Display title `category` was started with
+    pub fn category_title(&self, category: &str) -> Option<&str> {
+        self.category_titles.get(category).map(String::as_str)
+    }
+
+    /// Stop measuring new benchmarks once `budget` has elapsed from now
+    pub fn with_time_budget(mut self, budget: std::time::Duration) -> Self {
+        self.deadline = Some(std::time::Instant::now() + budget);
+        self
+    }
+
+    /// Abandon any single benchmark still running after `deadline`
+    pub fn with_benchmark_deadline(mut self, deadline: std::time::Duration) -> Self {
+        self.benchmark_deadline = Some(deadline);
+        self
+    }
+
+    /// Deadline of the current benchmark: the tighter of the suite-wide
+    /// one and the current category's
+    pub fn benchmark_deadline(&self) -> Option<std::time::Duration> {
+        match (self.benchmark_deadline, self.category_deadline) {
+            (Some(suite), Some(category)) => Some(suite.min(category)),
+            (suite, category) => suite.or(category),
+        }
+    }
+
+    /// Abandon the current category's benchmarks still running after
+    /// `deadline`, for those that may never finish on some engines
+    pub fn set_category_deadline(&mut self, deadline: std::time::Duration) {
+        self.category_deadline = Some(deadline);
+    }
+
+    /// Re-time `sentinel` at each [`ResultCollector::checkpoint`]
+    pub fn with_sentinel(mut self, sentinel: Sentinel) -> Self {
+        self.sentinel = Some(sentinel);
+        self
+    }
+
+    /// Take a sentinel reading labelled `stage`; a no-op in dry runs or
+    /// without a sentinel
+    pub fn checkpoint(&mut self, stage: &str) {
+        if self.dry_run {
+            return;
+        }
+        if let Some(sentinel) = &mut self.sentinel {
+            sentinel.measure(stage);
+        }
+    }
+
+    pub fn stability(&self) -> Option<StabilityReport> {
+        self.sentinel.as_ref()?.report()
+    }
+
+    /// Whether the suite time budget has run out; remaining benchmarks
+    /// should be recorded with [`BenchmarkResult::skipped`]
+    pub fn budget_exhausted(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+    }
+
+    /// Start a new category, ending the current one; subsequent results
+    /// are recorded under it
+    pub fn begin_category(&mut self, category: &str, title: &str) {
+        self.end_category();
+        self.category = category.to_string();
+        self.categories.push(category.to_string());
+        self.category_titles.insert(category.to_string(), title.to_string());
+        self.engine_path = None;
+        self.expects_no_match = false;
+        self.builder = None;
+        self.category_deadline = None;
+        if !self.dry_run {
+            println!("=== {} ===", title);
+        }
+    }
+
+    /// Tag the current category's benchmarks from here on with the Mojo
+    /// engine path they're designed to exercise
+    pub fn exercises(&mut self, path: EnginePath) {
+        self.engine_path = Some(path);
+    }
+
+    /// Mark the current category's benchmarks from here on as timing scans
+    /// that fail on purpose, which lint then doesn't flag for zero matches
+    pub fn expects_no_match(&mut self) {
+        self.expects_no_match = true;
+    }
+
+    /// Undo [`ResultCollector::expects_no_match`] for the current category's
+    /// benchmarks from here on
+    pub fn expects_matches(&mut self) {
+        self.expects_no_match = false;
+    }
+
+    /// Record the non-default `RegexBuilder` options (e.g. `unicode(false)`)
+    /// the current category's benchmarks from here on were built with, or
+    /// `None` for plain `Regex::new`
+    pub fn built_with(&mut self, options: Option<&str>) {
+        self.builder = options.map(str::to_string);
+    }
+
+    /// Options set by [`ResultCollector::built_with`], if any
+    pub fn builder(&self) -> Option<&str> {
+        self.builder.as_deref()
+    }
+
+    /// Run setup shared by the current category's benchmarks (generating a
+    /// corpus, writing temp files, spawning a subprocess engine) outside any
+    /// benchmark's timing, recording how long it took under `label` for the
+    /// results' `setup_costs`. Setup runs right away, in the suite's order;
+    /// there are no declared dependencies between steps, so a later category
+    /// reusing a value just keeps hold of it.
+    pub fn setup<T>(&mut self, label: &str, f: impl FnOnce() -> T) -> T {
+        let start = std::time::Instant::now();
+        let value = f();
+        let time_ns = start.elapsed().as_nanos() as f64;
+        if !self.dry_run {
+            println!("  (setup {}: {:.1} ms)", label, time_ns / 1e6);
+        }
+        self.setup_costs.push(SetupCost {
+            category: self.category.clone(),
+            label: label.to_string(),
+            time_ns,
+        });
+        value
+    }
+
+    /// Every setup run so far, in order
+    pub fn setup_costs(&self) -> &[SetupCost] {
+        &self.setup_costs
+    }
+
+    /// Run `f` once the current category's benchmarks are done, so what its
+    /// setup created (temp files, child processes, big buffers) doesn't
+    /// linger into the next category's measurements
+    pub fn on_teardown(&mut self, f: impl FnOnce() + 'static) {
+        self.teardowns.0.push(Box::new(f));
+    }
+
+    /// Run the current category's teardowns, most recently registered
+    /// first; also done by [`ResultCollector::begin_category`] and on drop
+    pub fn end_category(&mut self) {
+        self.teardowns.run();
+    }
+
+    /// Leave `count` indices unused for the benchmarks a disabled feature
+    /// would have registered here, so later ones are numbered as in a full build
+    pub fn skip_indices(&mut self, count: usize) {
+        self.next_index += count;
+    }
+
+    /// Record `result` under the current category, numbering it in
+    /// registration order
+    pub fn insert(&mut self, name: &str, mut result: BenchmarkResult) {
+        result.index = Some(self.next_index);
+        result.engine_path = result.engine_path.or(self.engine_path);
+        self.next_index += 1;
+        self.results
+            .entry(self.category.clone())
+            .or_default()
+            .insert(name.to_string(), result);
+    }
+
+    /// Result recorded under `name` in the current category
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut BenchmarkResult> {
+        self.results.get_mut(&self.category)?.get_mut(name)
+    }
+
+    /// Declare `criteria` for the already-run benchmark `name`, to be
+    /// enforced by [`criteria::evaluate`]
+    pub fn require(&mut self, name: &str, criteria: Criteria) {
+        if let Some(result) = self.get_mut(name) {
+            result.criteria = Some(criteria);
+        }
+    }
+
+    /// Declare how many matches the already-run benchmark `name` reports;
+    /// any other observed count marks its result incorrect
+    pub fn expect_matches(&mut self, name: &str, expected: usize) {
+        let Some(result) = self.get_mut(name) else {
+            return;
+        };
+        result.expected_matches = Some(expected);
+        if let Some(observed) = result.observed_matches {
+            result.correct = Some(result.correct != Some(false) && observed == expected);
+            if observed != expected {
+                println!("⚠ {}: reported {} matches, expected {}", name, observed, expected);
+            }
+        }
+    }
+
+    /// Fill in the already-run benchmark `name`'s `tokens_per_sec`, taking
+    /// each match it reports as a token
+    pub fn counts_tokens(&mut self, name: &str) {
+        let Some(result) = self.get_mut(name) else {
+            return;
+        };
+        if result.time_ns > 0.0 {
+            result.tokens_per_sec = result.observed_matches.map(|tokens| tokens as f64 / result.time_ns * 1e9);
+        }
+    }
+
+    pub fn results(&self) -> &CategorizedResults {
+        &self.results
+    }
+}
+
+/// Run `f` on a watchdog-monitored thread and wait at most `deadline` for
+/// it; `None` means it hadn't returned in time. A thread can't be killed, so
+/// a late one is left running and keeps competing for the CPU. Without a
+/// deadline `f` runs inline.
+pub fn with_deadline<T, F>(deadline: Option<std::time::Duration>, f: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let Some(deadline) = deadline else {
+        return Some(f());
+    };
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let handle = std::thread::spawn(move || {
+        let _ = sender.send(f());
+    });
+    match receiver.recv_timeout(deadline) {
+        Ok(value) => Some(value),
+        Err(std::sync::mpsc::RecvTimeoutError::Timeout) => None,
+        // The sender was dropped without sending, so `f` panicked
+        Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => unreachable!("the watchdog thread always sends before exiting"),
+        },
+    }
+}
+
+/// Longest repeated unit [`describe_haystack`] spells out
+const MAX_DESCRIBED_UNIT: usize = 200;
+
+/// Characters of a non-repeating haystack [`describe_haystack`] quotes
+const HAYSTACK_PREVIEW_CHARS: usize = 40;
+
+/// Short human-readable description of a haystack, for documentation:
+/// `"abc" x 100 + 2 bytes (302 bytes, ASCII)` when it repeats a short unit
+/// (as [`make_test_string`] builds them), else a quoted prefix
+pub fn describe_haystack(text: &str) -> String {
+    let bytes = text.as_bytes();
+    // Shortest period via the KMP failure function
+    let mut failure = vec![0usize; bytes.len()];
+    for i in 1..bytes.len() {
+        let mut k = failure[i - 1];
+        while k > 0 && bytes[i] != bytes[k] {
+            k = failure[k - 1];
+        }
+        if bytes[i] == bytes[k] {
+            k += 1;
+        }
+        failure[i] = k;
+    }
+    let period = bytes.len() - failure.last().copied().unwrap_or(0);
+    let repeats = bytes.len() / period.max(1);
+
+    let encoding = if text.is_ascii() { "ASCII" } else { "UTF-8" };
+    let body = match text.get(..period) {
+        Some(unit) if repeats >= 2 && period <= MAX_DESCRIBED_UNIT => {
+            let remainder = bytes.len() - repeats * period;
+            if remainder > 0 {
+                format!("{:?} x {} + {} bytes", unit, repeats, remainder)
+            } else {
+                format!("{:?} x {}", unit, repeats)
+            }
+        }
+        _ if text.chars().count() > HAYSTACK_PREVIEW_CHARS => {
+            let preview: String = text.chars().take(HAYSTACK_PREVIEW_CHARS).collect();
+            format!("{:?}...", preview)
+        }
+        _ => format!("{:?}", text),
+    };
+    format!("{} ({} bytes, {})", body, bytes.len(), encoding)
+}
+
+/// FNV-1a hash of a haystack, the same on every run and platform
+pub fn haystack_digest(text: &str) -> u64 {
+    text.bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x100000001b3))
 }
 
 /// Generate test string of specified length by repeating pattern
@@ -60,26 +964,299 @@ pub fn make_mixed_content_text(length: usize) -> String {
     result
 }
 
-/// Benchmark timing infrastructure
-pub struct BenchmarkTimer {
+/// Generate a server log of `num_lines` lines followed by a final
+/// `shutdown complete` without a newline: mostly `INFO` requests and `WARN`
+/// slow queries ending in their duration, with every tenth line an `ERROR`
+/// followed by a two-frame numbered backtrace
+pub fn make_log_text(num_lines: usize) -> String {
+    let mut log = String::new();
+    for i in 0..num_lines {
+        let time = format!("2024-01-15T14:{:02}:{:02}", i / 60 % 60, i % 60);
+        let line = match i % 10 {
+            6 => format!("WARN {} worker-{} slow query took {}ms", time, i % 4, 200 + i % 300),
+            7 => format!("ERROR {} worker-{} request {} failed: upstream timeout", time, i % 4, i),
+            8 => "0: at worker::handle (src/worker.rs:88)".to_string(),
+            9 => "1: at server::dispatch (src/server.rs:142)".to_string(),
+            _ => format!("INFO {} worker-{} GET /api/items/{} 200 in {}ms", time, i % 4, i, 1 + i % 97),
+        };
+        log.push_str(&line);
+        log.push('\n');
+    }
+    log.push_str("shutdown complete");
+    log
+}
+
+/// Generate `num_records` support-ticket lines, each holding one email, one
+/// phone number and one 16-digit card number (spaced or dashed on alternate
+/// lines) among a date, an amount and a ticket number that aren't sensitive
+pub fn make_support_tickets(num_records: usize) -> String {
+    let mut tickets = String::new();
+    for i in 0..num_records {
+        let separator = if i % 2 == 0 { ' ' } else { '-' };
+        tickets.push_str(&format!(
+            "#{} 2024-03-{:02} customer{}@mail{}.example.com called from 555.{:03}.{:04} disputing ${}.{:02} on card \
+             4111{sep}1111{sep}{:04}{sep}{:04}, escalated to tier {}\n",
+            100000 + i,
+            1 + i % 28,
+            i,
+            i % 50,
+            200 + i % 800,
+            i % 10000,
+            10 + i % 990,
+            i % 100,
+            i / 10000 % 10000,
+            i % 10000,
+            1 + i % 3,
+            sep = separator,
+        ));
+    }
+    tickets
+}
+
+/// Generate `count` distinct pseudo-random lowercase words of 4 to 8
+/// letters, the same on every call
+pub fn make_vocabulary(count: usize) -> Vec<String> {
+    let mut state: u32 = 12345;
+    let mut next = || {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        (state >> 16) as usize
+    };
+    let mut seen = HashSet::new();
+    let mut words = Vec::with_capacity(count);
+    while words.len() < count {
+        let len = 4 + next() % 5;
+        let word: String = (0..len).map(|_| (b'a' + (next() % 26) as u8) as char).collect();
+        if seen.insert(word.clone()) {
+            words.push(word);
+        }
+    }
+    words
+}
+
+/// Generate `len` bases of pseudo-random DNA, the same on every call
+pub fn make_dna_sequence(len: usize) -> String {
+    const BASES: [char; 4] = ['A', 'C', 'G', 'T'];
+    let mut state: u32 = 12345;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(1103515245).wrapping_add(12345);
+            BASES[(state >> 16) as usize % BASES.len()]
+        })
+        .collect()
+}
+
+/// Median of a sample set (sorts in place)
+pub fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    if values.is_empty() {
+        0.0
+    } else if values.len() % 2 == 1 {
+        values[values.len() / 2]
+    } else {
+        (values[values.len() / 2 - 1] + values[values.len() / 2]) / 2.0
+    }
+}
+
+/// Nearest-rank percentile of an already sorted sample set
+pub fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Samples kept for the median and bimodality check when full sample
+/// retention is off; everything else is accumulated online
+const RESERVOIR_SAMPLES: usize = 4096;
+
+/// Benchmark timing infrastructure, measuring with clock `C`
+#[derive(Debug)]
+pub struct BenchmarkTimer<C: Clock = WallClock> {
+    clock: C,
     target_runtime_ns: u128,
     max_iterations: u64,
     min_sample_ns: u128,
     warmup_iterations: usize,
+    latency_samples: usize,
+    keep_samples: bool,
+    max_cv: f64,
+    max_reruns: u32,
+    rounds: u32,
+    fixed_iterations: Option<u64>,
+    min_outer_iterations: u64,
+    metrics: Vec<Box<dyn Metric>>,
 }
 
 impl BenchmarkTimer {
     pub fn new() -> Self {
+        Self::with_clock(WallClock::new())
+    }
+}
+
+impl<C: Clock> BenchmarkTimer<C> {
+    /// Timer measuring with `clock` instead of wall time
+    pub fn with_clock(clock: C) -> Self {
         Self {
+            clock,
             target_runtime_ns: 500_000_000, // 500ms target runtime
             max_iterations: 200_000,
             min_sample_ns: 1_000_000, // 1ms minimum per sample
             warmup_iterations: 10,
+            latency_samples: 0,
+            keep_samples: false,
+            max_cv: 0.05,
+            max_reruns: 2,
+            rounds: 1,
+            fixed_iterations: None,
+            min_outer_iterations: 10,
+            metrics: Vec::new(),
+        }
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Clock ticks elapsed since the reading `start`
+    fn elapsed(&self, start: u64) -> u64 {
+        self.clock.now().saturating_sub(start)
+    }
+
+    /// Keep sampling past the runtime target until at least `iterations`
+    /// samples were taken, so slow benchmarks still get a usable median
+    pub fn with_min_outer_iterations(mut self, iterations: u64) -> Self {
+        self.min_outer_iterations = iterations;
+        self
+    }
+
+    /// Run exactly `iterations` single-call samples per benchmark instead of
+    /// calibrating to a time target, so every run performs identical work
+    /// (for instruction-count tools such as cachegrind). Disables re-runs.
+    pub fn with_fixed_iterations(mut self, iterations: u64) -> Self {
+        self.fixed_iterations = Some(iterations);
+        self
+    }
+
+    pub fn fixed_iterations(&self) -> Option<u64> {
+        self.fixed_iterations
+    }
+
+    /// Re-run a benchmark up to `max_reruns` times while its coefficient of
+    /// variation exceeds `max_cv`, keeping the lowest-variance round
+    pub fn with_noise_rerun(mut self, max_cv: f64, max_reruns: u32) -> Self {
+        self.max_cv = max_cv;
+        self.max_reruns = max_reruns;
+        self
+    }
+
+    /// Always run at least `rounds` full rounds, so every benchmark gets a
+    /// [`BenchmarkResult::stability_score`]; noise re-runs come on top
+    pub fn with_rounds(mut self, rounds: u32) -> Self {
+        self.rounds = rounds.max(1);
+        self
+    }
+
+    /// Also collect `metrics` over every measured round (see [`metric`])
+    pub fn with_metrics(mut self, metrics: Vec<Box<dyn Metric>>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    pub fn metrics(&self) -> &[Box<dyn Metric>] {
+        &self.metrics
+    }
+
+    /// Keep every sample in the result for significance testing. Without
+    /// it, memory stays flat: moments are accumulated online and the median
+    /// comes from a fixed-size reservoir of samples.
+    pub fn with_sample_retention(mut self, keep_samples: bool) -> Self {
+        self.keep_samples = keep_samples;
+        self
+    }
+
+    /// Also collect `samples` single-call latencies per benchmark
+    pub fn with_latency_samples(mut self, samples: usize) -> Self {
+        self.latency_samples = samples;
+        self
+    }
+
+    pub fn latency_mode(&self) -> bool {
+        self.latency_samples > 0
+    }
+
+    /// Time one call per sample to expose the per-call latency distribution
+    /// that batched throughput measurement averages away
+    pub fn bench_latency<F>(&self, mut f: F) -> LatencyStats
+    where
+        F: FnMut(),
+    {
+        for _ in 0..self.warmup_iterations {
+            f();
+        }
+
+        let mut overhead_ns: Vec<f64> = (0..1000)
+            .map(|_| {
+                let start = self.clock.now();
+                self.elapsed(start) as f64
+            })
+            .collect();
+        let clock_overhead_ns = median(&mut overhead_ns);
+
+        let mut samples_ns: Vec<f64> = Vec::with_capacity(self.latency_samples);
+        for _ in 0..self.latency_samples {
+            let start = self.clock.now();
+            f();
+            samples_ns.push(self.elapsed(start) as f64);
+        }
+        samples_ns.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        LatencyStats {
+            samples: samples_ns.len(),
+            min_ns: samples_ns.first().copied().unwrap_or(0.0),
+            p50_ns: percentile(&samples_ns, 50.0),
+            p90_ns: percentile(&samples_ns, 90.0),
+            p99_ns: percentile(&samples_ns, 99.0),
+            p999_ns: percentile(&samples_ns, 99.9),
+            max_ns: samples_ns.last().copied().unwrap_or(0.0),
+            clock_overhead_ns,
         }
     }
 
     /// Run a benchmark function and measure its performance using median timing
     pub fn bench_function<F>(&self, mut f: F) -> BenchmarkResult
+    where
+        F: FnMut(),
+    {
+        let mut best = self.bench_round(&mut f);
+        let mut round_medians = vec![best.time_ns];
+        let mut reran = 0;
+        // Re-running depends on measured noise, which would make the amount
+        // of work vary between fixed-iteration runs
+        let max_reruns = if self.fixed_iterations.is_some() { 0 } else { self.max_reruns };
+        loop {
+            if round_medians.len() as u32 >= self.rounds {
+                if reran >= max_reruns || !best.cv.is_some_and(|cv| cv > self.max_cv) {
+                    break;
+                }
+                reran += 1;
+            }
+            let round = self.bench_round(&mut f);
+            round_medians.push(round.time_ns);
+            if round.cv.unwrap_or(f64::INFINITY) < best.cv.unwrap_or(f64::INFINITY) {
+                best = round;
+            }
+        }
+        best.reran = reran;
+        best.stability_score = stats::stability_score(&round_medians);
+        if round_medians.len() > 1 {
+            best.round_medians_ns = Some(round_medians);
+        }
+        best
+    }
+
+    /// One warmup + calibration + measurement round
+    fn bench_round<F>(&self, f: &mut F) -> BenchmarkResult
     where
         F: FnMut(),
     {
@@ -88,51 +1265,100 @@ impl BenchmarkTimer {
             f();
         }
 
-        // Auto-calibrate: measure one run, scale if needed
-        let cal_start = std::time::Instant::now();
-        f();
-        let cal_elapsed = cal_start.elapsed().as_nanos();
-        let repetitions = if cal_elapsed < self.min_sample_ns {
-            (self.min_sample_ns / cal_elapsed) as usize + 1
-        } else {
-            1
+        let (repetitions, target_runtime_ns, max_iterations) = match self.fixed_iterations {
+            Some(iterations) => (1, u128::MAX, iterations),
+            None => {
+                // Auto-calibrate: measure one run, scale if needed
+                let cal_start = self.clock.now();
+                f();
+                let cal_elapsed = self.elapsed(cal_start).max(1) as u128;
+                let repetitions = if cal_elapsed < self.min_sample_ns {
+                    (self.min_sample_ns / cal_elapsed) as usize + 1
+                } else {
+                    1
+                };
+                (repetitions, self.target_runtime_ns, self.max_iterations)
+            }
         };
 
-        let mut times_ns: Vec<f64> = Vec::new();
+        let capacity = if self.keep_samples { usize::MAX } else { RESERVOIR_SAMPLES };
+        let mut times_ns = Reservoir::new(capacity);
+        let mut cycles_per_rep = Reservoir::new(RESERVOIR_SAMPLES);
+        let mut online = OnlineStats::default();
+        let mut histogram = Histogram::from_samples(&[]);
         let mut total_time_ns = 0u128;
         let mut iterations = 0u64;
 
-        while total_time_ns < self.target_runtime_ns && iterations < self.max_iterations {
-            let start = std::time::Instant::now();
+        let metric_starts: Vec<u64> = self.metrics.iter().map(|metric| metric.start()).collect();
+        while (total_time_ns < target_runtime_ns || iterations < self.min_outer_iterations)
+            && iterations < max_iterations
+        {
+            let start = self.clock.now();
+            let cycles_start = cycles::read();
             for _ in 0..repetitions {
                 f();
             }
-            let duration = start.elapsed();
-            let elapsed = duration.as_nanos();
+            let cycles_end = cycles::read();
+            let elapsed = self.elapsed(start) as u128;
 
             total_time_ns += elapsed;
             iterations += 1;
-            times_ns.push(elapsed as f64 / repetitions as f64);
+            let time_ns = elapsed as f64 / repetitions as f64;
+            times_ns.push(time_ns);
+            online.push(time_ns);
+            histogram.record(time_ns, 1);
+            if let (Some(c0), Some(c1)) = (cycles_start, cycles_end) {
+                cycles_per_rep.push(c1.wrapping_sub(c0) as f64 / repetitions as f64);
+            }
         }
 
-        // Compute median
-        times_ns.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        let median_time_ns = if times_ns.is_empty() {
-            0.0
-        } else if times_ns.len() % 2 == 1 {
-            times_ns[times_ns.len() / 2]
+        // Stopped in reverse, so each metric brackets those started after it
+        let operations = iterations * repetitions as u64;
+        let metrics: BTreeMap<String, MetricValue> = self
+            .metrics
+            .iter()
+            .zip(metric_starts)
+            .rev()
+            .filter_map(|(metric, start)| {
+                let total = metric.stop(start)?;
+                Some((metric.name().to_string(), metric.report(total, operations)))
+            })
+            .collect();
+
+        let bimodal = stats::is_bimodal(times_ns.samples());
+        let mut times_ns = times_ns.into_samples();
+        let samples = self.keep_samples.then(|| times_ns.clone());
+        let median_time_ns = median(&mut times_ns);
+        let mut cycles_per_rep = cycles_per_rep.into_samples();
+        let median_cycles = if cycles_per_rep.is_empty() {
+            None
         } else {
-            (times_ns[times_ns.len() / 2 - 1] + times_ns[times_ns.len() / 2]) / 2.0
+            Some(median(&mut cycles_per_rep))
         };
 
         BenchmarkResult {
             time_ns: median_time_ns,
             time_ms: median_time_ns / 1_000_000.0,
             iterations: iterations * repetitions as u64,
+            cycles: median_cycles,
+            samples,
+            histogram: Some(histogram),
+            summary: online.summary(),
+            outer_iterations: Some(iterations),
+            bimodal,
+            cv: online.cv(),
+            metrics: (!metrics.is_empty()).then_some(metrics),
+            ..Default::default()
         }
     }
 }
 
+impl Default for BenchmarkTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Test data constants matching Python benchmarks
 pub const SHORT_TEXT: &str = "hello world this is a test with hello again and hello there";
 pub const EMAIL_TEXT: &str = "test@example.com user@test.org admin@example.com support@example.com no-reply@example.com";
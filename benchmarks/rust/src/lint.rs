@@ -0,0 +1,149 @@
+//! Static checks over the registered benchmark definitions
+
+use crate::BenchDef;
+use regex_automata::util::syntax;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A problem found in the suite definitions
+#[derive(Debug, Clone)]
+pub enum LintIssue {
+    /// The same pattern and operation over the same haystack, built the same
+    /// way, registered under several names
+    DuplicatePattern {
+        pattern: String,
+        operation: String,
+        names: Vec<String>,
+    },
+    /// The pattern never matches its haystack, so the benchmark only
+    /// measures a failed scan
+    ZeroMatches { name: String },
+    /// The haystack is shorter than the shortest possible match
+    HaystackTooShort {
+        name: String,
+        haystack_len: usize,
+        min_match_len: usize,
+    },
+    /// A category was started but registered no benchmarks
+    EmptyCategory { category: String },
+}
+
+impl LintIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            LintIssue::DuplicatePattern { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for LintIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let level = match self.severity() {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        match self {
+            LintIssue::DuplicatePattern {
+                pattern,
+                operation,
+                names,
+            } => write!(
+                f,
+                "{}: pattern {:?} ({}) is duplicated across {}",
+                level,
+                pattern,
+                operation,
+                names.join(", ")
+            ),
+            LintIssue::ZeroMatches { name } => {
+                write!(f, "{}: {} never matches its haystack", level, name)
+            }
+            LintIssue::HaystackTooShort {
+                name,
+                haystack_len,
+                min_match_len,
+            } => write!(
+                f,
+                "{}: {} haystack is {} bytes but the shortest match is {}",
+                level, name, haystack_len, min_match_len
+            ),
+            LintIssue::EmptyCategory { category } => {
+                write!(f, "{}: category {} has no benchmarks", level, category)
+            }
+        }
+    }
+}
+
+/// Minimum match length of `pattern`, if it parses as a Unicode regex
+fn min_match_len(pattern: &str) -> Option<usize> {
+    syntax::parse(pattern).ok()?.properties().minimum_len()
+}
+
+/// Check `definitions` for duplicated patterns, benchmarks that can't match,
+/// and categories without members
+pub fn lint(definitions: &[BenchDef], categories: &[String]) -> Vec<LintIssue> {
+    let mut issues = Vec::new();
+
+    // Bespoke `Custom` operations reuse patterns on purpose (e.g. the same
+    // literal over several encodings), so only standard operations count.
+    // The same pattern over another haystack or with other builder options
+    // (lengths of a scaling series, Unicode vs ASCII twins) is no duplicate
+    type Key<'a> = (&'a str, &'a str, usize, Option<u64>, Option<&'a str>);
+    let mut by_pattern: BTreeMap<Key, Vec<String>> = BTreeMap::new();
+    for def in definitions.iter().filter(|def| def.operation != "Custom") {
+        if let Some(pattern) = &def.pattern {
+            let key = (
+                pattern.as_str(),
+                def.operation.as_str(),
+                def.haystack_len,
+                def.haystack_digest,
+                def.builder.as_deref(),
+            );
+            by_pattern.entry(key).or_default().push(def.name.clone());
+        }
+    }
+    for ((pattern, operation, ..), names) in by_pattern {
+        if names.len() > 1 {
+            issues.push(LintIssue::DuplicatePattern {
+                pattern: pattern.to_string(),
+                operation: operation.to_string(),
+                names,
+            });
+        }
+    }
+
+    for def in definitions {
+        if def.match_count == Some(0) && !def.expects_no_match {
+            issues.push(LintIssue::ZeroMatches {
+                name: def.name.clone(),
+            });
+        }
+        // A zero-length haystack means the benchmark has none (e.g. compile
+        // benchmarks), not that it is too short
+        let min_len = def.pattern.as_deref().and_then(min_match_len);
+        if let Some(min_match_len) = min_len.filter(|&min| def.haystack_len > 0 && def.haystack_len < min) {
+            issues.push(LintIssue::HaystackTooShort {
+                name: def.name.clone(),
+                haystack_len: def.haystack_len,
+                min_match_len,
+            });
+        }
+    }
+
+    for category in categories {
+        if !definitions.iter().any(|def| &def.category == category) {
+            issues.push(LintIssue::EmptyCategory {
+                category: category.clone(),
+            });
+        }
+    }
+
+    issues
+}
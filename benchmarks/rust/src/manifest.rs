@@ -0,0 +1,136 @@
+//! Definition-level manifests of a benchmark suite: what each benchmark
+//! runs rather than how fast, for checking that the engine harnesses
+//! benchmark the same inputs
+//!
+//! Other harnesses' manifests are JSON files of the same shape. A results
+//! file serves as one too, through the inputs it recorded per benchmark;
+//! fields only one side records are never reported as mismatched.
+
+use crate::{registration_order, BenchDef, BenchmarkResults};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// What one benchmark runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+    /// Haystack size in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub haystack_len: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operation: Option<String>,
+    /// Operations per timed call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inner_iterations: Option<usize>,
+}
+
+/// Every benchmark of one engine's suite, in registration order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub engine: String,
+    pub benchmarks: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    /// Manifest of the suite registering `definitions`
+    pub fn from_definitions(engine: &str, definitions: &[BenchDef]) -> Self {
+        Manifest {
+            engine: engine.to_string(),
+            benchmarks: definitions
+                .iter()
+                .map(|def| ManifestEntry {
+                    name: def.name.clone(),
+                    pattern: def.pattern.clone(),
+                    haystack_len: Some(def.haystack_len),
+                    operation: Some(def.operation.clone()),
+                    inner_iterations: Some(def.inner_iterations),
+                })
+                .collect(),
+        }
+    }
+
+    /// Manifest of the inputs `results` recorded for each benchmark
+    pub fn from_results(results: &BenchmarkResults) -> Self {
+        let mut flat: Vec<_> = results.flat().into_iter().collect();
+        flat.sort_by_key(|&(name, result)| registration_order(name, result));
+        Manifest {
+            engine: results.engine.clone(),
+            benchmarks: flat
+                .into_iter()
+                .map(|(name, result)| ManifestEntry {
+                    name: name.to_string(),
+                    pattern: result.pattern.clone(),
+                    haystack_len: result.haystack_len,
+                    operation: result.operation.clone(),
+                    inner_iterations: result.inner_iterations,
+                })
+                .collect(),
+        }
+    }
+
+    /// Load a manifest file, or derive one from a results file
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = crate::archive::read_to_string(path)?;
+        if let Ok(manifest) = serde_json::from_str(&content) {
+            return Ok(manifest);
+        }
+        let results = BenchmarkResults::load(path)
+            .map_err(|e| format!("{} is neither a manifest nor a results file: {}", path, e))?;
+        Ok(Self::from_results(&results))
+    }
+}
+
+/// Every difference of `theirs` from `ours`, one description each: the
+/// benchmarks missing from it, those only it defines, then those both
+/// define differently, each in its manifest's order
+pub fn diff(ours: &Manifest, theirs: &Manifest) -> Vec<String> {
+    let theirs_by_name: HashMap<&str, &ManifestEntry> =
+        theirs.benchmarks.iter().map(|entry| (entry.name.as_str(), entry)).collect();
+    let ours_by_name: HashMap<&str, &ManifestEntry> =
+        ours.benchmarks.iter().map(|entry| (entry.name.as_str(), entry)).collect();
+    let mut differences: Vec<String> = ours
+        .benchmarks
+        .iter()
+        .filter(|entry| !theirs_by_name.contains_key(entry.name.as_str()))
+        .map(|entry| format!("{}: missing from {}", entry.name, theirs.engine))
+        .collect();
+    differences.extend(
+        theirs
+            .benchmarks
+            .iter()
+            .filter(|entry| !ours_by_name.contains_key(entry.name.as_str()))
+            .map(|entry| format!("{}: only in {}", entry.name, theirs.engine)),
+    );
+    for entry in &ours.benchmarks {
+        let Some(other) = theirs_by_name.get(entry.name.as_str()) else {
+            continue;
+        };
+        let mut mismatches = Vec::new();
+        if let (Some(a), Some(b)) = (&entry.pattern, &other.pattern) {
+            if a != b {
+                mismatches.push(format!("pattern {:?} vs {:?}", a, b));
+            }
+        }
+        if let (Some(a), Some(b)) = (entry.haystack_len, other.haystack_len) {
+            if a != b {
+                mismatches.push(format!("haystack {} vs {} bytes", a, b));
+            }
+        }
+        if let (Some(a), Some(b)) = (&entry.operation, &other.operation) {
+            if a != b {
+                mismatches.push(format!("operation {} vs {}", a, b));
+            }
+        }
+        if let (Some(a), Some(b)) = (entry.inner_iterations, other.inner_iterations) {
+            if a != b {
+                mismatches.push(format!("{} vs {} operations per timed call", a, b));
+            }
+        }
+        if !mismatches.is_empty() {
+            differences.push(format!("{}: {}", entry.name, mismatches.join(", ")));
+        }
+    }
+    differences
+}
@@ -0,0 +1,71 @@
+//! Allocation counting and peak-RSS tracking for non-timing criteria
+//!
+//! Allocations are only counted when the binary installs
+//! [`CountingAllocator`] as its `#[global_allocator]`; without it
+//! [`allocations_during`] returns `None`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// System allocator that counts every allocation, process-wide
+#[derive(Debug, Default)]
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        INSTALLED.store(true, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations (including reallocations) made while `f` runs, by any
+/// thread; `None` if [`CountingAllocator`] isn't installed
+pub fn allocations_during<F: FnOnce()>(f: F) -> Option<u64> {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    f();
+    let after = ALLOCATIONS.load(Ordering::Relaxed);
+    INSTALLED.load(Ordering::Relaxed).then(|| after - before)
+}
+
+/// Allocations made so far by the whole process; `None` if
+/// [`CountingAllocator`] isn't installed
+pub fn allocation_count() -> Option<u64> {
+    let count = ALLOCATIONS.load(Ordering::Relaxed);
+    INSTALLED.load(Ordering::Relaxed).then_some(count)
+}
+
+/// Reset the process's peak RSS to its current RSS, so [`peak_rss_bytes`]
+/// covers only what runs afterwards; returns whether the kernel supports it
+pub fn reset_peak_rss() -> bool {
+    // Writing 5 to clear_refs resets VmHWM (Linux 4.0+)
+    std::fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// Peak resident set size of the process in bytes (`VmHWM`), Linux only
+pub fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let kib: u64 = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
@@ -0,0 +1,78 @@
+//! Merging repeated identical runs into one median-of-runs result set
+//!
+//! The interleaved methodology runs each engine several times, alternating
+//! between them, so slow drift in the machine hits every engine alike. Each
+//! benchmark's merged time is the median of its runs' times; the other
+//! fields come from the run closest to that median, so they stay consistent
+//! with a real measurement. The runs' times are kept as `round_medians_ns`
+//! with their stability score, as `--rounds` does within a single run.
+
+use crate::{median, stats, Aggregates, BenchmarkResults, CategorizedResults};
+
+/// The median-of-runs result set of `runs`, which must all come from the
+/// same engine and clock; timestamped with the latest run
+pub fn merge(runs: &[BenchmarkResults]) -> Result<BenchmarkResults, String> {
+    let first = runs.first().ok_or("no runs to merge")?;
+    if let Some(other) = runs.iter().find(|run| (&run.engine, &run.clock) != (&first.engine, &first.clock)) {
+        return Err(format!(
+            "can't merge runs of different engines or clocks: {} ({}) and {} ({})",
+            first.engine,
+            first.clock.as_deref().unwrap_or("wall"),
+            other.engine,
+            other.clock.as_deref().unwrap_or("wall")
+        ));
+    }
+
+    let mut results = CategorizedResults::new();
+    for run in runs {
+        for (category, benches) in &run.results {
+            for name in benches.keys() {
+                let merged = results.entry(category.clone()).or_default();
+                if merged.contains_key(name) {
+                    continue;
+                }
+                let measured: Vec<_> = runs
+                    .iter()
+                    .filter_map(|run| run.results.get(category)?.get(name))
+                    .filter(|result| result.is_measured())
+                    .collect();
+                let times: Vec<f64> = measured.iter().map(|result| result.time_ns).collect();
+                let time_ns = median(&mut times.clone());
+                let closest = measured
+                    .iter()
+                    .copied()
+                    .min_by(|a, b| (a.time_ns - time_ns).abs().total_cmp(&(b.time_ns - time_ns).abs()));
+                // Not measured in any run: keep how the first run recorded it
+                let Some(closest) = closest else {
+                    merged.insert(name.clone(), benches[name].clone());
+                    continue;
+                };
+                let mut result = closest.clone();
+                if time_ns > 0.0 {
+                    result.throughput_mb_s = result.throughput_mb_s.map(|mb_s| mb_s * closest.time_ns / time_ns);
+                    result.tokens_per_sec = result.tokens_per_sec.map(|rate| rate * closest.time_ns / time_ns);
+                }
+                result.time_ns = time_ns;
+                result.time_ms = time_ns / 1_000_000.0;
+                result.stability_score = stats::stability_score(&times);
+                result.round_medians_ns = Some(times);
+                merged.insert(name.clone(), result);
+            }
+        }
+    }
+
+    let latest = runs.iter().max_by(|a, b| a.timestamp.cmp(&b.timestamp)).unwrap_or(first);
+    Ok(BenchmarkResults {
+        schema_version: first.schema_version,
+        engine: first.engine.clone(),
+        timestamp: latest.timestamp.clone(),
+        aggregates: Aggregates::from_results(&results),
+        results,
+        environment: first.environment.clone(),
+        build_info: first.build_info.clone(),
+        clock: first.clock.clone(),
+        stability: None,
+        unstable_environment: runs.iter().any(|run| run.unstable_environment),
+        setup_costs: first.setup_costs.clone(),
+    })
+}
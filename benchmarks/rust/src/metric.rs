@@ -0,0 +1,141 @@
+//! Extra metrics collected alongside the timing clock
+//!
+//! A metric brackets each measured round (not each sample, so it adds no
+//! overhead to the timed region), which lets one run record wall time, CPU
+//! time, retired instructions, allocations and peak RSS together instead of
+//! re-running the suite once per `--clock`.
+
+use crate::clock::{self, Clock};
+use crate::memory;
+use serde::{Deserialize, Serialize};
+use std::fmt::Debug;
+
+/// Names accepted by [`select`]: every clock, plus `allocations` and
+/// `peak_rss`
+pub const METRIC_NAMES: &[&str] = &["wall", "cpu", "tsc", "instructions", "allocations", "peak_rss"];
+
+/// A quantity measured over a whole round of a benchmark
+pub trait Metric: Debug + Send + Sync {
+    /// Key the value is recorded under in the result's `metrics`
+    fn name(&self) -> &'static str;
+
+    fn unit(&self) -> &'static str;
+
+    /// Called right before the measured samples; the reading is handed back
+    /// to [`Metric::stop`]
+    fn start(&self) -> u64;
+
+    /// Called right after the measured samples; the round's total, or
+    /// `None` if it couldn't be measured
+    fn stop(&self, start: u64) -> Option<f64>;
+
+    /// Whether the total grows with the work done, so it's reported per
+    /// operation; a level such as peak RSS is reported as is
+    fn per_operation(&self) -> bool {
+        true
+    }
+
+    /// The value recorded for a round that ran `operations` operations
+    fn report(&self, total: f64, operations: u64) -> MetricValue {
+        let per_op = self.per_operation();
+        MetricValue {
+            value: if per_op { total / operations.max(1) as f64 } else { total },
+            unit: self.unit().to_string(),
+            per_op,
+        }
+    }
+}
+
+/// One metric's value for a benchmark
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricValue {
+    pub value: f64,
+    pub unit: String,
+    /// Averaged per operation (e.g. instructions) rather than a level over
+    /// the round (e.g. peak RSS)
+    pub per_op: bool,
+}
+
+/// Any [`Clock`] as a metric: the ticks elapsed over the round
+#[derive(Debug)]
+pub struct ClockMetric<C>(pub C);
+
+impl<C: Clock + Send + Sync> Metric for ClockMetric<C> {
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+    fn unit(&self) -> &'static str {
+        self.0.unit()
+    }
+    fn start(&self) -> u64 {
+        self.0.now()
+    }
+    fn stop(&self, start: u64) -> Option<f64> {
+        Some(self.0.now().saturating_sub(start) as f64)
+    }
+}
+
+/// Heap allocations, when the counting allocator is installed
+#[derive(Debug, Default)]
+pub struct AllocationsMetric;
+
+impl Metric for AllocationsMetric {
+    fn name(&self) -> &'static str {
+        "allocations"
+    }
+    fn unit(&self) -> &'static str {
+        "allocations"
+    }
+    fn start(&self) -> u64 {
+        memory::allocation_count().unwrap_or(0)
+    }
+    fn stop(&self, start: u64) -> Option<f64> {
+        memory::allocation_count().map(|count| count.saturating_sub(start) as f64)
+    }
+}
+
+/// Process peak RSS over the round (Linux only)
+#[derive(Debug, Default)]
+pub struct PeakRssMetric;
+
+impl Metric for PeakRssMetric {
+    fn name(&self) -> &'static str {
+        "peak_rss"
+    }
+    fn unit(&self) -> &'static str {
+        "bytes"
+    }
+    fn start(&self) -> u64 {
+        memory::reset_peak_rss();
+        0
+    }
+    fn stop(&self, _start: u64) -> Option<f64> {
+        memory::peak_rss_bytes().map(|bytes| bytes as f64)
+    }
+    fn per_operation(&self) -> bool {
+        false
+    }
+}
+
+/// Metrics for a comma-separated `--metrics` list (see [`METRIC_NAMES`])
+pub fn select(names: &str) -> Result<Vec<Box<dyn Metric>>, String> {
+    let mut metrics: Vec<Box<dyn Metric>> = Vec::new();
+    for name in names.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+        if metrics.iter().any(|metric| metric.name() == name) {
+            return Err(format!("metric {:?} given twice", name));
+        }
+        metrics.push(match name {
+            "allocations" => Box::new(AllocationsMetric),
+            "peak_rss" => Box::new(PeakRssMetric),
+            _ if clock::CLOCK_NAMES.contains(&name) => Box::new(ClockMetric(clock::select(name)?)),
+            _ => {
+                return Err(format!(
+                    "unknown metric {:?} (choose from {})",
+                    name,
+                    METRIC_NAMES.join(", ")
+                ))
+            }
+        });
+    }
+    Ok(metrics)
+}
@@ -0,0 +1,171 @@
+//! Run each engine's harness as a subprocess and merge their results
+//!
+//! Every harness is given a deadline; a harness that hangs, crashes or
+//! writes unreadable results is recorded as an error for that engine while
+//! the others are still merged, so one broken engine doesn't cost the whole
+//! comparison.
+
+use crate::BenchmarkResults;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+fn default_timeout_secs() -> f64 {
+    1800.0
+}
+
+/// How to run one engine's harness
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineSpec {
+    pub name: String,
+    /// Program and arguments
+    pub command: Vec<String>,
+    /// Results file the harness writes
+    pub results: String,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: f64,
+}
+
+/// Why an engine's results are missing or incomplete
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EngineError {
+    /// The harness could not be started
+    Spawn { message: String },
+    /// The harness was killed after running past its deadline
+    Timeout { after_secs: f64 },
+    /// The harness exited unsuccessfully
+    Crashed { status: Option<i32>, stderr: String },
+    /// The results file is missing or isn't valid results JSON
+    MalformedResults { message: String },
+    /// Benchmarks other engines reported but this one didn't
+    MissingBenchmarks { names: Vec<String> },
+}
+
+/// Results of every engine that produced any, plus every engine's errors
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct MergedResults {
+    pub timestamp: String,
+    pub engines: BTreeMap<String, BenchmarkResults>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<String, Vec<EngineError>>,
+}
+
+/// Only the tail of a crashing harness's stderr is kept
+const STDERR_TAIL_BYTES: usize = 4096;
+
+/// How often a running harness is polled for exit
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Run one harness to completion (or its deadline) and load its results
+pub fn run_engine(spec: &EngineSpec) -> Result<BenchmarkResults, EngineError> {
+    let (program, args) = spec.command.split_first().ok_or_else(|| EngineError::Spawn {
+        message: "empty command".to_string(),
+    })?;
+    let deadline = Duration::try_from_secs_f64(spec.timeout_secs)
+        .ok()
+        .filter(|timeout| !timeout.is_zero())
+        .and_then(|timeout| Instant::now().checked_add(timeout))
+        .ok_or_else(|| EngineError::Spawn {
+            message: format!("timeout_secs must be a positive number of seconds, got {}", spec.timeout_secs),
+        })?;
+    // A stale file from an earlier run must not pass for this run's results
+    let _ = std::fs::remove_file(&spec.results);
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| EngineError::Spawn {
+            message: format!("{}: {}", program, e),
+        })?;
+
+    // Drain stderr on a thread so a chatty harness can't block on a full pipe
+    let mut stderr = child.stderr.take().expect("stderr is piped");
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buffer = Vec::new();
+        let _ = stderr.read_to_end(&mut buffer);
+        buffer
+    });
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(POLL_INTERVAL),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(EngineError::Timeout {
+                    after_secs: spec.timeout_secs,
+                });
+            }
+            Err(e) => {
+                return Err(EngineError::Spawn {
+                    message: e.to_string(),
+                })
+            }
+        }
+    };
+
+    let stderr = stderr_reader.join().unwrap_or_default();
+    if !status.success() {
+        let tail = &stderr[stderr.len().saturating_sub(STDERR_TAIL_BYTES)..];
+        return Err(EngineError::Crashed {
+            status: status.code(),
+            stderr: String::from_utf8_lossy(tail).trim().to_string(),
+        });
+    }
+
+    BenchmarkResults::load(&spec.results).map_err(|e| EngineError::MalformedResults {
+        message: e.to_string(),
+    })
+}
+
+/// Run every engine in turn and merge whatever results they produced
+pub fn orchestrate(specs: &[EngineSpec]) -> MergedResults {
+    let mut merged = MergedResults {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+    for spec in specs {
+        match run_engine(spec) {
+            Ok(results) => {
+                merged.engines.insert(spec.name.clone(), results);
+            }
+            Err(error) => merged.errors.entry(spec.name.clone()).or_default().push(error),
+        }
+    }
+
+    // Partial results are kept, but anything an engine skipped is recorded
+    let all: BTreeSet<String> = merged
+        .engines
+        .values()
+        .flat_map(|results| results.flat().into_keys().map(str::to_string))
+        .collect();
+    for (name, results) in &merged.engines {
+        let reported = results.flat();
+        let missing: Vec<String> = all
+            .iter()
+            .filter(|bench| !reported.contains_key(bench.as_str()))
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            merged
+                .errors
+                .entry(name.clone())
+                .or_default()
+                .push(EngineError::MissingBenchmarks { names: missing });
+        }
+    }
+    merged
+}
+
+/// Load an orchestration config: a JSON array of [`EngineSpec`]
+pub fn load_specs(path: &str) -> Result<Vec<EngineSpec>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path, e))?;
+    Ok(serde_json::from_str(&content).map_err(|e| format!("failed to parse {}: {}", path, e))?)
+}
@@ -0,0 +1,266 @@
+//! Standalone SVG charts of cross-engine comparisons, for release notes, and
+//! of one benchmark's history
+//!
+//! Like the HTML report's charts, these are written by hand rather than
+//! with a plotting crate: a few rectangles and circles don't justify the
+//! dependency, and SVG converts to PNG with any image tool.
+
+use crate::cross_engine::Row;
+use crate::report::escape_html;
+use crate::summary::format_ns;
+use crate::trend::TrendPoint;
+use std::fmt::Write;
+
+/// Directory in the results directory charts are written to
+pub const PLOTS_DIR: &str = "plots";
+
+/// `name` with everything but ASCII letters and digits replaced by `_`, so a
+/// category or benchmark name can't lead a chart's path outside [`PLOTS_DIR`]
+pub fn file_stem(name: &str) -> String {
+    name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect()
+}
+
+/// Fill of each engine's bars and points, in engine order
+const COLORS: [&str; 6] = ["#4e79a7", "#f28e2b", "#59a14f", "#e15759", "#76b7b2", "#b07aa1"];
+
+const LABEL_WIDTH: usize = 260;
+const BAR_WIDTH: usize = 360;
+const BAR_HEIGHT: usize = 10;
+/// Space above the first group, for the legend
+const LEGEND_HEIGHT: usize = 24;
+
+/// Scatter plot area and the margin around it for the axis labels
+const PLOT_SIZE: f64 = 360.0;
+const MARGIN: f64 = 60.0;
+
+/// Width of a time series per run, and the most commits labelled on its axis
+const RUN_WIDTH: f64 = 24.0;
+const MAX_COMMIT_LABELS: usize = 20;
+
+fn open(out: &mut String, width: usize, height: usize, title: &str) {
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" role=\"img\">\n<title>{}</title>",
+        width,
+        height,
+        escape_html(title)
+    );
+    let _ = writeln!(out, "<style>text{{font-size:11px;font-family:monospace}}</style>");
+    let _ = writeln!(out, "<rect width=\"100%\" height=\"100%\" fill=\"white\"/>");
+}
+
+/// Grouped horizontal bars of one category: a bar per engine for each
+/// benchmark, scaled to the slowest engine on that benchmark, since times
+/// between engines can differ by orders of magnitude
+pub fn grouped_bars(title: &str, rows: &[&Row], engines: &[&str]) -> String {
+    let group_height = engines.len() * BAR_HEIGHT + 8;
+    let mut out = String::new();
+    open(&mut out, LABEL_WIDTH + BAR_WIDTH + 100, LEGEND_HEIGHT + rows.len() * group_height, title);
+    for (engine, name) in engines.iter().enumerate() {
+        let x = LABEL_WIDTH + engine * 90;
+        let _ = writeln!(
+            out,
+            "<rect x=\"{}\" y=\"6\" width=\"10\" height=\"10\" fill=\"{}\"/><text x=\"{}\" y=\"15\">{}</text>",
+            x,
+            COLORS[engine % COLORS.len()],
+            x + 14,
+            escape_html(name)
+        );
+    }
+    for (group, row) in rows.iter().enumerate() {
+        let y = LEGEND_HEIGHT + group * group_height;
+        let _ = writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+            LABEL_WIDTH - 6,
+            y + group_height / 2 + 2,
+            escape_html(&row.name)
+        );
+        let slowest = row.times_ns.iter().flatten().copied().fold(0.0, f64::max);
+        for (engine, time) in row.times_ns.iter().enumerate() {
+            let Some(time) = time.filter(|_| slowest > 0.0) else {
+                continue;
+            };
+            let width = (time / slowest * BAR_WIDTH as f64).max(1.0);
+            let bar_y = y + engine * BAR_HEIGHT;
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\" fill=\"{}\"><title>{}: {}</title></rect>",
+                LABEL_WIDTH,
+                bar_y,
+                width,
+                BAR_HEIGHT - 1,
+                COLORS[engine % COLORS.len()],
+                escape_html(engines[engine]),
+                format_ns(time)
+            );
+            let _ = writeln!(
+                out,
+                "<text x=\"{:.1}\" y=\"{}\">{}</text>",
+                LABEL_WIDTH as f64 + width + 4.0,
+                bar_y + BAR_HEIGHT - 1,
+                format_ns(time)
+            );
+        }
+    }
+    let _ = writeln!(out, "</svg>");
+    out
+}
+
+/// Scatter plot of [`Row::ratio`] of `numerator` over `denominator`
+/// against the denominator's time, both on log scales, with the parity line
+/// at 1.0x: points above it are benchmarks where the numerator is slower
+pub fn ratio_scatter(title: &str, rows: &[Row], numerator: usize, denominator: usize) -> String {
+    let points: Vec<(&str, f64, f64)> = rows
+        .iter()
+        .filter_map(|row| Some((row.name.as_str(), row.times_ns[denominator]?, row.ratio(numerator, denominator)?)))
+        .filter(|&(_, time, ratio)| time > 0.0 && ratio > 0.0)
+        .collect();
+    let size = (PLOT_SIZE + 2.0 * MARGIN) as usize;
+    let mut out = String::new();
+    open(&mut out, size, size, title);
+    let _ = writeln!(out, "<text x=\"{}\" y=\"20\" text-anchor=\"middle\">{}</text>", size / 2, escape_html(title));
+    if points.is_empty() {
+        let _ = writeln!(out, "<text x=\"{}\" y=\"{}\">no benchmarks in common</text>", MARGIN, size / 2);
+        let _ = writeln!(out, "</svg>");
+        return out;
+    }
+
+    // Log ranges, widened so a single point or a flat axis still has a span
+    let range = |values: &mut dyn Iterator<Item = f64>| {
+        let (low, high) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+        (low - 0.1, high + 0.1)
+    };
+    let (x_low, x_high) = range(&mut points.iter().map(|&(_, time, _)| time.log10()));
+    let (y_low, y_high) = range(&mut points.iter().map(|&(_, _, ratio)| ratio.log10()).chain([0.0]));
+    let x_at = |time: f64| MARGIN + (time.log10() - x_low) / (x_high - x_low) * PLOT_SIZE;
+    let y_at = |ratio: f64| MARGIN + PLOT_SIZE - (ratio.log10() - y_low) / (y_high - y_low) * PLOT_SIZE;
+
+    let _ = writeln!(
+        out,
+        "<rect x=\"{m}\" y=\"{m}\" width=\"{s}\" height=\"{s}\" fill=\"none\" stroke=\"#999\"/>",
+        m = MARGIN,
+        s = PLOT_SIZE
+    );
+    let _ = writeln!(
+        out,
+        "<line x1=\"{}\" y1=\"{y:.1}\" x2=\"{}\" y2=\"{y:.1}\" stroke=\"#e15759\" stroke-dasharray=\"4\"/>\
+         <text x=\"{}\" y=\"{y:.1}\">1.0x</text>",
+        MARGIN,
+        MARGIN + PLOT_SIZE,
+        MARGIN + PLOT_SIZE + 4.0,
+        y = y_at(1.0)
+    );
+    for &(name, time, ratio) in &points {
+        let _ = writeln!(
+            out,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\" fill=\"{}\"><title>{}: {:.2}x at {}</title></circle>",
+            x_at(time),
+            y_at(ratio),
+            COLORS[numerator % COLORS.len()],
+            escape_html(name),
+            ratio,
+            format_ns(time)
+        );
+    }
+    // Axis extremes, in the units plotted
+    let bottom = MARGIN + PLOT_SIZE + 14.0;
+    let _ = writeln!(
+        out,
+        "<text x=\"{}\" y=\"{}\">{}</text><text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+        MARGIN,
+        bottom,
+        format_ns(10f64.powf(x_low)),
+        MARGIN + PLOT_SIZE,
+        bottom,
+        format_ns(10f64.powf(x_high))
+    );
+    let _ = writeln!(
+        out,
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{:.2}x</text>\
+         <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{:.2}x</text>",
+        MARGIN - 4.0,
+        MARGIN + 4.0,
+        10f64.powf(y_high),
+        MARGIN - 4.0,
+        MARGIN + PLOT_SIZE,
+        10f64.powf(y_low)
+    );
+    let _ = writeln!(out, "</svg>");
+    out
+}
+
+/// A benchmark's time across runs, oldest first, on a linear axis from
+/// zero; the points at `regressions` (see [`crate::trend::step_regressions`])
+/// are marked in red
+pub fn time_series(title: &str, points: &[TrendPoint], regressions: &[usize]) -> String {
+    let plot_width = (points.len().max(2) - 1) as f64 * RUN_WIDTH;
+    let width = (plot_width + 2.0 * MARGIN) as usize;
+    let height = (PLOT_SIZE / 2.0 + 2.0 * MARGIN) as usize;
+    let mut out = String::new();
+    // Wide enough for the title even with few runs
+    let title_width = title.chars().count() * 7 + 2 * MARGIN as usize;
+    open(&mut out, width.max(title_width), height, title);
+    let _ = writeln!(out, "<text x=\"{}\" y=\"20\">{}</text>", MARGIN, escape_html(title));
+    let slowest = points.iter().map(|point| point.time_ns).fold(0.0, f64::max);
+    if slowest <= 0.0 {
+        let _ = writeln!(out, "</svg>");
+        return out;
+    }
+    let plot_height = PLOT_SIZE / 2.0;
+    let x_at = |run: usize| MARGIN + run as f64 * RUN_WIDTH;
+    let y_at = |time: f64| MARGIN + plot_height - time / slowest * plot_height;
+
+    let _ = writeln!(
+        out,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#999\"/>",
+        MARGIN, MARGIN, plot_width, plot_height
+    );
+    let _ = writeln!(
+        out,
+        "<text x=\"{m:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>\
+         <text x=\"{m:.1}\" y=\"{:.1}\" text-anchor=\"end\">0</text>",
+        MARGIN + 4.0,
+        format_ns(slowest),
+        MARGIN + plot_height,
+        m = MARGIN - 4.0
+    );
+    let line: Vec<String> = points
+        .iter()
+        .enumerate()
+        .map(|(run, point)| format!("{:.1},{:.1}", x_at(run), y_at(point.time_ns)))
+        .collect();
+    let _ = writeln!(out, "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\"/>", line.join(" "), COLORS[0]);
+    let label_every = points.len().div_ceil(MAX_COMMIT_LABELS);
+    for (run, point) in points.iter().enumerate() {
+        let regressed = regressions.contains(&run);
+        let commit = &point.git_sha[..point.git_sha.len().min(7)];
+        let step = match run.checked_sub(1).map(|previous| points[previous].time_ns) {
+            Some(previous) if previous > 0.0 => format!(" ({:+.1}%)", (point.time_ns / previous - 1.0) * 100.0),
+            _ => String::new(),
+        };
+        let _ = writeln!(
+            out,
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{}\" fill=\"{}\"><title>{} {}: {}{}</title></circle>",
+            x_at(run),
+            y_at(point.time_ns),
+            if regressed { 5 } else { 3 },
+            if regressed { COLORS[3] } else { COLORS[0] },
+            escape_html(commit),
+            escape_html(&point.timestamp),
+            format_ns(point.time_ns),
+            step
+        );
+        if run % label_every == 0 || regressed {
+            let _ = writeln!(
+                out,
+                "<text x=\"{x:.1}\" y=\"{y:.1}\" transform=\"rotate(45 {x:.1} {y:.1})\">{}</text>",
+                escape_html(commit),
+                x = x_at(run),
+                y = MARGIN + plot_height + 12.0
+            );
+        }
+    }
+    let _ = writeln!(out, "</svg>");
+    out
+}
@@ -0,0 +1,131 @@
+//! Ed25519 signing of exported results files
+//!
+//! A signed results file gets a `<file>.sig` sidecar recording who signed it,
+//! their public key and the signature over the signer identity plus the exact
+//! file bytes. Published Mojo-vs-Rust numbers can then be told apart from
+//! ad-hoc runs by checking the key against the project's published one.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+
+/// Contents of a `.sig` sidecar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResultsSignature {
+    /// Free-form identity, e.g. `Jane Doe <jane@example.com>`
+    pub signer: String,
+    /// Hex-encoded Ed25519 public key
+    pub public_key: String,
+    /// Hex-encoded signature over [`signed_message`]
+    pub signature: String,
+}
+
+/// Outcome of checking a results file's sidecar
+#[derive(Debug, Clone)]
+pub enum Verification {
+    Unsigned,
+    Valid { signer: String, public_key: String },
+    Invalid { signer: String, reason: String },
+}
+
+/// Path of the signature sidecar for `results_path`
+pub fn signature_path(results_path: &str) -> String {
+    format!("{}.sig", results_path)
+}
+
+/// The signer identity is covered too, so it can't be swapped afterwards
+fn signed_message(signer: &str, content: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(signer.len() + 1 + content.len());
+    message.extend_from_slice(signer.as_bytes());
+    message.push(b'\n');
+    message.extend_from_slice(content);
+    message
+}
+
+/// Write a new hex-encoded secret key to `key_path`, returning the public key
+pub fn generate_key(key_path: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut seed = [0u8; 32];
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut urandom| urandom.read_exact(&mut seed))
+        .map_err(|e| format!("failed to read /dev/urandom: {}", e))?;
+    let key = SigningKey::from_bytes(&seed);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(key_path)
+        .and_then(|mut file| writeln!(file, "{}", hex::encode(seed)))
+        .map_err(|e| format!("failed to write {}: {}", key_path, e))?;
+    Ok(hex::encode(key.verifying_key().as_bytes()))
+}
+
+fn load_key(key_path: &str) -> Result<SigningKey, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(key_path)
+        .map_err(|e| format!("failed to read {}: {}", key_path, e))?;
+    let seed: [u8; 32] = hex::decode(content.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| format!("{}: expected a 64-digit hex secret key", key_path))?;
+    Ok(SigningKey::from_bytes(&seed))
+}
+
+/// Sign `results_path` with the key in `key_path`, writing the sidecar
+pub fn sign_file(
+    results_path: &str,
+    key_path: &str,
+    signer: &str,
+) -> Result<ResultsSignature, Box<dyn std::error::Error>> {
+    let key = load_key(key_path)?;
+    let content = std::fs::read(results_path)
+        .map_err(|e| format!("failed to read {}: {}", results_path, e))?;
+    let signature = ResultsSignature {
+        signer: signer.to_string(),
+        public_key: hex::encode(key.verifying_key().as_bytes()),
+        signature: hex::encode(key.sign(&signed_message(signer, &content)).to_bytes()),
+    };
+    std::fs::write(signature_path(results_path), serde_json::to_string_pretty(&signature)?)?;
+    Ok(signature)
+}
+
+/// Check `results_path` against its sidecar, if there is one
+pub fn verify_file(results_path: &str) -> Verification {
+    let Ok(sidecar) = std::fs::read_to_string(signature_path(results_path)) else {
+        return Verification::Unsigned;
+    };
+    let signature: ResultsSignature = match serde_json::from_str(&sidecar) {
+        Ok(signature) => signature,
+        Err(e) => {
+            return Verification::Invalid {
+                signer: "?".to_string(),
+                reason: format!("malformed signature file: {}", e),
+            }
+        }
+    };
+    match check(results_path, &signature) {
+        Ok(()) => Verification::Valid {
+            signer: signature.signer,
+            public_key: signature.public_key,
+        },
+        Err(reason) => Verification::Invalid {
+            signer: signature.signer,
+            reason,
+        },
+    }
+}
+
+fn check(results_path: &str, signature: &ResultsSignature) -> Result<(), String> {
+    let public_key: [u8; 32] = hex::decode(&signature.public_key)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("malformed public key")?;
+    let public_key = VerifyingKey::from_bytes(&public_key).map_err(|_| "malformed public key")?;
+    let sig: [u8; 64] = hex::decode(&signature.signature)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("malformed signature")?;
+    let content = std::fs::read(results_path).map_err(|e| e.to_string())?;
+    public_key
+        .verify(&signed_message(&signature.signer, &content), &Signature::from_bytes(&sig))
+        .map_err(|_| "signature does not match the file (modified after signing?)".to_string())
+}
@@ -0,0 +1,608 @@
+//! Renderings of a results file: GitHub-flavored markdown for pasting into
+//! PR descriptions, a self-contained HTML page with charts for CI artifacts,
+//! a few-line plain-text brief for scheduled runs to mail or post to chat,
+//! Google Benchmark JSON for dashboards that ingest C++ suites, JUnit XML
+//! for CI test UIs, and a collapsible comparison comment for pull requests
+
+use crate::compare::{self, DEFAULT_ALPHA};
+use crate::criteria;
+use crate::histogram::Histogram;
+use crate::summary::format_ns;
+use crate::{registration_order, BenchmarkResult, BenchmarkResults};
+use serde_json::json;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// One category's benchmarks, in registration order
+pub(crate) type Category<'a> = (&'a String, Vec<(&'a String, &'a BenchmarkResult)>);
+
+/// Categories ordered by where their first benchmark was registered, each
+/// with its benchmarks in registration order
+pub(crate) fn ordered_categories(results: &BenchmarkResults) -> Vec<Category<'_>> {
+    let mut categories: Vec<Category<'_>> = results
+        .results
+        .iter()
+        .map(|(category, benches)| {
+            let mut benches: Vec<_> = benches.iter().collect();
+            benches.sort_by_key(|(name, result)| registration_order(name, result));
+            (category, benches)
+        })
+        .collect();
+    categories.sort_by_key(|(category, benches)| {
+        let first = benches.first().map(|(name, result)| registration_order(name, result));
+        (first.map(|(index, _)| index), category.as_str())
+    });
+    categories
+}
+
+/// Escape the characters that would break a table cell
+fn escape_cell(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|").replace('\n', " ")
+}
+
+fn fmt_time(result: &BenchmarkResult) -> String {
+    if result.skipped {
+        "skipped".to_string()
+    } else if result.timed_out {
+        "timed out".to_string()
+    } else {
+        format!("{:.1}", result.time_ns)
+    }
+}
+
+fn fmt_option(value: Option<f64>, precision: usize) -> String {
+    value.map_or("-".to_string(), |v| format!("{:.*}", precision, v))
+}
+
+/// Render a table per category, in registration order, with each
+/// benchmark's median time and throughput, followed by the geomeans
+pub fn markdown(results: &BenchmarkResults) -> String {
+    let categories = ordered_categories(results);
+    let mut out = String::new();
+    let _ = writeln!(out, "## {} results ({})", escape_cell(&results.engine), results.timestamp);
+    if let Some(clock) = &results.clock {
+        let _ = writeln!(out, "\nClock: {}", clock);
+    }
+    for (category, benches) in &categories {
+        let _ = writeln!(out, "\n### {}\n", escape_cell(category));
+        let _ = writeln!(out, "| benchmark | median (ns) | throughput (MB/s) | cycles/byte | iterations |");
+        let _ = writeln!(out, "|:----------|------------:|------------------:|------------:|-----------:|");
+        for (name, result) in benches {
+            let iterations = if result.is_measured() {
+                result.iterations.to_string()
+            } else {
+                "-".to_string()
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                escape_cell(name),
+                fmt_time(result),
+                fmt_option(result.throughput_mb_s, 1),
+                fmt_option(result.cycles_per_byte, 3),
+                iterations
+            );
+        }
+    }
+
+    if let Some(aggregates) = &results.aggregates {
+        let _ = writeln!(out, "\n### Geomean\n");
+        let _ = writeln!(out, "| category | geomean (ns) |");
+        let _ = writeln!(out, "|:---------|-------------:|");
+        for (category, geomean_ns) in &aggregates.categories {
+            let _ = writeln!(out, "| {} | {:.1} |", escape_cell(category), geomean_ns);
+        }
+        let _ = writeln!(out, "| **overall** | **{:.1}** |", aggregates.geomean_ns);
+    }
+    out
+}
+
+/// Escape text for HTML content and attribute values
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2em;color:#222}\
+table{border-collapse:collapse}td,th{padding:2px 8px;border-bottom:1px solid #ddd}\
+td.num{text-align:right;font-variant-numeric:tabular-nums}\
+svg text{font-size:11px;font-family:monospace}.bar{fill:#4e79a7}.dist{fill:#f28e2b}\
+details{margin:0.5em 0}summary{cursor:pointer}";
+
+/// Width of the label column and of the longest bar in a category chart
+const LABEL_WIDTH: usize = 260;
+const BAR_WIDTH: usize = 420;
+const ROW_HEIGHT: usize = 18;
+
+/// Horizontal bar chart of the measured medians in one category, scaled to
+/// its slowest benchmark
+fn bar_chart(out: &mut String, benches: &[(&String, &BenchmarkResult)]) {
+    let max_ns = benches
+        .iter()
+        .filter(|(_, result)| result.is_measured())
+        .map(|(_, result)| result.time_ns)
+        .fold(0.0, f64::max);
+    let height = benches.len() * ROW_HEIGHT;
+    let _ = writeln!(
+        out,
+        "<svg width=\"{}\" height=\"{}\" role=\"img\">",
+        LABEL_WIDTH + BAR_WIDTH + 120,
+        height
+    );
+    for (row, (name, result)) in benches.iter().enumerate() {
+        let y = row * ROW_HEIGHT;
+        let _ = writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>",
+            LABEL_WIDTH - 6,
+            y + 13,
+            escape_html(name)
+        );
+        if !result.is_measured() || max_ns <= 0.0 {
+            let _ = writeln!(out, "<text x=\"{}\" y=\"{}\">{}</text>", LABEL_WIDTH, y + 13, fmt_time(result));
+            continue;
+        }
+        let width = (result.time_ns / max_ns * BAR_WIDTH as f64).max(1.0);
+        let _ = writeln!(
+            out,
+            "<rect class=\"bar\" x=\"{}\" y=\"{}\" width=\"{:.1}\" height=\"{}\"><title>{} ns</title></rect>",
+            LABEL_WIDTH,
+            y + 2,
+            width,
+            ROW_HEIGHT - 4,
+            fmt_time(result)
+        );
+        let _ = writeln!(
+            out,
+            "<text x=\"{:.1}\" y=\"{}\">{} ns</text>",
+            LABEL_WIDTH as f64 + width + 4.0,
+            y + 13,
+            fmt_time(result)
+        );
+    }
+    let _ = writeln!(out, "</svg>");
+}
+
+/// Column plot of a per-operation time distribution, one column per
+/// log-spaced bucket
+fn distribution_plot(out: &mut String, histogram: &Histogram) {
+    const WIDTH: f64 = 360.0;
+    const HEIGHT: f64 = 60.0;
+    let (Some(&(first, _)), Some(&(last, _))) = (histogram.counts.first(), histogram.counts.last()) else {
+        return;
+    };
+    let max_count = histogram.counts.iter().map(|&(_, count)| count).max().unwrap_or(1) as f64;
+    let column = WIDTH / (last - first + 1) as f64;
+    let _ = writeln!(out, "<svg width=\"{}\" height=\"{}\" role=\"img\">", WIDTH, HEIGHT + 14.0);
+    for &(index, count) in &histogram.counts {
+        let height = count as f64 / max_count * HEIGHT;
+        let (low, high) = histogram.bucket_bounds(index);
+        let _ = writeln!(
+            out,
+            "<rect class=\"dist\" x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\">\
+             <title>{:.1}-{:.1} ns: {}</title></rect>",
+            (index - first) as f64 * column,
+            HEIGHT - height,
+            column.max(1.0),
+            height,
+            low,
+            high,
+            count
+        );
+    }
+    let _ = writeln!(
+        out,
+        "<text x=\"0\" y=\"{}\">{:.1} ns</text><text x=\"{}\" y=\"{}\" text-anchor=\"end\">{:.1} ns</text>",
+        HEIGHT + 12.0,
+        histogram.bucket_bounds(first).0,
+        WIDTH,
+        HEIGHT + 12.0,
+        histogram.bucket_bounds(last).1
+    );
+    let _ = writeln!(out, "</svg>");
+}
+
+/// Render a single-file HTML report: a bar chart of medians per category,
+/// per-benchmark distribution plots (from the kept samples, or else the
+/// exported histogram) and the geomeans. Needs no scripts or network.
+pub fn html(results: &BenchmarkResults) -> String {
+    let mut out = String::new();
+    let title = format!("{} benchmark results", escape_html(&results.engine));
+    let _ = writeln!(out, "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(out, "<title>{}</title>\n<style>{}</style>\n</head>\n<body>", title, HTML_STYLE);
+    let _ = writeln!(out, "<h1>{}</h1>", title);
+    let _ = write!(out, "<p>{}", escape_html(&results.timestamp));
+    if let Some(clock) = &results.clock {
+        let _ = write!(out, ", clock: {}", escape_html(clock));
+    }
+    if results.unstable_environment {
+        let _ = write!(out, ", <strong>unstable environment</strong>");
+    }
+    let _ = writeln!(out, "</p>");
+
+    if let Some(aggregates) = &results.aggregates {
+        let _ = writeln!(out, "<h2>Geomean</h2>\n<table>\n<tr><th>category</th><th>geomean (ns)</th></tr>");
+        for (category, geomean_ns) in &aggregates.categories {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td class=\"num\">{:.1}</td></tr>",
+                escape_html(category),
+                geomean_ns
+            );
+        }
+        let _ = writeln!(
+            out,
+            "<tr><th>overall</th><th class=\"num\">{:.1}</th></tr>\n</table>",
+            aggregates.geomean_ns
+        );
+    }
+
+    for (category, benches) in ordered_categories(results) {
+        let _ = writeln!(out, "<h2>{}</h2>", escape_html(category));
+        bar_chart(&mut out, &benches);
+        let distributions: Vec<(&String, Histogram)> = benches
+            .iter()
+            .filter(|(_, result)| result.is_measured())
+            .filter_map(|(name, result)| {
+                let histogram = match &result.samples {
+                    Some(samples) => Histogram::from_samples(samples),
+                    None => result.histogram.clone()?,
+                };
+                Some((*name, histogram))
+            })
+            .collect();
+        if distributions.is_empty() {
+            continue;
+        }
+        let _ = writeln!(out, "<details><summary>Distributions</summary>");
+        for (name, histogram) in &distributions {
+            let _ = writeln!(out, "<h3>{}</h3>", escape_html(name));
+            distribution_plot(&mut out, histogram);
+        }
+        let _ = writeln!(out, "</details>");
+    }
+    let _ = writeln!(out, "</body>\n</html>");
+    out
+}
+
+/// Regressions and improvements a PR comment leads with
+const PR_COMMENT_CHANGES: usize = 5;
+
+/// Compact markdown comparing `candidate` against `baseline`, for a bot to
+/// post on a pull request: the geomean, the largest regressions and
+/// improvements, and every benchmark in a collapsed `<details>` block.
+/// Deltas beyond noise (see [`compare::Comparison::beyond_noise`]) are bold.
+pub fn pr_comment(baseline: &BenchmarkResults, candidate: &BenchmarkResults) -> String {
+    let comparisons = compare::compare(baseline, candidate, DEFAULT_ALPHA);
+    let fmt_delta = |c: &compare::Comparison| {
+        if c.beyond_noise() {
+            format!("**{:+.1}%**", c.delta_pct)
+        } else {
+            format!("{:+.1}%", c.delta_pct)
+        }
+    };
+    let table = |out: &mut String, rows: &[&compare::Comparison]| {
+        let _ = writeln!(out, "| benchmark | baseline | candidate | delta |");
+        let _ = writeln!(out, "|---|---:|---:|---:|");
+        for c in rows {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                escape_cell(&c.name),
+                format_ns(c.baseline_ns),
+                format_ns(c.candidate_ns),
+                fmt_delta(c)
+            );
+        }
+    };
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "### Benchmarks: {} → {}\n",
+        escape_cell(&baseline.engine),
+        escape_cell(&candidate.engine)
+    );
+    match compare::geomean_ratio(&comparisons) {
+        Some(ratio) => {
+            let beyond_noise = |faster: bool| {
+                comparisons.iter().filter(|c| c.beyond_noise() && (c.delta_pct < 0.0) == faster).count()
+            };
+            let _ = writeln!(
+                out,
+                "**Geomean: {:.3}x ({:+.1}%)** over {} benchmarks; {} slower and {} faster beyond noise\n",
+                ratio,
+                (ratio - 1.0) * 100.0,
+                comparisons.len(),
+                beyond_noise(false),
+                beyond_noise(true)
+            );
+        }
+        None => {
+            let _ = writeln!(out, "No benchmarks in common.\n");
+            return out;
+        }
+    }
+    let (regressions, improvements) = compare::top_changes(&comparisons, PR_COMMENT_CHANGES);
+    for (kind, rows) in [("regressions", &regressions), ("improvements", &improvements)] {
+        if !rows.is_empty() {
+            let _ = writeln!(out, "#### Top {} {}\n", PR_COMMENT_CHANGES, kind);
+            table(&mut out, rows);
+            let _ = writeln!(out);
+        }
+    }
+    let _ = writeln!(out, "<details>\n<summary>All {} benchmarks</summary>\n", comparisons.len());
+    table(&mut out, &comparisons.iter().collect::<Vec<_>>());
+    let _ = writeln!(out, "\n</details>");
+    out
+}
+
+/// Regressions listed in the brief
+const BRIEF_REGRESSIONS: usize = 3;
+
+/// Load average per core above which the brief warns the machine was busy
+const BUSY_LOAD_PER_CORE: f64 = 0.5;
+
+/// Plain-text summary of a run for a mailbox or chat: what ran, the geomean
+/// against `baseline` and its top regressions, broken criteria, and anything
+/// about the machine or build that makes the numbers suspect
+pub fn brief(results: &BenchmarkResults, baseline: Option<&BenchmarkResults>) -> String {
+    let flat = results.flat();
+    let measured = flat.values().filter(|result| result.is_measured()).count();
+    let skipped = flat.values().filter(|result| result.skipped).count();
+    let timed_out = flat.values().filter(|result| result.timed_out).count();
+
+    let mut out = String::new();
+    let _ = write!(out, "{} benchmarks, {}: {} measured", results.engine, results.timestamp, measured);
+    if skipped > 0 {
+        let _ = write!(out, ", {} skipped", skipped);
+    }
+    if timed_out > 0 {
+        let _ = write!(out, ", {} timed out", timed_out);
+    }
+    let _ = writeln!(out);
+
+    if let Some(baseline) = baseline {
+        let comparisons = compare::compare(baseline, results, DEFAULT_ALPHA);
+        match compare::geomean_ratio(&comparisons) {
+            Some(ratio) => {
+                let _ = writeln!(
+                    out,
+                    "Geomean vs baseline ({}): {:.3}x ({:+.1}%) over {} benchmarks",
+                    baseline.timestamp,
+                    ratio,
+                    (ratio - 1.0) * 100.0,
+                    comparisons.len()
+                );
+            }
+            None => {
+                let _ = writeln!(out, "No benchmarks in common with the baseline ({})", baseline.timestamp);
+            }
+        }
+        let (regressions, _) = compare::top_changes(&comparisons, BRIEF_REGRESSIONS);
+        if !regressions.is_empty() {
+            let _ = writeln!(out, "Top {} regressions:", BRIEF_REGRESSIONS);
+        }
+        for c in &regressions {
+            let _ = writeln!(
+                out,
+                "  {:+.1}% {} ({:.1} -> {:.1} ns){}",
+                c.delta_pct,
+                c.name,
+                c.baseline_ns,
+                c.candidate_ns,
+                if c.significant { ", significant" } else { "" }
+            );
+        }
+    }
+
+    let violations = criteria::evaluate(results);
+    if !violations.is_empty() {
+        let _ = writeln!(out, "Criteria violations: {}", violations.len());
+        for violation in &violations {
+            let _ = writeln!(out, "  {}", violation);
+        }
+    }
+
+    let notes = environment_notes(results, baseline);
+    if !notes.is_empty() {
+        let _ = writeln!(out, "Environment notes:");
+        for note in notes {
+            let _ = writeln!(out, "  {}", note);
+        }
+    }
+    out
+}
+
+/// Reasons the run's numbers may not be comparable
+fn environment_notes(results: &BenchmarkResults, baseline: Option<&BenchmarkResults>) -> Vec<String> {
+    let mut notes = Vec::new();
+    if let Some(stability) = &results.stability {
+        if stability.unstable() {
+            notes.push(format!(
+                "unstable: the sentinel drifted {:.1}% (max {:.0}%)",
+                stability.drift_pct, stability.max_drift_pct
+            ));
+        }
+    }
+    if let Some(environment) = &results.environment {
+        let cores = environment.logical_cores.max(1) as f64;
+        let busiest = [environment.load_average_before, environment.load_average_after]
+            .into_iter()
+            .flatten()
+            .map(|load| load[0])
+            .fold(0.0, f64::max);
+        if busiest / cores > BUSY_LOAD_PER_CORE {
+            notes.push(format!("busy machine: load average reached {:.2} on {} cores", busiest, cores));
+        }
+        if let Some(governor) = environment.cpu_governor.as_deref().filter(|g| *g != "performance") {
+            notes.push(format!("CPU governor is {:?}, not \"performance\"", governor));
+        }
+    }
+    if let (Some(build), Some(base)) = (
+        &results.build_info,
+        baseline.and_then(|baseline| baseline.build_info.as_ref()),
+    ) {
+        if build.rustc != base.rustc {
+            notes.push(format!("compiler changed: {} -> {}", base.rustc, build.rustc));
+        }
+        if build.regex_version != base.regex_version {
+            notes.push(format!("regex changed: {} -> {}", base.regex_version, build.regex_version));
+        }
+    }
+    if let Some(build) = &results.build_info {
+        if build.git_dirty {
+            notes.push("built from a checkout with uncommitted changes".to_string());
+        }
+    }
+    notes
+}
+
+/// Google Benchmark's `--benchmark_format=json` output: one entry per
+/// benchmark named `category/benchmark`, with unmeasured ones reported as
+/// errors. `cpu_time` is the `cpu` metric when `--metrics` recorded it, and
+/// otherwise repeats `real_time`.
+pub fn google_benchmark(results: &BenchmarkResults) -> String {
+    let environment = results.environment.as_ref();
+    let load_avg = environment.and_then(|env| env.load_average_before).unwrap_or_default();
+    let context = json!({
+        "date": results.timestamp,
+        "executable": std::env::current_exe().map(|path| path.display().to_string()).unwrap_or_default(),
+        "num_cpus": environment.map_or(0, |env| env.logical_cores),
+        "mhz_per_cpu": environment.and_then(|env| env.cpu_freq_mhz_before).unwrap_or_default().round(),
+        "cpu_scaling_enabled": environment
+            .and_then(|env| env.cpu_governor.as_deref())
+            .is_some_and(|governor| governor != "performance"),
+        "caches": [],
+        "load_avg": load_avg,
+        "library_build_type": results.build_info.as_ref().map_or("release", |build| build.profile.as_str()),
+    });
+
+    let mut benchmarks = Vec::new();
+    for (family_index, (category, benches)) in ordered_categories(results).into_iter().enumerate() {
+        for (instance, (name, result)) in benches.into_iter().enumerate() {
+            let full_name = format!("{}/{}", category, name);
+            let mut entry = json!({
+                "name": full_name,
+                "family_index": family_index,
+                "per_family_instance_index": instance,
+                "run_name": full_name,
+                "run_type": "iteration",
+                "repetitions": 1,
+                "repetition_index": 0,
+                "threads": 1,
+                "iterations": result.iterations,
+                "real_time": result.time_ns,
+                "cpu_time": result
+                    .metrics
+                    .as_ref()
+                    .and_then(|metrics| metrics.get("cpu"))
+                    .map_or(result.time_ns, |cpu| cpu.value),
+                "time_unit": "ns",
+            });
+            if let Some(mb_s) = result.throughput_mb_s {
+                entry["bytes_per_second"] = json!(mb_s * 1e6);
+            }
+            if !result.is_measured() {
+                entry["error_occurred"] = json!(true);
+                entry["error_message"] = json!(fmt_time(result));
+            }
+            benchmarks.push(entry);
+        }
+    }
+    serde_json::to_string_pretty(&json!({ "context": context, "benchmarks": benchmarks })).unwrap_or_default()
+}
+
+/// JUnit XML for CI test dashboards (Jenkins, GitLab): a `<testsuite>` per
+/// category and a `<testcase>` per benchmark, timed by its median per call.
+/// Broken criteria, wrong match counts and `regressions` (see
+/// [`compare::regressions`]) are failures; timeouts are errors.
+pub fn junit(results: &BenchmarkResults, regressions: &[compare::Regression]) -> String {
+    let regressions: HashMap<&str, &compare::Regression> =
+        regressions.iter().map(|regression| (regression.name.as_str(), regression)).collect();
+
+    let mut suites = String::new();
+    let (mut total_tests, mut total_failures, mut total_errors, mut total_skipped) = (0, 0, 0, 0);
+    let mut total_time = 0.0;
+    for (category, benches) in ordered_categories(results) {
+        let (mut failures, mut errors, mut skipped) = (0, 0, 0);
+        let mut time = 0.0;
+        let mut cases = String::new();
+        for (name, result) in &benches {
+            let seconds = if result.is_measured() { result.time_ns / 1e9 } else { 0.0 };
+            time += seconds;
+            let _ = write!(
+                cases,
+                "    <testcase classname=\"{}.{}\" name=\"{}\" time=\"{:.9}\"",
+                escape_html(&results.engine),
+                escape_html(category),
+                escape_html(name),
+                seconds
+            );
+            let mut body = String::new();
+            if result.skipped {
+                skipped += 1;
+                let _ = writeln!(body, "      <skipped message=\"time budget exhausted\"/>");
+            } else if result.timed_out {
+                errors += 1;
+                let _ = writeln!(body, "      <error message=\"timed out\" type=\"timeout\"/>");
+            } else {
+                let mut messages: Vec<String> =
+                    criteria::check(name, result).iter().map(ToString::to_string).collect();
+                if let Some(regression) = regressions.get(name.as_str()) {
+                    messages.push(regression.to_string());
+                }
+                if let Some(first) = messages.first() {
+                    failures += 1;
+                    let _ = writeln!(
+                        body,
+                        "      <failure message=\"{}\" type=\"{}\">{}</failure>",
+                        escape_html(first),
+                        if regressions.contains_key(name.as_str()) { "regression" } else { "criteria" },
+                        escape_html(&messages.join("\n"))
+                    );
+                }
+            }
+            if body.is_empty() {
+                let _ = writeln!(cases, "/>");
+            } else {
+                let _ = writeln!(cases, ">\n{}    </testcase>", body);
+            }
+        }
+        let _ = writeln!(
+            suites,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.9}\" timestamp=\"{}\">",
+            escape_html(category),
+            benches.len(),
+            failures,
+            errors,
+            skipped,
+            time,
+            escape_html(&results.timestamp)
+        );
+        suites.push_str(&cases);
+        let _ = writeln!(suites, "  </testsuite>");
+        total_tests += benches.len();
+        total_failures += failures;
+        total_errors += errors;
+        total_skipped += skipped;
+        total_time += time;
+    }
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\" time=\"{:.9}\">",
+        escape_html(&results.engine),
+        total_tests,
+        total_failures,
+        total_errors,
+        total_skipped,
+        total_time
+    );
+    out.push_str(&suites);
+    let _ = writeln!(out, "</testsuites>");
+    out
+}
@@ -0,0 +1,89 @@
+//! Fixed literal search re-timed at the start, middle and end of the suite
+//!
+//! On laptops and passively cooled machines the CPU can throttle partway
+//! through a long run, silently skewing the tail benchmarks. If the sentinel
+//! drifts by more than the threshold, the run is flagged as unstable.
+
+use crate::{make_test_string, BenchmarkTimer};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+
+/// Drift from the first reading, in percent, above which a run is unstable
+pub const DEFAULT_MAX_DRIFT_PCT: f64 = 10.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SentinelReading {
+    /// Point in the suite the reading was taken (`start`, `middle`, `end`)
+    pub stage: String,
+    pub time_ns: f64,
+}
+
+/// Sentinel readings for a run and the largest drift between them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StabilityReport {
+    pub readings: Vec<SentinelReading>,
+    /// Largest deviation from the first reading, in percent
+    pub drift_pct: f64,
+    pub max_drift_pct: f64,
+}
+
+impl StabilityReport {
+    pub fn unstable(&self) -> bool {
+        self.drift_pct > self.max_drift_pct
+    }
+}
+
+#[derive(Debug)]
+pub struct Sentinel {
+    pattern: Regex,
+    haystack: String,
+    timer: BenchmarkTimer,
+    max_drift_pct: f64,
+    readings: Vec<SentinelReading>,
+}
+
+impl Sentinel {
+    pub fn new(max_drift_pct: f64) -> Self {
+        Sentinel {
+            // Never matches, so every search scans the whole haystack
+            pattern: Regex::new("sentinel").unwrap(),
+            haystack: make_test_string(100_000, "the quick brown fox jumps over the lazy dog "),
+            timer: BenchmarkTimer {
+                target_runtime_ns: 100_000_000, // 100ms per reading
+                max_reruns: 0,
+                ..BenchmarkTimer::new()
+            },
+            max_drift_pct,
+            readings: Vec::new(),
+        }
+    }
+
+    /// Time the sentinel and record it under `stage`
+    pub fn measure(&mut self, stage: &str) {
+        let result = self.timer.bench_function(|| {
+            black_box(self.pattern.find(black_box(&self.haystack)));
+        });
+        self.readings.push(SentinelReading {
+            stage: stage.to_string(),
+            time_ns: result.time_ns,
+        });
+    }
+
+    /// Drift across the readings so far; `None` until there are two
+    pub fn report(&self) -> Option<StabilityReport> {
+        let (first, rest) = self.readings.split_first()?;
+        if rest.is_empty() || first.time_ns <= 0.0 {
+            return None;
+        }
+        let drift_pct = rest
+            .iter()
+            .map(|r| (r.time_ns - first.time_ns).abs() / first.time_ns * 100.0)
+            .fold(0.0, f64::max);
+        Some(StabilityReport {
+            readings: self.readings.clone(),
+            drift_pct,
+            max_drift_pct: self.max_drift_pct,
+        })
+    }
+}
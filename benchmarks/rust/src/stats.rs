@@ -0,0 +1,299 @@
+//! Statistical tests over per-sample benchmark timings
+
+use crate::percentile;
+use serde::{Deserialize, Serialize};
+
+/// Welford's online mean and variance, plus min and max, in O(1) memory
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OnlineStats {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl OnlineStats {
+    pub fn push(&mut self, x: f64) {
+        if self.count == 0 {
+            self.min = x;
+            self.max = x;
+        } else {
+            self.min = self.min.min(x);
+            self.max = self.max.max(x);
+        }
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Sample variance, or `None` with fewer than two samples
+    pub fn variance(&self) -> Option<f64> {
+        (self.count >= 2).then(|| self.m2 / (self.count - 1) as f64)
+    }
+
+    /// Same as [`coefficient_of_variation`] over the pushed samples
+    pub fn cv(&self) -> Option<f64> {
+        let variance = self.variance()?;
+        (self.mean > 0.0).then(|| variance.sqrt() / self.mean)
+    }
+
+    pub fn summary(&self) -> Option<SampleSummary> {
+        (self.count > 0).then(|| SampleSummary {
+            count: self.count,
+            mean_ns: self.mean,
+            std_dev_ns: self.variance().map_or(0.0, f64::sqrt),
+            min_ns: self.min,
+            max_ns: self.max,
+        })
+    }
+}
+
+/// Moments of a benchmark's per-sample times, accumulated online
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SampleSummary {
+    pub count: u64,
+    pub mean_ns: f64,
+    pub std_dev_ns: f64,
+    pub min_ns: f64,
+    pub max_ns: f64,
+}
+
+impl SampleSummary {
+    /// Summary of every sample divided by `divisor`
+    pub fn scaled(&self, divisor: f64) -> Self {
+        SampleSummary {
+            count: self.count,
+            mean_ns: self.mean_ns / divisor,
+            std_dev_ns: self.std_dev_ns / divisor,
+            min_ns: self.min_ns / divisor,
+            max_ns: self.max_ns / divisor,
+        }
+    }
+}
+
+/// Uniform random subset of at most `capacity` samples (Algorithm R)
+///
+/// Keeps median and shape estimates available for any number of samples
+/// without the memory growing with them. With a capacity of `usize::MAX`
+/// every sample is kept.
+#[derive(Debug, Clone)]
+pub struct Reservoir {
+    capacity: usize,
+    seen: u64,
+    samples: Vec<f64>,
+    rng: u64,
+}
+
+impl Reservoir {
+    pub fn new(capacity: usize) -> Self {
+        Reservoir {
+            capacity,
+            seen: 0,
+            samples: Vec::new(),
+            // Fixed seed: the kept subset is reproducible run to run
+            rng: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    pub fn push(&mut self, x: f64) {
+        self.seen += 1;
+        if self.samples.len() < self.capacity {
+            self.samples.push(x);
+            return;
+        }
+        // xorshift64
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 7;
+        self.rng ^= self.rng << 17;
+        let slot = self.rng % self.seen;
+        if slot < self.capacity as u64 {
+            self.samples[slot as usize] = x;
+        }
+    }
+
+    pub fn samples(&self) -> &[f64] {
+        &self.samples
+    }
+
+    pub fn into_samples(self) -> Vec<f64> {
+        self.samples
+    }
+}
+
+/// Coefficient of variation (sample standard deviation / mean), or `None`
+/// with fewer than two samples
+pub fn coefficient_of_variation(samples: &[f64]) -> Option<f64> {
+    if samples.len() < 2 {
+        return None;
+    }
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    if mean <= 0.0 {
+        return None;
+    }
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Some(variance.sqrt() / mean)
+}
+
+/// Stability score above which a benchmark is reported as noisy: its round
+/// medians disagree by more than 10%
+pub const NOISY_STABILITY_SCORE: f64 = 1.10;
+
+/// Spread of per-round medians as max / min (1.0 is perfectly stable), or
+/// `None` with fewer than two rounds
+pub fn stability_score(round_medians: &[f64]) -> Option<f64> {
+    if round_medians.len() < 2 {
+        return None;
+    }
+    let min = round_medians.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = round_medians.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (min > 0.0).then(|| max / min)
+}
+
+/// Geometric mean of the positive `values`, or `None` if there are none
+pub fn geomean(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+    let (log_sum, count) = values
+        .into_iter()
+        .filter(|&v| v > 0.0)
+        .fold((0.0, 0usize), |(log_sum, count), v| (log_sum + v.ln(), count + 1));
+    (count > 0).then(|| (log_sum / count as f64).exp())
+}
+
+/// Normal quantile of a two-sided 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// 95% confidence interval of the mean `summary` describes, or `None` with
+/// fewer than two samples
+pub fn confidence_interval(summary: &SampleSummary) -> Option<(f64, f64)> {
+    if summary.count < 2 {
+        return None;
+    }
+    let half_width = Z_95 * summary.std_dev_ns / (summary.count as f64).sqrt();
+    Some((summary.mean_ns - half_width, summary.mean_ns + half_width))
+}
+
+/// Two-sided Mann-Whitney U test p-value for samples `a` and `b`
+///
+/// Uses the normal approximation with tie and continuity corrections, which
+/// is accurate for the sample counts the timer produces (tens to thousands).
+/// Returns `None` when either side has fewer than two samples.
+pub fn mann_whitney_u(a: &[f64], b: &[f64]) -> Option<f64> {
+    let (n1, n2) = (a.len(), b.len());
+    if n1 < 2 || n2 < 2 {
+        return None;
+    }
+
+    let mut pooled: Vec<(f64, bool)> = a
+        .iter()
+        .map(|&x| (x, true))
+        .chain(b.iter().map(|&x| (x, false)))
+        .collect();
+    pooled.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+
+    // Average ranks over ties, accumulating the tie correction term
+    let n = pooled.len();
+    let mut rank_sum_a = 0.0;
+    let mut tie_term = 0.0;
+    let mut i = 0;
+    while i < n {
+        let mut j = i + 1;
+        while j < n && pooled[j].0 == pooled[i].0 {
+            j += 1;
+        }
+        let avg_rank = (i + j + 1) as f64 / 2.0;
+        rank_sum_a += avg_rank * pooled[i..j].iter().filter(|(_, in_a)| *in_a).count() as f64;
+        let t = (j - i) as f64;
+        tie_term += t * t * t - t;
+        i = j;
+    }
+
+    let (n1, n2, n) = (n1 as f64, n2 as f64, n as f64);
+    let u1 = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let mean = n1 * n2 / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+    if variance <= 0.0 {
+        // Every sample identical: no evidence of a difference
+        return Some(1.0);
+    }
+
+    let z = ((u1 - mean).abs() - 0.5).max(0.0) / variance.sqrt();
+    Some(erfc(z / std::f64::consts::SQRT_2).min(1.0))
+}
+
+/// Complementary error function (Numerical Recipes `erfcc`, |error| < 1.2e-7)
+pub fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let r = t * poly.exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+/// Heuristic bimodality check over a sample set
+///
+/// Builds a smoothed histogram between the 1st and 99th percentiles (so a few
+/// outliers can't create a second mode) and reports bimodal when a second
+/// local peak holding a meaningful share of the samples is separated from the
+/// main peak by a valley at most half its height. Frequency scaling and
+/// lazy-initialization effects typically show up this way.
+pub fn is_bimodal(samples: &[f64]) -> bool {
+    const BINS: usize = 20;
+    const MIN_SAMPLES: usize = 40;
+    // A secondary peak must hold at least this share of samples (smoothed)
+    const MIN_PEAK_SHARE: f64 = 0.05;
+    const MAX_VALLEY_RATIO: f64 = 0.5;
+
+    if samples.len() < MIN_SAMPLES {
+        return false;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let (lo, hi) = (percentile(&sorted, 1.0), percentile(&sorted, 99.0));
+    if hi <= lo {
+        return false;
+    }
+
+    let width = (hi - lo) / BINS as f64;
+    let mut counts = [0.0f64; BINS];
+    for &x in sorted.iter().filter(|&&x| x >= lo && x <= hi) {
+        counts[(((x - lo) / width) as usize).min(BINS - 1)] += 1.0;
+    }
+    let smoothed: Vec<f64> = (0..BINS)
+        .map(|i| {
+            let window = &counts[i.saturating_sub(1)..(i + 2).min(BINS)];
+            window.iter().sum::<f64>() / window.len() as f64
+        })
+        .collect();
+
+    let main_peak = (0..BINS)
+        .max_by(|&a, &b| smoothed[a].partial_cmp(&smoothed[b]).unwrap())
+        .unwrap();
+    let min_peak = MIN_PEAK_SHARE * sorted.len() as f64;
+    (0..BINS).any(|j| {
+        let is_local_max = (j == 0 || smoothed[j] >= smoothed[j - 1])
+            && (j == BINS - 1 || smoothed[j] >= smoothed[j + 1]);
+        if j == main_peak || !is_local_max || smoothed[j] < min_peak {
+            return false;
+        }
+        let (a, b) = (j.min(main_peak), j.max(main_peak));
+        let valley = smoothed[a..=b].iter().cloned().fold(f64::INFINITY, f64::min);
+        valley <= MAX_VALLEY_RATIO * smoothed[j]
+    })
+}
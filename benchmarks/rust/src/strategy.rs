@@ -0,0 +1,59 @@
+//! Introspection of the execution strategy the `regex` crate picks
+//!
+//! `regex::Regex` is a thin wrapper over regex-automata's meta engine, which
+//! chooses a strategy at build time (prefilter-only, core engines, or one of
+//! the reverse-search optimizations) and, within the core, which forward
+//! engines are available. None of this is public API, so it is recovered from
+//! the meta regex's `Debug` output; if that format changes, `describe`
+//! degrades to reporting the top-level strategy name only.
+
+use regex_automata::meta;
+use regex_automata::util::syntax;
+
+/// Forward engines in the order the meta core tries them for a search
+const CORE_ENGINES: [(&str, &str); 4] = [
+    ("dfa", "dfa: DFA(Some"),
+    ("hybrid", "hybrid: Hybrid(Some"),
+    ("onepass", "onepass: OnePass(Some"),
+    ("backtrack", "backtrack: BoundedBacktracker(Some"),
+];
+
+/// Describe the strategy used for `pattern`, e.g. `Pre/Memmem`, `Core/dfa`
+/// or `ReverseInner/hybrid`
+///
+/// For core-based strategies the suffix names the fastest forward engine that
+/// was built; `pikevm` means every faster engine was unavailable and searches
+/// fall back to the PikeVM. Returns `None` if the pattern doesn't compile.
+pub fn describe(pattern: &str) -> Option<String> {
+    describe_with(pattern, true)
+}
+
+/// [`describe`] for a pattern built with `RegexBuilder::unicode(unicode)`
+pub fn describe_with(pattern: &str, unicode: bool) -> Option<String> {
+    // Same defaults regex::Regex::new uses for the meta builder
+    let regex = meta::Builder::new()
+        .syntax(syntax::Config::new().unicode(unicode))
+        .build(pattern)
+        .ok()?;
+    let debug = format!("{:?}", regex);
+
+    let strategy = debug
+        .split("strat: ")
+        .nth(1)?
+        .split(|c: char| !c.is_alphanumeric())
+        .next()?;
+
+    let detail = if strategy == "Pre" {
+        debug
+            .split("pre: ")
+            .nth(1)
+            .and_then(|rest| rest.split(|c: char| !c.is_alphanumeric()).next())
+            .unwrap_or("?")
+    } else {
+        CORE_ENGINES
+            .iter()
+            .find(|(_, marker)| debug.contains(marker))
+            .map_or("pikevm", |(name, _)| name)
+    };
+    Some(format!("{}/{}", strategy, detail))
+}
@@ -0,0 +1,2155 @@
+//! The canonical benchmark suite
+//!
+//! [`register_benchmarks`] both runs the suite and, given a dry-run
+//! collector, only records its definitions; [`all_benchmarks`] and
+//! [`export_definitions`] expose those definitions to external tools (the
+//! Mojo test generator, documentation tooling, the fuzzer) without timing
+//! anything.
+
+use crate::clock::{self, Clock};
+use crate::criteria::Criteria;
+use crate::engine_path::EnginePath;
+use crate::corpus::{self, LANGUAGES};
+use crate::{
+    encoding, get_email_long, get_long_text, get_medium_text, make_log_text, make_mixed_content_text,
+    make_support_tickets, make_test_string, make_vocabulary, memory, strategy, with_deadline, BenchDef, BenchmarkResult,
+    BenchmarkTimer, Phases, ResultCollector, Variant, EMAIL_TEXT, SHORT_TEXT,
+};
+use regex::{Captures, NoExpand, Regex, RegexBuilder};
+use regex_automata::{meta, Anchored, Input, MatchKind};
+#[cfg(feature = "overlapping")]
+use regex_automata::hybrid::dfa::OverlappingState;
+#[cfg(any(feature = "overlapping", feature = "streaming", feature = "engines"))]
+use regex_automata::hybrid;
+#[cfg(feature = "engines")]
+use regex_automata::{
+    dfa,
+    nfa::thompson::{backtrack::BoundedBacktracker, pikevm::PikeVM},
+};
+#[cfg(any(feature = "overlapping", feature = "streaming", feature = "engines"))]
+use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::sync::{Arc, OnceLock};
+
+/// Timer with a runtime-selected clock; shared with the watchdog threads
+/// benchmarks run on
+pub type Timer = BenchmarkTimer<Box<dyn Clock + Send + Sync>>;
+
+/// A category of the suite, as documentation lists it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryDef {
+    pub name: String,
+    pub title: String,
+}
+
+/// The whole suite's definitions, for generating reference tables
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuiteDefinitions {
+    /// Categories in the order they run
+    pub categories: Vec<CategoryDef>,
+    /// Benchmarks in registration order
+    pub benchmarks: Vec<BenchDef>,
+}
+
+/// Feature-gated categories and how many benchmarks each registers. A build
+/// without the feature skips that many indices, so every other benchmark
+/// keeps the index it has in a full build
+pub const GATED_CATEGORIES: [(&str, usize); 5] = [
+    ("backreference", 3),
+    ("lookaround", 4),
+    ("overlapping", 6),
+    ("streaming", 6),
+    ("engine_breakdown", 15),
+];
+
+/// Benchmarks the feature-gated `category` registers, 0 for ungated ones
+pub fn gated_len(category: &str) -> usize {
+    GATED_CATEGORIES
+        .iter()
+        .find(|(name, _)| *name == category)
+        .map_or(0, |(_, len)| *len)
+}
+
+/// [`ResultCollector::built_with`] options of the ASCII-only class variants
+pub const ASCII_ONLY: &str = "unicode(false)";
+
+fn is_unicode(builder: Option<&str>) -> bool {
+    builder != Some(ASCII_ONLY)
+}
+
+/// Compile `pattern` with the builder options a benchmark recorded, so cold
+/// phases and strategy descriptions see the same regex the benchmark timed
+pub fn build_regex(pattern: &str, builder: Option<&str>) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern).unicode(is_unicode(builder)).build()
+}
+
+/// Register the suite into a dry-run collector
+fn dry_run() -> ResultCollector {
+    // A dry run never reads the timer, but the runners still take one
+    static TIMER: OnceLock<Timer> = OnceLock::new();
+    let timer = TIMER.get_or_init(|| {
+        BenchmarkTimer::with_clock(clock::select("wall").expect("wall clock is always available"))
+    });
+    let mut collector = ResultCollector::dry_run();
+    register_benchmarks(timer, &mut collector).expect("suite patterns compile");
+    collector
+}
+
+/// Definitions of every benchmark in the suite, in registration order
+pub fn all_benchmarks() -> Vec<BenchDef> {
+    dry_run().definitions().to_vec()
+}
+
+/// Every category and benchmark definition, with patterns, haystack
+/// descriptions and expected match counts
+pub fn export_definitions() -> SuiteDefinitions {
+    let collector = dry_run();
+    SuiteDefinitions {
+        categories: collector
+            .categories()
+            .iter()
+            .map(|name| CategoryDef {
+                name: name.clone(),
+                title: collector.category_title(name).unwrap_or(name).to_string(),
+            })
+            .collect(),
+        benchmarks: collector.definitions().to_vec(),
+    }
+}
+
+/// Run (or, with a dry-run collector, just register) every benchmark
+pub fn register_benchmarks(
+    timer: &'static Timer,
+    all_results: &mut ResultCollector,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Pre-create test strings to avoid measurement overhead - scaled up to match Mojo benchmarks
+    let text_10000 = make_test_string(10000, "abcdefghijklmnopqrstuvwxyz");     // Increased from 1000 to 10000
+    let text_100000 = make_test_string(100000, "abcdefghijklmnopqrstuvwxyz");   // Increased from 10000 to 100000
+    let text_range_10000 = make_test_string(10000, "abc123XYZ");
+    let text_alternation_10000 = make_test_string(10000, "abcdefghijklmnopqrstuvwxyz");
+    let text_group_10000 = make_test_string(10000, "abcabcabc");
+
+    // Create complex text patterns - scaled up to match Mojo benchmarks
+    let base_text = make_test_string(2000, "abcdefghijklmnopqrstuvwxyz");  // Increased from 100 to 2000
+    let emails = " user@example.com more text john@test.org ";
+    let email_text = format!("{} {} {} {} {}", base_text, emails, base_text, emails, base_text);
+
+    let base_number_text = make_test_string(20000, "abc def ghi ");  // Increased from 500 to 20000
+    let number_text = format!("{} 123 price $456.78 quantity 789 {}", base_number_text, base_number_text);
+
+    // Pre-compile all regex patterns to avoid compilation overhead in benchmarks
+    let patterns = create_all_patterns()?;
+
+    // ===-----------------------------------------------------------------------===
+    // Basic Literal Matching Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("literal", "Literal Matching Benchmarks");
+    all_results.exercises(EnginePath::LiteralOptimizer);
+
+    run_benchmark(
+        timer,
+        all_results,
+        "literal_match_short",
+        &patterns.hello,
+        &format!("{} hello world {}", text_10000, text_10000),  // Updated variable names
+        2000,  // Increased from 100 to 2000
+        BenchType::Search
+    );
+
+    run_benchmark(
+        timer,
+        all_results,
+        "literal_match_long",
+        &patterns.hello,
+        &format!("{} hello world {}", text_100000, text_10000),  // Updated variable names
+        2000,  // Increased from 100 to 2000
+        BenchType::Search
+    );
+    // A memchr-backed literal scan should neither allocate nor fall below 1 GB/s
+    all_results.require("literal_match_long", Criteria::new().max_allocations(0.0).min_throughput_mb_s(1000.0));
+
+    // ===-----------------------------------------------------------------------===
+    // Wildcard and Quantifier Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("quantifier", "Wildcard and Quantifier Benchmarks");
+    all_results.exercises(EnginePath::PureDfa);
+
+    run_benchmark(timer, all_results, "wildcard_match_any", &patterns.dot_star, &text_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
+    run_benchmark(timer, all_results, "quantifier_zero_or_more", &patterns.a_star, &text_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
+    run_benchmark(timer, all_results, "quantifier_one_or_more", &patterns.a_plus, &text_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
+    run_benchmark(timer, all_results, "quantifier_zero_or_one", &patterns.a_question, &text_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
+
+    // ===-----------------------------------------------------------------------===
+    // Character Range Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("range", "Character Range Benchmarks");
+    all_results.exercises(EnginePath::SimdCharClass);
+
+    run_benchmark(timer, all_results, "range_lowercase", &patterns.range_a_z, &text_range_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
+    run_benchmark(timer, all_results, "range_digits", &patterns.range_0_9, &text_range_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
+    run_benchmark(timer, all_results, "range_alphanumeric", &patterns.range_alnum, &text_range_10000, 1000, BenchType::IsMatch);  // Updated text size and iterations (50->1000)
+
+    // ===-----------------------------------------------------------------------===
+    // Predefined Character Class Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("predefined_class", "Predefined Character Class Benchmarks");
+    all_results.exercises(EnginePath::SimdCharClass);
+
+    run_benchmark(timer, all_results, "predefined_digits", &patterns.predefined_digits, &text_range_10000, 1000, BenchType::Search);
+    run_benchmark(timer, all_results, "predefined_word", &patterns.predefined_word, &text_range_10000, 1000, BenchType::IsMatch);
+
+    // ===-----------------------------------------------------------------------===
+    // Anchor Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("anchor", "Anchor Benchmarks");
+    all_results.exercises(EnginePath::PureDfa);
+
+    run_benchmark(timer, all_results, "anchor_start", &patterns.anchor_start, &text_10000, 2000, BenchType::IsMatch);  // Updated text size and iterations (100->2000)
+    // The alphabet text doesn't end in "xyz": a failed end-anchored scan
+    all_results.expects_no_match();
+    run_benchmark(timer, all_results, "anchor_end", &patterns.anchor_end, &text_10000, 2000, BenchType::IsMatch);  // Updated text size and iterations (100->2000)
+    all_results.expect_matches("anchor_end", 0);
+
+    // ===-----------------------------------------------------------------------===
+    // Multiline Anchor Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("multiline_anchor", "Multiline Anchor Benchmarks");
+
+    // Line anchors over a multi-line log, next to the text anchors, which
+    // keep matching only at the very start and end whatever the newlines
+    let log_text = make_log_text(2000);
+    let error_lines = Regex::new("(?m)^ERROR")?;
+    let backtrace_frames = Regex::new(r"(?m)^\d+:")?;
+    let durations = Regex::new(r"(?m)\d+ms$")?;
+    let text_start = Regex::new(r"\AINFO")?;
+    let text_end = Regex::new(r"complete\z")?;
+    run_benchmark(timer, all_results, "multiline_start_error", &error_lines, &log_text, 20, BenchType::FindAll);
+    run_benchmark(timer, all_results, "multiline_start_digits", &backtrace_frames, &log_text, 20, BenchType::FindAll);
+    run_benchmark(timer, all_results, "multiline_end_duration", &durations, &log_text, 20, BenchType::FindAll);
+    run_benchmark(timer, all_results, "text_start_anchor", &text_start, &log_text, 1000, BenchType::FindAll);
+    run_benchmark(timer, all_results, "text_end_anchor", &text_end, &log_text, 1000, BenchType::FindAll);
+    for (name, expected) in [
+        ("multiline_start_error", 200),
+        ("multiline_start_digits", 400),
+        ("multiline_end_duration", 1400),
+        ("text_start_anchor", 1),
+        ("text_end_anchor", 1),
+    ] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Per-Line Matching Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("per_line", "Per-Line (grep-style) Matching Benchmarks");
+
+    // grep's hot loop: one is_match per line, so per-call overhead on short
+    // haystacks weighs as much as the scan itself; counts the matching lines
+    for (name, pattern) in [
+        ("grep_literal", "upstream timeout"),
+        ("grep_char_class", r"[0-9]{3}ms"),
+        ("grep_alternation", r"WARN|ERROR|panic"),
+    ] {
+        let line_pat = Regex::new(pattern)?;
+        run_scan_benchmark(timer, all_results, name, "IsMatchPerLine", pattern, &log_text, 20, move |text| {
+            text.lines().filter(|line| line_pat.is_match(line)).count()
+        });
+    }
+    for (name, expected) in [("grep_literal", 200), ("grep_char_class", 200), ("grep_alternation", 400)] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Word Boundary Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("word_boundary", "Word Boundary Benchmarks");
+
+    // Whole words among longer words containing them, as `grep -w` finds them
+    let prose_text = "Hello hello, said the cat to the dog. The fox said: hello! Othello and helloworld don't count, \
+                      nor do catalog, hotdog or foxglove; a bobcat and a wildcat concatenate their meows. "
+        .repeat(500);
+    let whole_hello = Regex::new(r"\bhello\b")?;
+    let whole_animals = Regex::new(r"\bcat\b|\bdog\b|\bfox\b")?;
+    // Inside a word only: "cat" in bobcat, wildcat and concatenate
+    let inner_cat = Regex::new(r"\Bcat")?;
+    // An ASCII-only boundary needs no Unicode word tables
+    let ascii_hello = Regex::new(r"(?-u:\b)hello(?-u:\b)")?;
+    run_benchmark(timer, all_results, "word_boundary_literal", &whole_hello, &prose_text, 20, BenchType::FindAll);
+    run_benchmark(timer, all_results, "word_boundary_alternation", &whole_animals, &prose_text, 20, BenchType::FindAll);
+    run_benchmark(timer, all_results, "non_word_boundary", &inner_cat, &prose_text, 20, BenchType::FindAll);
+    run_benchmark(timer, all_results, "word_boundary_ascii", &ascii_hello, &prose_text, 20, BenchType::FindAll);
+    for (name, expected) in [
+        ("word_boundary_literal", 1000),
+        ("word_boundary_alternation", 1500),
+        ("non_word_boundary", 1500),
+        ("word_boundary_ascii", 1000),
+    ] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Empty-Match Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("empty_match", "Empty and Zero-Width Match Benchmarks");
+
+    // An empty match at nearly every position: the iterator has to step past
+    // each one without reporting it twice, and on Unicode text without
+    // stopping inside a character
+    let no_a_text = "hello world, this is some text with no first letter in it. ".repeat(200);
+    let no_a_unicode_text = "Привет мир, 你好世界, grüß dich! ".repeat(200);
+    let a_star = Regex::new(r"a*")?;
+    let empty_group = Regex::new(r"(?:)")?;
+    // Leftmost-first prefers the empty branch, so commas are never consumed
+    let empty_alternation = Regex::new(r"(?:|,)")?;
+    let word_boundary = Regex::new(r"\b")?;
+    for (variant, text) in [("ascii", &no_a_text), ("unicode", &no_a_unicode_text)] {
+        let name = format!("empty_star_{}", variant);
+        run_benchmark(timer, all_results, &name, &a_star, text, 10, BenchType::FindAll);
+        if let Some(result) = all_results.get_mut(&name) {
+            result.variant = Some(Variant {
+                family: "empty_star".to_string(),
+                name: variant.to_string(),
+            });
+        }
+    }
+    run_benchmark(timer, all_results, "empty_group", &empty_group, &no_a_text, 10, BenchType::FindAll);
+    run_benchmark(timer, all_results, "empty_alternation", &empty_alternation, &no_a_text, 10, BenchType::FindAll);
+    run_benchmark(timer, all_results, "word_boundary_scan", &word_boundary, &no_a_text, 10, BenchType::FindAll);
+    for (name, expected) in [
+        ("empty_star_ascii", 11801),
+        ("empty_star_unicode", 5801),
+        ("empty_group", 11801),
+        ("empty_alternation", 11801),
+        ("word_boundary_scan", 4800),
+    ] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Alternation Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("alternation", "Alternation Benchmarks");
+    all_results.exercises(EnginePath::PureDfa);
+
+    run_benchmark(timer, all_results, "alternation_simple", &patterns.alt_simple, &text_alternation_10000, 1000, BenchType::Search);  // Updated text size and iterations (50->1000)
+    run_benchmark(timer, all_results, "alternation_words", &patterns.alt_words, &text_alternation_10000, 1000, BenchType::Search);  // Updated text size and iterations (50->1000)
+
+    // ===-----------------------------------------------------------------------===
+    // Keyword Set Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("keyword_set", "Keyword Set Benchmarks (alternation vs Aho-Corasick)");
+    all_results.exercises(EnginePath::LiteralOptimizer);
+
+    // The same literals as a regex alternation and through the aho-corasick
+    // crate directly (leftmost-first, as the alternation matches), so the
+    // gap is what the regex engine adds around its own literal machinery
+    let vocabulary = make_vocabulary(2000);
+    let keyword_text =
+        (0..30_000).map(|i| vocabulary[i * 7919 % vocabulary.len()].as_str()).collect::<Vec<_>>().join(" ");
+    for (size, expected) in [(10, 150), (100, 1500), (1000, 15060)] {
+        let keywords = &vocabulary[..size];
+        let alternation = keywords.iter().map(|keyword| regex::escape(keyword)).collect::<Vec<_>>().join("|");
+        let keyword_regex = Regex::new(&alternation)?;
+        let automaton = aho_corasick::AhoCorasick::builder()
+            .match_kind(aho_corasick::MatchKind::LeftmostFirst)
+            .build(keywords)?;
+        let family = format!("keywords_{}", size);
+        let regex_name = format!("{}_regex", family);
+        let aho_name = format!("{}_aho", family);
+        run_benchmark(timer, all_results, &regex_name, &keyword_regex, &keyword_text, 10, BenchType::FindAll);
+        let scan = move |text: &str| automaton.find_iter(text).collect::<Vec<_>>().len();
+        run_scan_benchmark(timer, all_results, &aho_name, "FindAllAhoCorasick", &alternation, &keyword_text, 10, scan);
+        for (name, variant) in [(regex_name, "regex"), (aho_name, "aho")] {
+            all_results.expect_matches(&name, expected);
+            if let Some(result) = all_results.get_mut(&name) {
+                result.variant = Some(Variant {
+                    family: family.clone(),
+                    name: variant.to_string(),
+                });
+            }
+        }
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Match Semantics Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("match_semantics", "Leftmost-First vs Leftmost-Longest Benchmarks");
+
+    // Alternations whose branches are prefixes of each other, where the two
+    // semantics report different matches: leftmost-first takes the first
+    // branch that matches, leftmost-longest (POSIX) the longest
+    let semantics_text = "int count; interface Shape; integer in range; sam met samwise and samuel at 3.14 or 42. ".repeat(500);
+    let semantics_patterns = [
+        ("semantics_keywords", r"in|int|integer|interface"),
+        ("semantics_names", r"sam|samwise|samuel"),
+        ("semantics_numbers", r"\d+|\d+\.\d+"),
+    ];
+    for (family, pattern) in semantics_patterns {
+        let first_name = format!("{}_first", family);
+        run_benchmark(timer, all_results, &first_name, &Regex::new(pattern)?, &semantics_text, 10, BenchType::FindAll);
+        let first = meta::Regex::new(pattern)?;
+        let longest = meta::Regex::builder()
+            .configure(meta::Regex::config().match_kind(MatchKind::All))
+            .build(pattern)?;
+        let longest_name = format!("{}_longest", family);
+        run_scan_benchmark(timer, all_results, &longest_name, "FindAllLongest", pattern, &semantics_text, 10, move |text| {
+            count_leftmost_longest(&first, &longest, text)
+        });
+        for (name, variant) in [(first_name, "first"), (longest_name, "longest")] {
+            if let Some(result) = all_results.get_mut(&name) {
+                result.variant = Some(Variant {
+                    family: family.to_string(),
+                    name: variant.to_string(),
+                });
+            }
+        }
+    }
+    for (name, expected) in [
+        ("semantics_keywords_first", 2000),
+        ("semantics_keywords_longest", 2000),
+        ("semantics_names_first", 1500),
+        ("semantics_names_longest", 1500),
+        // "3.14" is two matches to leftmost-first, "3" and "14"
+        ("semantics_numbers_first", 1500),
+        ("semantics_numbers_longest", 1000),
+    ] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Group Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("group", "Group Benchmarks");
+    all_results.exercises(EnginePath::Hybrid);
+
+    run_benchmark(timer, all_results, "group_quantified", &patterns.group_quantified, &text_group_10000, 1000, BenchType::Search);  // Updated text size and iterations (50->1000)
+    run_benchmark(timer, all_results, "group_alternation", &patterns.group_alternation, &text_group_10000, 1000, BenchType::Search);  // Updated text size and iterations (50->1000)
+
+    // Each group benchmark again with `(?:...)`, so the variant deltas show
+    // what tracking captures costs. The regex crate only tracks them when
+    // asked for captures, hence the Captures pair on the serial numbers
+    let group_quantified_noncap = Regex::new("(?:abc)+")?;
+    let group_alternation_noncap = Regex::new("(?:a|b)*")?;
+    let serials_noncap = Regex::new(r"(?:[A-Z]{3}[0-9]{4})-(?:[A-Z]{3}[0-9]{3})")?;
+    let serials_text = "Serial: ABC1234-DEF567 Part: MNO3456-PQR678 Code: none ".repeat(100);
+    run_benchmark(timer, all_results, "group_quantified_noncap", &group_quantified_noncap, &text_group_10000, 1000, BenchType::Search);
+    run_benchmark(timer, all_results, "group_alternation_noncap", &group_alternation_noncap, &text_group_10000, 1000, BenchType::Search);
+    run_benchmark(timer, all_results, "group_captures_serials", &patterns.grouped_quantifiers, &serials_text, 100, BenchType::Captures);
+    run_benchmark(timer, all_results, "group_captures_serials_noncap", &serials_noncap, &serials_text, 100, BenchType::Captures);
+    all_results.expect_matches("group_captures_serials", 200);
+    all_results.expect_matches("group_captures_serials_noncap", 200);
+    for (family, capturing, noncapturing) in [
+        ("group_quantified", "group_quantified", "group_quantified_noncap"),
+        ("group_alternation", "group_alternation", "group_alternation_noncap"),
+        ("group_captures_serials", "group_captures_serials", "group_captures_serials_noncap"),
+    ] {
+        for (name, variant) in [(capturing, "capture"), (noncapturing, "noncap")] {
+            if let Some(result) = all_results.get_mut(name) {
+                result.variant = Some(Variant {
+                    family: family.to_string(),
+                    name: variant.to_string(),
+                });
+            }
+        }
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // NEW: Optimization Showcase Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("optimization_showcase", "Optimization Showcase Benchmarks");
+    all_results.exercises(EnginePath::Hybrid);
+
+    // Test case 1: Large alternation (8 branches) - benefits from increased branch limit (3→8)
+    let fruit_text = "I love eating apple and banana and cherry and date and elderberry and fig and grape with honey";
+    run_benchmark(timer, all_results, "large_8_alternations", &patterns.large_alternation, fruit_text, 1000, BenchType::Search);
+
+    // Test case 2: Deeply nested groups (depth 4) - benefits from increased depth tolerance (3→4)
+    let nested_text = "Testing deep nested patterns with abcdefgh characters";
+    run_benchmark(timer, all_results, "deep_nested_groups_depth4", &patterns.deep_nested, nested_text, 1000, BenchType::Search);
+
+    // Test case 3: Literal-heavy alternation - benefits from 80% threshold detection
+    let user_text = "Login attempts: user123 failed, admin456 success, guest789 failed, root000 success, test111 pending, demo222 active, sample333 inactive, client444 locked";
+    run_benchmark(timer, all_results, "literal_heavy_alternation", &patterns.literal_heavy, user_text, 1000, BenchType::Search);
+
+    // Test case 4: Complex group with 5 children - benefits from increased children limit (3→5)
+    let mixed_text = "Found: hello123ab, world456cd, test789ef, demo012gh, sample345ij in the data";
+    run_benchmark(timer, all_results, "complex_group_5_children", &patterns.complex_group, mixed_text, 1000, BenchType::Search);
+
+    // ===-----------------------------------------------------------------------===
+    // Global Matching Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("global", "Global Matching Benchmarks");
+    all_results.exercises(EnginePath::PureDfa);
+
+    run_benchmark(timer, all_results, "match_all_simple", &patterns.a, &text_10000, 200, BenchType::FindAll);  // Updated text size and iterations (10->200)
+    run_benchmark(timer, all_results, "match_all_pattern", &patterns.range_a_z, &text_10000, 200, BenchType::FindAll);  // Updated text size and iterations (10->200)
+    // Match counts are part of the contract: an engine that silently finds
+    // nothing would otherwise just look fast
+    all_results.expect_matches("match_all_simple", 385);
+    all_results.expect_matches("match_all_pattern", 1);
+
+    // ===-----------------------------------------------------------------------===
+    // Complex Pattern Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("complex", "Complex Pattern Benchmarks");
+    all_results.exercises(EnginePath::Hybrid);
+
+    run_benchmark(timer, all_results, "complex_email_extraction", &patterns.email, &email_text, 40, BenchType::FindAll);  // Increased from 2 to 40
+    run_benchmark(timer, all_results, "complex_number_extraction", &patterns.number, &number_text, 500, BenchType::FindAll);  // Increased from 25 to 500
+    all_results.expect_matches("complex_email_extraction", 4);
+    all_results.expect_matches("complex_number_extraction", 3);
+
+    // ===-----------------------------------------------------------------------===
+    // SIMD-Optimized Character Filtering Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("simd", "SIMD-Optimized Character Filtering Benchmarks");
+    all_results.exercises(EnginePath::SimdCharClass);
+
+    let large_mixed_text = make_mixed_content_text(100000);  // Increased from 10000 to 100000
+    let xlarge_mixed_text = make_mixed_content_text(500000);  // Increased from 50000 to 500000
+
+    run_benchmark(timer, all_results, "simd_alphanumeric_large", &patterns.range_alnum, &large_mixed_text, 200, BenchType::IsMatch);  // Increased from 10 to 200
+    run_benchmark(timer, all_results, "simd_alphanumeric_xlarge", &patterns.range_alnum, &xlarge_mixed_text, 200, BenchType::IsMatch);  // Increased from 10 to 200
+    // Scanning 500 KB must not grow the lazy DFA cache without bound
+    all_results.require("simd_alphanumeric_xlarge", Criteria::new().max_allocations(0.0).max_peak_rss_mib(64));
+    run_benchmark(timer, all_results, "simd_negated_alphanumeric", &patterns.negated_alnum, &large_mixed_text, 200, BenchType::IsMatch);  // Increased from 10 to 200
+    run_benchmark(timer, all_results, "simd_multi_char_class", &patterns.multi_char_class, &large_mixed_text, 200, BenchType::IsMatch);  // Increased from 10 to 200
+
+    // Halfway through the suite: re-time the throttling sentinel
+    all_results.checkpoint("middle");
+
+    // ===-----------------------------------------------------------------------===
+    // Literal Optimization Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("literal_optimization", "Literal Optimization Benchmarks");
+    all_results.exercises(EnginePath::LiteralOptimizer);
+
+    let medium_text = get_medium_text();
+    let long_text = get_long_text();
+    let email_long = get_email_long();
+
+    run_benchmark(timer, all_results, "literal_prefix_short", &patterns.literal_prefix_short, SHORT_TEXT, 1, BenchType::FindAll);
+    run_benchmark(timer, all_results, "literal_prefix_medium", &patterns.literal_prefix_medium, &medium_text, 1, BenchType::FindAll);
+    run_benchmark(timer, all_results, "literal_prefix_long", &patterns.literal_prefix_long, &long_text, 1, BenchType::FindAll);
+    run_benchmark(timer, all_results, "required_literal_short", &patterns.required_literal, EMAIL_TEXT, 1, BenchType::FindAll);
+    run_benchmark(timer, all_results, "required_literal_long", &patterns.required_literal, &email_long, 1, BenchType::FindAll);
+    run_benchmark(timer, all_results, "no_literal_baseline", &patterns.range_a_z, &medium_text, 1, BenchType::FindAll);
+    run_benchmark(timer, all_results, "alternation_common_prefix", &patterns.alt_common_prefix, &medium_text, 1, BenchType::FindAll);
+
+    // ===-----------------------------------------------------------------------===
+    // US Phone Number Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("phone", "US Phone Number Benchmarks");
+    all_results.exercises(EnginePath::Hybrid);
+
+    let phone_text = make_phone_test_data(1000);
+
+    run_benchmark(timer, all_results, "simple_phone", &patterns.simple_phone, &phone_text, 100, BenchType::FindAll);
+    run_benchmark(timer, all_results, "flexible_phone", &patterns.flexible_phone, &phone_text, 100, BenchType::FindAll);
+    run_benchmark(timer, all_results, "multi_format_phone", &patterns.multi_format_phone, &phone_text, 50, BenchType::FindAll);
+    all_results.expect_matches("simple_phone", 375);
+    all_results.expect_matches("flexible_phone", 1000);
+    all_results.expect_matches("multi_format_phone", 875);
+    // Rejects its input, whose exchange starts with 1
+    all_results.expects_no_match();
+    run_benchmark(timer, all_results, "phone_validation", &patterns.phone_validation, "555-123-4567", 500, BenchType::IsMatch);
+    all_results.expect_matches("phone_validation", 0);
+
+    // ===-----------------------------------------------------------------------===
+    // DFA-Optimized Phone Number Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("dfa_phone", "DFA-Optimized Phone Number Benchmarks");
+    all_results.exercises(EnginePath::PureDfa);
+
+    run_benchmark(timer, all_results, "dfa_simple_phone", &patterns.dfa_simple_phone, &phone_text, 100, BenchType::FindAll);
+    run_benchmark(timer, all_results, "dfa_paren_phone", &patterns.dfa_paren_phone, &phone_text, 100, BenchType::FindAll);
+    run_benchmark(timer, all_results, "dfa_dot_phone", &patterns.dfa_dot_phone, &phone_text, 100, BenchType::FindAll);
+    run_benchmark(timer, all_results, "dfa_digits_only", &patterns.dfa_digits_only, &phone_text, 100, BenchType::FindAll);
+
+    // National Phone Number Validation (Complex Pattern)
+    let national_phone_text = make_complex_pattern_test_data(500);
+    run_benchmark(timer, all_results, "national_phone_validation", &patterns.national_phone_validation, &national_phone_text, 10, BenchType::FindAll);
+    for (name, expected) in [
+        ("dfa_simple_phone", 375),
+        ("dfa_paren_phone", 125),
+        ("dfa_dot_phone", 125),
+        ("dfa_digits_only", 125),
+        ("national_phone_validation", 400),
+    ] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Capture Group Extraction Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("captures", "Capture Group Extraction Benchmarks");
+    all_results.exercises(EnginePath::NfaBacktracker);
+
+    // Area code, exchange and line number, of one number and of every number in a text
+    let phone_groups = Regex::new(r"\(?(\d{3})\)?[\s.-]?(\d{3})[\s.-]?(\d{4})")?;
+    let email_groups = Regex::new(r"([a-zA-Z0-9._%+-]+)@([a-zA-Z0-9.-]+\.[a-zA-Z]{2,})")?;
+    // Unlike phone_validation's input, a valid number: exchanges can't start with 1
+    run_benchmark(timer, all_results, "phone_validation_captures", &patterns.phone_validation, "555-234-5678", 500, BenchType::Captures);
+    run_benchmark(timer, all_results, "phone_captures_iter", &phone_groups, &phone_text, 100, BenchType::Captures);
+    // Local part and domain
+    run_benchmark(timer, all_results, "email_captures_iter", &email_groups, &email_text, 40, BenchType::Captures);
+    for (name, expected) in [("phone_validation_captures", 1), ("phone_captures_iter", 1000), ("email_captures_iter", 4)] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Backreference Benchmarks
+    // ===-----------------------------------------------------------------------===
+    // The regex crate has no backreferences or lookaround, so these and the
+    // lookaround benchmarks run on fancy-regex, as the Rust baseline for
+    // engines that support them
+    #[cfg(feature = "fancy")]
+    {
+        all_results.begin_category("backreference", "Backreference Benchmarks (fancy-regex)");
+
+        let doubled_text = "Paris in the the spring. It was was a dark and stormy night; this is fine, \
+                            but don't repeat repeat yourself, and and never end with the\nthe same word. "
+            .repeat(500);
+        let html_text = "<p>An <b>important</b> note</p><div><i>tilted</i> and <b>bold <i>both</i></b></div>\n".repeat(500);
+        // Substrings too: "is is" in "this is"
+        let repeated_word = fancy_regex::Regex::new(r"(\w+) \1")?;
+        // Whole words only, across any whitespace
+        let duplicate_word = fancy_regex::Regex::new(r"\b(\w+)\s+\1\b")?;
+        // An element with no nested elements, closed by its own tag
+        let html_element = fancy_regex::Regex::new(r"<(\w+)>[^<]*</\1>")?;
+        run_fancy_benchmark(timer, all_results, "backref_repeated_word", &repeated_word, &doubled_text, 20);
+        run_fancy_benchmark(timer, all_results, "backref_duplicate_word", &duplicate_word, &doubled_text, 20);
+        run_fancy_benchmark(timer, all_results, "backref_html_element", &html_element, &html_text, 20);
+        for (name, expected) in [("backref_repeated_word", 3000), ("backref_duplicate_word", 2500), ("backref_html_element", 1500)] {
+            all_results.expect_matches(name, expected);
+        }
+    }
+
+    #[cfg(not(feature = "fancy"))]
+    all_results.skip_indices(gated_len("backreference"));
+
+    // ===-----------------------------------------------------------------------===
+    // Lookaround Benchmarks
+    // ===-----------------------------------------------------------------------===
+    #[cfg(feature = "fancy")]
+    {
+        all_results.begin_category("lookaround", "Lookaround Benchmarks (fancy-regex)");
+
+        let price_text = "Paid 25 dollars for 3 books, $40 for lunch and 120 dollars in fees; saved $7. ".repeat(500);
+        let password_text = "hunter2\nPassword1!\ncorrecthorsebatterystaple\nTr0ub4dor&3\nALLCAPS123!\ns3cr3t!Pass\n".repeat(500);
+        let amount_in_dollars = fancy_regex::Regex::new(r"\d+(?= dollars)")?;
+        let amount_after_sign = fancy_regex::Regex::new(r"(?<=\$)\d+")?;
+        let amount_without_sign = fancy_regex::Regex::new(r"(?<!\$)\b\d+\b")?;
+        // Lowercase, uppercase, digit and symbol, at least 8 characters
+        let password_policy = fancy_regex::Regex::new(r"(?m)^(?=.*[a-z])(?=.*[A-Z])(?=.*\d)(?=.*[^\w\s]).{8,}$")?;
+        run_fancy_benchmark(timer, all_results, "lookahead_dollars", &amount_in_dollars, &price_text, 20);
+        run_fancy_benchmark(timer, all_results, "lookbehind_dollar_sign", &amount_after_sign, &price_text, 20);
+        run_fancy_benchmark(timer, all_results, "negative_lookbehind_dollar", &amount_without_sign, &price_text, 20);
+        run_fancy_benchmark(timer, all_results, "lookahead_password_policy", &password_policy, &password_text, 20);
+        for (name, expected) in [
+            ("lookahead_dollars", 1000),
+            ("lookbehind_dollar_sign", 1000),
+            ("negative_lookbehind_dollar", 1500),
+            ("lookahead_password_policy", 1500),
+        ] {
+            all_results.expect_matches(name, expected);
+        }
+    }
+
+    #[cfg(not(feature = "fancy"))]
+    all_results.skip_indices(gated_len("lookaround"));
+
+    // ===-----------------------------------------------------------------------===
+    // Quantifier Parser Optimization Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("quantifier_parser", "Quantifier Parser Optimization Benchmarks");
+    all_results.exercises(EnginePath::PureDfa);
+
+    // Generate test data for quantifier-intensive patterns
+    let serial_number_text = "Serial: ABC1234-DEF5678-GHI9012 Model: XYZ123-ABC456-DEF789 Part: MNO345-PQR678-STU901 Code: VWX234-YZA567-BCD890 ".repeat(50);
+    let datetime_text = "2024-01-15 14:30:25.123 2024-02-28 09:45:30.456 2024-03-10 16:20:15.789 2024-04-05 11:35:40.012 ".repeat(100);
+    let structured_data_text = "Record: USER12345-DEPT678-LOC901-ID234 Status: ACTIVE567-FLAG890-CODE123 Transaction: TXN9876-AMT543-FEE210-TAX087 Reference: REF1357-NUM246-CHK802 ".repeat(75);
+
+    // Single quantifier patterns (baseline)
+    run_benchmark(timer, all_results, "single_quantifier_digits", &patterns.single_quantifier_digits, &serial_number_text, 200, BenchType::FindAll);
+    run_benchmark(timer, all_results, "single_quantifier_alpha", &patterns.single_quantifier_alpha, &serial_number_text, 200, BenchType::FindAll);
+
+    // Multiple quantifier patterns - these benefit most from the optimization
+    run_benchmark(timer, all_results, "dual_quantifiers", &patterns.dual_quantifiers, &serial_number_text, 150, BenchType::FindAll);
+    run_benchmark(timer, all_results, "triple_quantifiers", &patterns.triple_quantifiers, &serial_number_text, 100, BenchType::FindAll);
+    // Segment shapes the serials never have, so it measures rejected
+    // candidates (as does mixed_range_quantifiers below)
+    all_results.expects_no_match();
+    run_benchmark(timer, all_results, "quad_quantifiers", &patterns.quad_quantifiers, &serial_number_text, 100, BenchType::FindAll);
+    all_results.expects_matches();
+    all_results.expect_matches("quad_quantifiers", 0);
+
+    // Complex quantifier ranges {min,max} - stress test the parser optimization
+    run_benchmark(timer, all_results, "range_quantifiers", &patterns.range_quantifiers, &serial_number_text, 100, BenchType::FindAll);
+    all_results.expects_no_match();
+    run_benchmark(timer, all_results, "mixed_range_quantifiers", &patterns.mixed_range_quantifiers, &serial_number_text, 75, BenchType::FindAll);
+    all_results.expects_matches();
+    all_results.expect_matches("mixed_range_quantifiers", 0);
+
+    // DateTime patterns with many quantifiers
+    run_benchmark(timer, all_results, "datetime_quantifiers", &patterns.datetime_quantifiers, &datetime_text, 100, BenchType::FindAll);
+    run_benchmark(timer, all_results, "flexible_datetime", &patterns.flexible_datetime, &datetime_text, 100, BenchType::FindAll);
+
+    // High quantifier density patterns - maximum parser stress
+    run_benchmark(timer, all_results, "dense_quantifiers", &patterns.dense_quantifiers, &structured_data_text, 50, BenchType::FindAll);
+    run_benchmark(timer, all_results, "ultra_dense_quantifiers", &patterns.ultra_dense_quantifiers, &structured_data_text, 25, BenchType::FindAll);
+
+    // Nested quantifiers within groups
+    all_results.exercises(EnginePath::Hybrid);
+    run_benchmark(timer, all_results, "grouped_quantifiers", &patterns.grouped_quantifiers, &serial_number_text, 100, BenchType::FindAll);
+    run_benchmark(timer, all_results, "alternation_quantifiers", &patterns.alternation_quantifiers, &structured_data_text, 75, BenchType::FindAll);
+
+    // ===-----------------------------------------------------------------------===
+    // OPTIMIZATION SHOWCASE: Quantifier Parser Improvements
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("quantifier_showcase", "Quantifier Optimization Showcase Benchmarks");
+    all_results.exercises(EnginePath::PureDfa);
+
+    // Optimization test data for quantifier stress testing
+    let optimization_test_text = "Transaction: TXN12345-DEPT678-LOC90123-ID4567 Status: ACTIVE12-FLAG890-CODE1234 Reference: REF13579-NUM24680-CHK80246 Product: PROD123-CAT456-TYPE789-SUB012 ".repeat(100);
+
+    // Most significant optimization cases from analysis
+    run_benchmark(timer, all_results, "optimize_range_quantifier", &patterns.optimize_range_quantifier, &("aaaabbbbccccdddd".repeat(500)), 1000, BenchType::FindAll);
+    // No record has this shape: rejected candidates only
+    all_results.expects_no_match();
+    run_benchmark(timer, all_results, "optimize_multiple_quantifiers", &patterns.optimize_multiple_quantifiers, &optimization_test_text, 200, BenchType::FindAll);
+    all_results.expects_matches();
+    all_results.expect_matches("optimize_multiple_quantifiers", 0);
+    run_benchmark(timer, all_results, "optimize_phone_quantifiers", &patterns.optimize_phone_quantifiers, &("Call 555-123-4567 or 800-555-1234 or 900-876-5432 for help. ".repeat(200)), 300, BenchType::FindAll);
+    run_benchmark(timer, all_results, "optimize_large_quantifiers", &patterns.optimize_large_quantifiers, &(format!("PREFIX{}{}SUFFIX ", "A".repeat(15), "1".repeat(20)).repeat(50)), 100, BenchType::FindAll);
+    // One "b" short of a match in every unit
+    all_results.expects_no_match();
+    run_benchmark(timer, all_results, "optimize_extreme_quantifiers", &patterns.optimize_extreme_quantifiers, &("abcccddddeeeeeffffffggggggghhhhhhhhSEPARATOR".repeat(20)), 500, BenchType::FindAll);
+    all_results.expect_matches("optimize_extreme_quantifiers", 0);
+
+    // ===-----------------------------------------------------------------------===
+    // is_match (Bool-only) Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("is_match", "is_match (Bool-only) Benchmarks");
+    all_results.exercises(EnginePath::SimdCharClass);
+
+    let text_digits_10000 = "0123456789".repeat(1000) + "abcdefghijklmnopqrstuvwxyz";
+    run_benchmark(timer, all_results, "is_match_lowercase", &patterns.range_a_z, &text_range_10000, 1000, BenchType::IsMatchBool);
+    run_benchmark(timer, all_results, "is_match_digits", &patterns.range_0_9, &text_digits_10000, 1000, BenchType::IsMatchBool);
+    run_benchmark(timer, all_results, "is_match_alphanumeric", &patterns.range_alnum, &text_range_10000, 1000, BenchType::IsMatchBool);
+    run_benchmark(timer, all_results, "is_match_predefined_digits", &patterns.predefined_digits, &text_digits_10000, 1000, BenchType::IsMatchBool);
+    run_benchmark(timer, all_results, "is_match_predefined_word", &patterns.predefined_word, &text_range_10000, 1000, BenchType::IsMatchBool);
+
+    // ===-----------------------------------------------------------------------===
+    // Early-Exit Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("early_exit", "Early-Exit (shortest_match) Benchmarks");
+
+    // One needle at 1%, 50% or 99% of the haystack: a search that stops at
+    // the first match should cost in proportion to where it sits. The long
+    // digit run separates is_match and shortest_match, which stop at its
+    // first digit, from find, which has to reach its end
+    let needle = format!("ERROR {}", "7".repeat(64));
+    let needle_pat = Regex::new(r"ERROR [0-9]+")?;
+    let filler: String = "the quick brown fox jumps over the lazy dog ".chars().cycle().take(100000).collect();
+    for percent in [1, 50, 99] {
+        let mut text = filler.clone();
+        text.insert_str(filler.len() * percent / 100, &needle);
+        for (family, bench_type) in [
+            ("early_exit_is_match", BenchType::IsMatchBool),
+            ("early_exit_shortest", BenchType::ShortestMatch),
+            ("early_exit_find", BenchType::Search),
+        ] {
+            let name = format!("{}_{}pct", family, percent);
+            run_benchmark(timer, all_results, &name, &needle_pat, &text, 100, bench_type);
+            all_results.expect_matches(&name, 1);
+            if let Some(result) = all_results.get_mut(&name) {
+                result.variant = Some(Variant {
+                    family: family.to_string(),
+                    name: format!("{}pct", percent),
+                });
+            }
+        }
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // sub (replacement) Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("sub", "sub (replacement) Benchmarks");
+
+    let short_text_20 = "hello world this is a test with hello again and hello there".repeat(20);
+    let whitespace_text = "  hello   world   foo   bar   baz  ".repeat(100);
+    let short_text_100 = "hello world this is a test with hello again and hello there".repeat(100);
+
+    let sub_hello = Regex::new("hello")?;
+    let sub_phone = Regex::new(r"\d{3}-\d{3}-\d{4}")?;
+    let sub_digits = Regex::new("[0-9]+")?;
+    let sub_whitespace = Regex::new(r"\s+")?;
+
+    run_sub_benchmark(timer, all_results, "sub_literal", &sub_hello, "REPLACED", &short_text_20, 100);
+    run_sub_benchmark(timer, all_results, "sub_digits", &sub_phone, "XXX-XXX-XXXX", &phone_text, 10);
+    run_sub_benchmark(timer, all_results, "sub_char_class", &sub_digits, "#", &phone_text, 10);
+    run_sub_benchmark(timer, all_results, "sub_whitespace", &sub_whitespace, " ", &whitespace_text, 50);
+    run_sub_benchmark(timer, all_results, "sub_limited_count", &sub_hello, "HI", &short_text_100, 100);
+
+    // Group-reference substitution benchmarks; capture groups need the NFA
+    all_results.exercises(EnginePath::NfaBacktracker);
+    let phone_numbers = "Call 6502530000 or 4155551234 today. ".repeat(100);
+    let sub_group_phone = Regex::new(r"(\d{3})(\d{3})(\d{4})")?;
+    let sub_group_date = Regex::new(r"(\d{4})-(\d{2})-(\d{2})")?;
+    let sub_group_word = Regex::new(r"(\w+) (\w+)")?;
+
+    run_sub_benchmark(timer, all_results, "sub_group_phone_fmt", &sub_group_phone, "$1-$2-$3", &phone_numbers, 10);
+    run_sub_benchmark(timer, all_results, "sub_group_date_fmt", &sub_group_date, "$2/$3/$1", &("Event on 2026-04-12 and 2025-12-25 and 2024-01-01. ".repeat(50)), 20);
+    run_sub_benchmark(timer, all_results, "sub_group_word_swap", &sub_group_word, "$2 $1", &("hello world foo bar baz qux ".repeat(50)), 20);
+
+    // Redaction: mask the emails of the mixed-content text and the phone
+    // numbers of the phone text, with fixed text, a template and a closure
+    let redact_email = Regex::new(r"([a-zA-Z0-9._%+-]+)@([a-zA-Z0-9.-]+\.[a-zA-Z]{2,})")?;
+    let redact_phone = Regex::new(r"\(?(\d{3})\)?[\s.-]?(\d{3})[\s.-]?(\d{4})")?;
+    run_benchmark(timer, all_results, "replace_email_literal", &redact_email, &large_mixed_text, 2, BenchType::Replace(Replacement::Literal("[EMAIL]")));
+    run_benchmark(timer, all_results, "replace_email_template", &redact_email, &large_mixed_text, 2, BenchType::Replace(Replacement::Template("***@$2")));
+    run_benchmark(timer, all_results, "replace_email_closure", &redact_email, &large_mixed_text, 2, BenchType::Replace(Replacement::MaskFirstGroup));
+    run_benchmark(timer, all_results, "replace_phone_template", &redact_phone, &phone_text, 10, BenchType::Replace(Replacement::Template("(XXX) XXX-$3")));
+    run_benchmark(timer, all_results, "replace_phone_closure", &redact_phone, &phone_text, 10, BenchType::Replace(Replacement::MaskFirstGroup));
+    all_results.expect_matches("replace_email_closure", 1064);
+    all_results.expect_matches("replace_phone_closure", 1000);
+
+    // Sanitization pipelines redact every kind of sensitive value in a single
+    // pass over a multi-MB document, one alternation for all of them
+    let support_tickets = all_results.setup("support tickets", || make_support_tickets(75_000));
+    let redact_sensitive = Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}|\b(?:\d{4}[ -]?){3}\d{4}\b|\b\d{3}[-. ]\d{3}[-. ]\d{4}\b")?;
+    run_benchmark(timer, all_results, "redact_tickets_10mb", &redact_sensitive, &support_tickets, 1, BenchType::Replace(Replacement::Literal("[REDACTED]")));
+    all_results.expect_matches("redact_tickets_10mb", 225_000);
+
+    // ===-----------------------------------------------------------------------===
+    // Split (Tokenization) Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("split", "split (tokenization) Benchmarks");
+
+    let words_text = "the quick  brown fox\tjumps over\nthe lazy dog ".repeat(2000);
+    let csv_text = "alpha,beta, gamma ,delta,,epsilon\n".repeat(3000);
+    let mixed_delimited_text = "key=value; other | item,thing  last;".repeat(2500);
+    let split_whitespace = Regex::new(r"\s+")?;
+    let split_comma = Regex::new(r"\s*,\s*")?;
+    let split_delimiters = Regex::new(r"[,;|\s]+")?;
+
+    run_benchmark(timer, all_results, "split_whitespace", &split_whitespace, &words_text, 5, BenchType::Split);
+    run_benchmark(timer, all_results, "split_comma", &split_comma, &csv_text, 5, BenchType::Split);
+    run_benchmark(timer, all_results, "split_multi_delimiter", &split_delimiters, &mixed_delimited_text, 5, BenchType::Split);
+    // A small limit stops at the first few matches, however long the text
+    run_benchmark(timer, all_results, "splitn_whitespace_2", &split_whitespace, &words_text, 1000, BenchType::SplitN(2));
+    run_benchmark(timer, all_results, "splitn_comma_8", &split_comma, &csv_text, 1000, BenchType::SplitN(8));
+
+    // ===-----------------------------------------------------------------------===
+    // Anchored-at-Offset Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("anchored_scan", "Anchored-at-Offset (Lexer) Benchmarks");
+
+    // A lexer matches one token where the last one ended, over and over:
+    // thousands of short searches that each must start at a given offset
+    let source_text = "let x1 = foo(42) + bar_baz * 7;\nif x1 > 10 { return x1; } else { x1 = x1 - 1; }\n".repeat(500);
+    let token = r"[A-Za-z_][A-Za-z0-9_]*|[0-9]+|\s+|[-+*/=<>;(){}]";
+    let keyword = r"(?:fn|let|if|else|return)\b";
+    // find_at searches on past the offset, so a token found further on
+    // means none starts there
+    let token_pat = Regex::new(token)?;
+    run_scan_benchmark(timer, all_results, "lexer_find_at", "TokenizeFindAt", token, &source_text, 20, move |text| {
+        let (mut pos, mut tokens) = (0, 0);
+        while let Some(m) = token_pat.find_at(text, pos).filter(|m| m.start() == pos) {
+            pos = m.end();
+            tokens += 1;
+        }
+        tokens
+    });
+    let token_anchored = meta::Regex::new(token)?;
+    run_scan_benchmark(timer, all_results, "lexer_anchored", "TokenizeAnchored", token, &source_text, 20, move |text| {
+        let (mut pos, mut tokens) = (0, 0);
+        while let Some(m) = token_anchored.search(&Input::new(text).range(pos..).anchored(Anchored::Yes)) {
+            pos = m.end();
+            tokens += 1;
+        }
+        tokens
+    });
+    // Tried at every offset, failing at almost all of them
+    let keyword_anchored = meta::Regex::new(keyword)?;
+    run_scan_benchmark(timer, all_results, "keyword_every_offset", "ScanAnchored", keyword, &source_text, 5, move |text| {
+        (0..text.len())
+            .filter(|&pos| keyword_anchored.is_match(Input::new(text).range(pos..).anchored(Anchored::Yes)))
+            .count()
+    });
+    for (name, expected) in [("lexer_find_at", 27000), ("lexer_anchored", 27000), ("keyword_every_offset", 2000)] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // A whole tokenizer: rules tried in priority order where the last token
+    // ended, the first to match winning (keywords before identifiers). Both
+    // record their tokens per second
+    let program = r#"fn fib(n) { // naive
+    if n <= 1 { return n; } else { return fib(n - 1) + fib(n - 2); }
+}
+let greeting = "hello, \"world\"";
+while count != 10 && ready { count = count + 1; total = total * 2.5; }
+"#
+    .repeat(200);
+    let lexer_rules = [
+        r"\s+",
+        r"//[^\n]*",
+        r#""(?:[^"\\]|\\.)*""#,
+        r"[0-9]+(?:\.[0-9]+)?",
+        r"(?:fn|let|if|else|return|while)\b",
+        r"[A-Za-z_][A-Za-z0-9_]*",
+        r"==|!=|<=|>=|&&|\|\||[-+*/=<>!;,.(){}]",
+    ];
+    let all_rules = lexer_rules.join("|");
+    let mut rule_regexes = Vec::new();
+    for rule in lexer_rules {
+        rule_regexes.push(meta::Regex::new(rule)?);
+    }
+    run_scan_benchmark(timer, all_results, "lexer_ordered_rules", "TokenizeRules", &all_rules, &program, 5, move |text| {
+        let (mut pos, mut tokens) = (0, 0);
+        while pos < text.len() {
+            let input = Input::new(text).range(pos..).anchored(Anchored::Yes);
+            let Some(m) = rule_regexes.iter().find_map(|rule| rule.search(&input)) else {
+                break;
+            };
+            pos = m.end();
+            tokens += 1;
+        }
+        tokens
+    });
+    // The same rules as one multi-pattern regex, whose leftmost-first
+    // semantics keep the priority order
+    let multi_rules = meta::Regex::new_many(&lexer_rules)?;
+    run_scan_benchmark(timer, all_results, "lexer_multi_pattern", "TokenizeMultiPattern", &all_rules, &program, 5, move |text| {
+        let (mut pos, mut tokens) = (0, 0);
+        while let Some(m) = multi_rules.search(&Input::new(text).range(pos..).anchored(Anchored::Yes)) {
+            pos = m.end();
+            tokens += 1;
+        }
+        tokens
+    });
+    // The whole program, with nothing left unlexed
+    for name in ["lexer_ordered_rules", "lexer_multi_pattern"] {
+        all_results.expect_matches(name, 21200);
+        all_results.counts_tokens(name);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Match Counting Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("count", "Match Counting Benchmarks");
+
+    // Each pattern both collected into a Vec and only counted: the difference
+    // is the cost of materializing the matches, which dominates dense ones
+    let digits_text = make_test_string(100000, "0123456789 ");
+    let count_patterns = [
+        ("dense_words", Regex::new(r"\w+")?, words_text.as_str()),
+        ("dense_digits", Regex::new(r"\d")?, digits_text.as_str()),
+        ("dense_vowels", Regex::new("[aeiou]")?, text_100000.as_str()),
+        ("sparse_emails", patterns.email.clone(), large_mixed_text.as_str()),
+    ];
+    for (family, pattern, text) in &count_patterns {
+        for (variant, bench_type) in [("findall", BenchType::FindAll), ("count", BenchType::Count)] {
+            let name = format!("{}_{}", family, variant);
+            run_benchmark(timer, all_results, &name, pattern, text, 5, bench_type);
+            if let Some(result) = all_results.get_mut(&name) {
+                result.variant = Some(Variant {
+                    family: family.to_string(),
+                    name: variant.to_string(),
+                });
+            }
+        }
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Overlapping Match Benchmarks
+    // ===-----------------------------------------------------------------------===
+    #[cfg(feature = "overlapping")]
+    {
+        all_results.begin_category("overlapping", "Overlapping Match Benchmarks (regex-automata)");
+
+        // Every occurrence, including those starting inside another, which
+        // find_iter skips past; the leftmost twins are what it finds instead.
+        // Occurrences are counted by where they end, one each since every
+        // motif is fixed-length
+        let dna = all_results.setup("DNA sequence", || crate::make_dna_sequence(100000));
+        let ab_text = "ababababab xyz ".repeat(1000);
+        let motifs = [("aba", "aba", &ab_text), ("tata_box", "TATA", &dna), ("at_rich", "[AT]{6}", &dna)];
+        for (family, pattern, text) in motifs {
+            for variant in ["leftmost", "overlapping"] {
+                let name = format!("{}_{}", family, variant);
+                if variant == "leftmost" {
+                    run_benchmark(timer, all_results, &name, &Regex::new(pattern)?, text, 10, BenchType::FindAll);
+                } else {
+                    let dfa = hybrid::dfa::DFA::builder()
+                        .configure(hybrid::dfa::DFA::config().match_kind(MatchKind::All))
+                        .build(pattern)?;
+                    let cache = Mutex::new(dfa.create_cache());
+                    run_scan_benchmark(timer, all_results, &name, "FindOverlapping", pattern, text, 10, move |text| {
+                        count_overlapping(&dfa, &mut cache.lock().unwrap(), text)
+                    });
+                }
+                if let Some(result) = all_results.get_mut(&name) {
+                    result.variant = Some(Variant {
+                        family: family.to_string(),
+                        name: variant.to_string(),
+                    });
+                }
+            }
+        }
+        for (name, expected) in [
+            ("aba_leftmost", 2000),
+            ("aba_overlapping", 4000),
+            ("tata_box_leftmost", 341),
+            ("tata_box_overlapping", 360),
+            ("at_rich_leftmost", 782),
+            ("at_rich_overlapping", 1549),
+        ] {
+            all_results.expect_matches(name, expected);
+        }
+    }
+
+    #[cfg(not(feature = "overlapping"))]
+    all_results.skip_indices(gated_len("overlapping"));
+
+    // ===-----------------------------------------------------------------------===
+    // Streaming Benchmarks
+    // ===-----------------------------------------------------------------------===
+    #[cfg(feature = "streaming")]
+    {
+        all_results.begin_category("streaming", "Chunked Streaming Benchmarks (regex-automata)");
+
+        // A log read in 4KB or 64KB chunks, one DFA transition per byte with
+        // the state kept across chunks, against the same loop over the whole
+        // log as one chunk, so chunk size is all that differs; every
+        // occurrence is counted where it ends
+        let stream_log = all_results.setup("streamed log", || make_log_text(20000));
+        for (family, pattern) in [("stream_timeout", "upstream timeout"), ("stream_slow_query", r"took [0-9]{3}ms")] {
+            let dfa = hybrid::dfa::DFA::builder()
+                .configure(hybrid::dfa::DFA::config().match_kind(MatchKind::All))
+                .build(pattern)?;
+            for (variant, chunk_len) in [("whole", None), ("64k", Some(64 * 1024)), ("4k", Some(4 * 1024))] {
+                let name = format!("{}_{}", family, variant);
+                let (dfa, cache) = (dfa.clone(), Mutex::new(dfa.create_cache()));
+                let operation = chunk_len.map_or("StreamWhole".to_string(), |len| format!("Stream{}K", len / 1024));
+                let chunk_len = chunk_len.unwrap_or(stream_log.len());
+                run_scan_benchmark(timer, all_results, &name, &operation, pattern, &stream_log, 5, move |text| {
+                    count_overlapping_streamed(&dfa, &mut cache.lock().unwrap(), text, chunk_len)
+                });
+                all_results.expect_matches(&name, 2000);
+                if let Some(result) = all_results.get_mut(&name) {
+                    result.variant = Some(Variant {
+                        family: family.to_string(),
+                        name: variant.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "streaming"))]
+    all_results.skip_indices(gated_len("streaming"));
+
+    // ===-----------------------------------------------------------------------===
+    // Per-Engine Breakdown Benchmarks
+    // ===-----------------------------------------------------------------------===
+    #[cfg(feature = "engines")]
+    {
+        all_results.begin_category("engine_breakdown", "Per-Engine Breakdown Benchmarks (regex-automata)");
+
+        // The same searches through each engine the meta regex chooses
+        // between, rather than whichever it picked; each result records its
+        // engine. The backtracker's visited set bounds the haystack it takes
+        let breakdown_text = "Call 555-234-5678 or mail jane.doe@example.com about the apple, banana and cherry order. ".repeat(200);
+        for (family, pattern, expected) in [
+            ("engines_phone", r"[0-9]{3}-[0-9]{3}-[0-9]{4}", 200),
+            ("engines_email", r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}", 200),
+            ("engines_fruit", r"apple|banana|cherry", 600),
+        ] {
+            let dense = dfa::regex::Regex::new(pattern)?;
+            let sparse = dfa::regex::Builder::new().build_sparse(pattern)?;
+            let lazy = hybrid::regex::Regex::new(pattern)?;
+            let lazy_cache = Mutex::new(lazy.create_cache());
+            let pikevm = PikeVM::new(pattern)?;
+            let pikevm_cache = Mutex::new(pikevm.create_cache());
+            let backtracker = BoundedBacktracker::builder()
+                .configure(BoundedBacktracker::config().visited_capacity(16 << 20))
+                .build(pattern)?;
+            let backtracker_cache = Mutex::new(backtracker.create_cache());
+            type Scan = Box<dyn Fn(&str) -> usize + Send>;
+            let engines: [(&str, &str, &str, Scan); 5] = [
+                ("dense", "FindAllDenseDfa", "dense DFA", Box::new(move |text| dense.find_iter(text).count())),
+                ("sparse", "FindAllSparseDfa", "sparse DFA", Box::new(move |text| sparse.find_iter(text).count())),
+                (
+                    "lazy",
+                    "FindAllLazyDfa",
+                    "lazy DFA",
+                    Box::new(move |text| lazy.find_iter(&mut lazy_cache.lock().unwrap(), text).count()),
+                ),
+                (
+                    "pikevm",
+                    "FindAllPikeVm",
+                    "PikeVM",
+                    Box::new(move |text| pikevm.find_iter(&mut pikevm_cache.lock().unwrap(), text).count()),
+                ),
+                (
+                    "backtrack",
+                    "FindAllBacktrack",
+                    "bounded backtracker",
+                    Box::new(move |text| {
+                        let mut cache = backtracker_cache.lock().unwrap();
+                        let mut count = 0;
+                        for found in backtracker.try_find_iter(&mut cache, text) {
+                            found.expect("haystack fits the visited set");
+                            count += 1;
+                        }
+                        count
+                    }),
+                ),
+            ];
+            for (variant, operation, engine, scan) in engines {
+                let name = format!("{}_{}", family, variant);
+                run_scan_benchmark(timer, all_results, &name, operation, pattern, &breakdown_text, 10, scan);
+                all_results.expect_matches(&name, expected);
+                if let Some(result) = all_results.get_mut(&name) {
+                    result.engine = Some(engine.to_string());
+                    result.variant = Some(Variant {
+                        family: family.to_string(),
+                        name: variant.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "engines"))]
+    all_results.skip_indices(gated_len("engine_breakdown"));
+
+    // ===-----------------------------------------------------------------------===
+    // Sparse Match Benchmarks (long text, rare matches)
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("sparse", "Sparse Match Benchmarks");
+
+    let filler = "The quick brown fox jumps over the lazy dog. ".repeat(40);
+    let mut sparse_phone_text = String::new();
+    for _ in 0..20 {
+        sparse_phone_text.push_str(&filler);
+        sparse_phone_text.push_str("Call 555-123-4567 now. ");
+    }
+
+    let sparse_phone_pat = Regex::new(r"\d{3}-\d{3}-\d{4}")?;
+    run_benchmark(timer, all_results, "sparse_phone_findall", &sparse_phone_pat, &sparse_phone_text, 5, BenchType::FindAll);
+
+    let sparse_paren_phone_pat = Regex::new(r"\(\d{3}\)\s\d{3}-\d{4}")?;
+    let sparse_search_text = format!("{}(555) 123-4567{}", filler.repeat(50), filler.repeat(50));
+    run_benchmark(timer, all_results, "sparse_phone_search", &sparse_paren_phone_pat, &sparse_search_text, 5, BenchType::Search);
+
+    let sparse_email_pat = Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}")?;
+    let sparse_email_text = format!("{filler}Contact admin@example.com for details. ").repeat(10);
+    run_benchmark(timer, all_results, "sparse_email_findall", &sparse_email_pat, &sparse_email_text, 5, BenchType::FindAll);
+
+    let sparse_flex_phone_pat = Regex::new(r"\(?\d{3}\)?[\s.-]?\d{3}[\s.-]?\d{4}")?;
+    let mut sparse_flex_text = String::new();
+    for _ in 0..10 {
+        sparse_flex_text.push_str(&filler);
+        sparse_flex_text.push_str("Reach us at (555) 123-4567 today. ");
+    }
+    run_benchmark(timer, all_results, "sparse_flex_phone_findall", &sparse_flex_phone_pat, &sparse_flex_text, 2, BenchType::FindAll);
+
+    // ===-----------------------------------------------------------------------===
+    // Many-State DFA Benchmarks (NANPA)
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("nanpa", "Many-State DFA Benchmarks (NANPA)");
+    all_results.exercises(EnginePath::PureDfa);
+
+    let nanpa_pat = Regex::new(r"(?:2(?:0[1-35-9]|1[02-9]|2[03-57-9]|3[1459]|4[08]|5[1-46]|6[0279]|7[0269]|8[13])|3(?:0[1-47-9]|1[02-9]|2[0135-79]|3[0-24679]|4[167]|5[0-2]|6[01349]|8[056])|4(?:0[124-9]|1[02-579]|2[3-5]|3[0245]|4[023578]|58|6[349]|7[0589]|8[04])|5(?:0[1-47-9]|1[0235-8]|20|3[0149]|4[01]|5[179]|6[1-47]|7[0-5]|8[0256])|6(?:0[1-35-9]|1[024-9]|2[03689]|3[016]|4[0156]|5[01679]|6[0-279]|78|8[0-29])|7(?:0[1-46-8]|1[2-9]|2[04-8]|3[0-247]|4[037]|5[47]|6[02359]|7[0-59]|8[156])|8(?:0[1-68]|1[02-8]|2[0168]|3[0-2589]|4[03578]|5[046-9]|6[02-5]|7[028])|9(?:0[1346-9]|1[02-9]|2[0589]|3[0146-8]|4[01357-9]|5[12469]|7[0-389]|8[04-69]))[2-9]\d{6}")?;
+    let nanpa_text = "Call 6502530000 or 2125551234 or 9175559876. ".repeat(50);
+    run_benchmark(timer, all_results, "nanpa_findall", &nanpa_pat, &nanpa_text, 2, BenchType::FindAll);
+    run_benchmark(timer, all_results, "nanpa_search", &nanpa_pat, &nanpa_text, 50, BenchType::Search);
+    run_benchmark(timer, all_results, "nanpa_match_first", &nanpa_pat, "6502530000", 500, BenchType::IsMatch);
+
+    // ===-----------------------------------------------------------------------===
+    // Alternative Encoding Benchmarks (Latin-1, UTF-16)
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("encoding", "Alternative Encoding Benchmarks");
+
+    // The regex crate matches Latin-1 natively via bytes::Regex in non-Unicode
+    // mode; UTF-16 has no native path, so those benches include transcoding
+    // to UTF-8, which is what a user processing such input pays
+    let (european_text, latin1_text, utf16_text) = all_results.setup("encoded texts", || {
+        let european_text = encoding::make_european_text(100000);
+        let latin1_text = Arc::new(encoding::to_latin1(&european_text));
+        let utf16_text = Arc::new(encoding::to_utf16le(&european_text));
+        (european_text, latin1_text, utf16_text)
+    });
+
+    let surname_pat = Regex::new("Müller")?;
+    let surname_latin1_pat = regex::bytes::Regex::new(r"(?-u)M\xFCller")?;
+    let word_pat = Regex::new("[a-zA-ZÀ-ÿ]+")?;
+    let word_latin1_pat = regex::bytes::Regex::new(r"(?-u)[a-zA-Z\xC0-\xFF]+")?;
+
+    for (suffix, pat, latin1_pat, inner_iterations) in [
+        ("literal", &surname_pat, &surname_latin1_pat, 20),
+        ("word_class", &word_pat, &word_latin1_pat, 5),
+    ] {
+        run_benchmark(timer, all_results, &format!("encoding_utf8_{}", suffix), pat, &european_text, inner_iterations, BenchType::FindAll);
+        let (native_pat, latin1_text_ref) = (latin1_pat.clone(), latin1_text.clone());
+        run_op_benchmark(timer, all_results, &format!("encoding_latin1_native_{}", suffix), Some(latin1_pat.as_str()), latin1_text.len(), inner_iterations, move || {
+            black_box(native_pat.find_iter(black_box(&latin1_text_ref)).count());
+        });
+        let (transcode_pat, latin1_text_ref) = (pat.clone(), latin1_text.clone());
+        run_op_benchmark(timer, all_results, &format!("encoding_latin1_transcode_{}", suffix), Some(pat.as_str()), latin1_text.len(), inner_iterations, move || {
+            let decoded = encoding::from_latin1(black_box(&latin1_text_ref));
+            black_box(transcode_pat.find_iter(&decoded).count());
+        });
+        let (transcode_pat, utf16_text_ref) = (pat.clone(), utf16_text.clone());
+        run_op_benchmark(timer, all_results, &format!("encoding_utf16_transcode_{}", suffix), Some(pat.as_str()), utf16_text.len(), inner_iterations, move || {
+            let decoded = encoding::from_utf16le(black_box(&utf16_text_ref));
+            black_box(transcode_pat.find_iter(&decoded).count());
+        });
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Raw Byte Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("raw_bytes", "Raw Byte (non-UTF-8) Benchmarks");
+
+    // bytes::Regex scans haystacks no str can hold, with no decoding at all;
+    // the lossy benchmark is what going through the str API costs instead
+    let binary_log = all_results.setup("binary log", || encoding::make_binary_log(2000));
+    let error_bytes = regex::bytes::Regex::new(r"(?-u)level=ERROR")?;
+    let digits_bytes = regex::bytes::Regex::new(r"(?-u)[0-9]+")?;
+    let words_bytes = regex::bytes::Regex::new(r"(?-u)\w+")?;
+    // Runs of non-ASCII bytes, the invalid UTF-8 itself
+    let high_bytes = regex::bytes::Regex::new(r"(?-u)[\x80-\xFF]+")?;
+    run_bytes_benchmark(timer, all_results, "bytes_literal", &error_bytes, &binary_log, 100);
+    run_bytes_benchmark(timer, all_results, "bytes_digits", &digits_bytes, &binary_log, 20);
+    run_bytes_benchmark(timer, all_results, "bytes_word_class", &words_bytes, &binary_log, 20);
+    run_bytes_benchmark(timer, all_results, "bytes_high_bytes", &high_bytes, &binary_log, 20);
+    let lossy_digits = Regex::new(r"[0-9]+")?;
+    let (lossy_pat, haystack) = (lossy_digits.clone(), Arc::new(binary_log.clone()));
+    run_op_benchmark(timer, all_results, "bytes_lossy_decode_digits", Some(lossy_digits.as_str()), binary_log.len(), 20, move || {
+        let text = String::from_utf8_lossy(black_box(&haystack));
+        black_box(lossy_pat.find_iter(&text).count());
+    });
+    for (name, expected) in [("bytes_literal", 500), ("bytes_digits", 4000), ("bytes_word_class", 12000), ("bytes_high_bytes", 6000)] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Case-Insensitive Matching Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("case_insensitive", "Case-Insensitive Matching Benchmarks");
+
+    // The same words in mixed case on ASCII and Unicode text; case folding
+    // runs a different code path than any case-sensitive pattern
+    let ascii_mixed_case = "Say HELLO to the Apple and BANANA stand, hello again; mail John.Doe@Example.COM or \
+                            sales@shop.org. Orange, GRAPE, cherry. "
+        .repeat(500);
+    let unicode_mixed_case = "Grüße! HELLO aus München: Äpfel, APPLE und BANANA für die Straße 5 und STRAẞE 7, \
+                              nicht Strasse; İSTANBUL, Istanbul, ıstanbul. Mail JOHN.DOE@EXAMPLE.COM, merci chérie. "
+        .repeat(500);
+    let case_insensitive_patterns = [
+        ("ci_literal", Regex::new("(?i)hello")?),
+        ("ci_alternation", Regex::new("(?i)(apple|banana|cherry|grape|orange|mango)")?),
+        ("ci_email", Regex::new(r"(?i)[a-z0-9._%+-]+@[a-z0-9.-]+\.[a-z]{2,}")?),
+    ];
+    for (family, pattern) in &case_insensitive_patterns {
+        for (variant, text) in [("ascii", &ascii_mixed_case), ("unicode", &unicode_mixed_case)] {
+            let name = format!("{}_{}", family, variant);
+            run_benchmark(timer, all_results, &name, pattern, text, 10, BenchType::FindAll);
+            if let Some(result) = all_results.get_mut(&name) {
+                result.variant = Some(Variant {
+                    family: family.to_string(),
+                    name: variant.to_string(),
+                });
+            }
+        }
+    }
+    // Simple case folding, as the regex crate does it: ẞ folds to ß but
+    // neither to "ss", and İ and ı don't fold to i outside Turkish rules
+    let sharp_s = Regex::new("(?i)straße")?;
+    let dotted_i = Regex::new("(?i)istanbul")?;
+    run_benchmark(timer, all_results, "ci_sharp_s_folding", &sharp_s, &unicode_mixed_case, 10, BenchType::FindAll);
+    run_benchmark(timer, all_results, "ci_dotted_i_folding", &dotted_i, &unicode_mixed_case, 10, BenchType::FindAll);
+    all_results.expect_matches("ci_sharp_s_folding", 1000);
+    all_results.expect_matches("ci_dotted_i_folding", 500);
+
+    // ===-----------------------------------------------------------------------===
+    // Unicode Class Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("unicode_classes", "Unicode vs ASCII-only Class Benchmarks");
+
+    // The same classes with Unicode on (the regex crate's default) and off,
+    // on ASCII text where both find the same matches: the ASCII numbers are
+    // the fair comparison for an engine whose classes are ASCII-only
+    let class_text = "Order 66 shipped to user_42 on day 7 of 2024; tracking code ab12cd34 was sent twice. ".repeat(200);
+    for (family, pattern) in [("class_word", r"\w+"), ("class_digits", r"\d+"), ("class_lowercase", r"[a-z]+")] {
+        for (variant, unicode) in [("unicode", true), ("ascii", false)] {
+            let name = format!("{}_{}", family, variant);
+            all_results.built_with((!unicode).then_some(ASCII_ONLY));
+            let class_pat = build_regex(pattern, all_results.builder())?;
+            run_benchmark(timer, all_results, &name, &class_pat, &class_text, 10, BenchType::FindAll);
+            if let Some(result) = all_results.get_mut(&name) {
+                result.variant = Some(Variant {
+                    family: family.to_string(),
+                    name: variant.to_string(),
+                });
+            }
+        }
+    }
+    for (name, expected) in [
+        ("class_word_unicode", 3200),
+        ("class_word_ascii", 3200),
+        ("class_digits_unicode", 1200),
+        ("class_digits_ascii", 1200),
+        ("class_lowercase_unicode", 2800),
+        ("class_lowercase_ascii", 2800),
+    ] {
+        all_results.expect_matches(name, expected);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Parallel Corpus Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("corpus", "Parallel Corpus Benchmarks");
+
+    // Same matches embedded in English, accented European and CJK filler, so
+    // the per-language delta is the cost of scanning non-ASCII text
+    let corpus_patterns = [
+        ("corpus_literal", Regex::new("invoice")?),
+        ("corpus_digits", Regex::new(r"\d{8}")?),
+        ("corpus_email", Regex::new(r"[a-z]+@[a-z]+\.[a-z]+")?),
+        ("corpus_unicode_email", Regex::new(r"\b\w+@\w+\.\w+\b")?),
+    ];
+    let corpora: Vec<_> = all_results.setup("parallel corpora", || {
+        LANGUAGES
+            .iter()
+            .map(|&language| (language, corpus::parallel_text(language, 1000)))
+            .collect()
+    });
+    for (family, pattern) in &corpus_patterns {
+        for (language, text) in &corpora {
+            let name = format!("{}_{}", family, language.suffix());
+            run_benchmark(timer, all_results, &name, pattern, text, 10, BenchType::FindAll);
+            if let Some(result) = all_results.get_mut(&name) {
+                result.variant = Some(Variant {
+                    family: family.to_string(),
+                    name: language.suffix().to_string(),
+                });
+            }
+        }
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Pattern Compilation Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("compile", "Pattern Compilation Benchmarks");
+
+    // Regex::new alone, which match benchmarks compile ahead of timing; a
+    // short-lived script pays it on every run
+    let huge_alternation = (0..1000).map(|i| format!("term{:04}", i)).collect::<Vec<_>>().join("|");
+    let compile_patterns = [
+        ("compile_literal", "hello".to_string(), 1000),
+        ("compile_email", r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}".to_string(), 100),
+        ("compile_flexible_phone", r"\(?\d{3}\)?[\s.-]?\d{3}[\s.-]?\d{4}".to_string(), 100),
+        ("compile_timestamp_captures", r"(\d{4})-(\d{2})-(\d{2})T(\d{2}):(\d{2}):(\d{2})".to_string(), 100),
+        ("compile_alternation_1000", huge_alternation, 1),
+        ("compile_bounded_repetition", "(a|b){1,100}".to_string(), 5),
+    ];
+    for (name, pattern, inner_iterations) in &compile_patterns {
+        run_compile_benchmark(timer, all_results, name, pattern, *inner_iterations);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Hostile Pattern Compilation Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("compile_hostile", "Hostile Pattern Compilation Benchmarks");
+
+    // Time Regex::new on patterns that blow up compiled size or nesting depth;
+    // rejected patterns record the error, so compile-time guards can be tuned
+    // against how long the regex crate takes to reach the same verdict
+    let nested_quantifiers = |depth: usize| format!("{}a{}", "(".repeat(depth), ")*".repeat(depth));
+    let hostile_patterns = [
+        ("compile_hostile_counted_groups", r"((a{10}){10}){10}".to_string(), 10),
+        ("compile_hostile_counted_groups_too_big", r"(?:(?:a{100}){100}){100}".to_string(), 1),
+        ("compile_hostile_nested_plus", "((a+)+)+".to_string(), 100),
+        ("compile_hostile_alternation_repeat", "(a|aa|aaa){1000}".to_string(), 2),
+        ("compile_hostile_nesting_depth_50", nested_quantifiers(50), 20),
+        ("compile_hostile_nesting_depth_300", nested_quantifiers(300), 20),
+        ("compile_hostile_unicode_word_100", r"\w{100}".to_string(), 1),
+        ("compile_hostile_unicode_word_1000", r"\w{1000}".to_string(), 1),
+    ];
+    for (name, pattern, inner_iterations) in &hostile_patterns {
+        run_compile_benchmark(timer, all_results, name, pattern, *inner_iterations);
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Pathological Pattern Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("pathological", "Pathological (Catastrophic Backtracking) Pattern Benchmarks");
+    all_results.expects_no_match();
+    // A backtracker takes exponential time on these; the regex crate stays
+    // linear, so only engines that backtrack should ever reach the deadline
+    all_results.set_category_deadline(std::time::Duration::from_secs(10));
+
+    // Runs of "a" that almost match, ended by a character no pattern accepts
+    let pathological_patterns = [
+        ("redos_nested_plus", Regex::new(r"(a+)+$")?),
+        ("redos_overlap_alt", Regex::new(r"(a|a)*b")?),
+        ("redos_nested_star", Regex::new(r"(a*)*b")?),
+    ];
+    for (family, pattern) in &pathological_patterns {
+        for len in [16, 256, 4096, 65536] {
+            let name = format!("{}_{}", family, len);
+            let text = "a".repeat(len) + "!";
+            run_benchmark(timer, all_results, &name, pattern, &text, (65536 / len).max(10), BenchType::Search);
+            all_results.expect_matches(&name, 0);
+            if let Some(result) = all_results.get_mut(&name) {
+                result.variant = Some(Variant {
+                    family: family.to_string(),
+                    name: len.to_string(),
+                });
+            }
+        }
+    }
+
+    // ===-----------------------------------------------------------------------===
+    // Engine Overhead Benchmarks
+    // ===-----------------------------------------------------------------------===
+    all_results.begin_category("overhead", "Engine Overhead Benchmarks");
+
+    // An empty pattern matches immediately, so these time the binding rather
+    // than the matcher: per-call dispatch on a 1-byte haystack, then what
+    // handing the engine raw bytes costs through the str API (validation,
+    // or an owned copy as a subprocess or FFI shim makes) versus the bytes API
+    let empty_pat = Regex::new("")?;
+    let empty_bytes_pat = regex::bytes::Regex::new("")?;
+    run_benchmark(timer, all_results, "overhead_empty_is_match", &empty_pat, "a", 10000, BenchType::IsMatchBool);
+    run_benchmark(timer, all_results, "overhead_empty_find", &empty_pat, "a", 10000, BenchType::IsMatch);
+
+    let raw_bytes = Arc::new(encoding::make_european_text(10000).into_bytes());
+    let (bytes_pat, haystack) = (empty_bytes_pat.clone(), raw_bytes.clone());
+    run_op_benchmark(timer, all_results, "overhead_bytes_api", Some(""), raw_bytes.len(), 1000, move || {
+        black_box(bytes_pat.is_match(black_box(&haystack)));
+    });
+    let (str_pat, haystack) = (empty_pat.clone(), raw_bytes.clone());
+    run_op_benchmark(timer, all_results, "overhead_str_api_validated", Some(""), raw_bytes.len(), 1000, move || {
+        let text = std::str::from_utf8(black_box(&haystack)).expect("haystack is UTF-8");
+        black_box(str_pat.is_match(text));
+    });
+    let (str_pat, haystack) = (empty_pat.clone(), raw_bytes.clone());
+    run_op_benchmark(timer, all_results, "overhead_str_api_owned", Some(""), raw_bytes.len(), 1000, move || {
+        let text = String::from_utf8(black_box(haystack.to_vec())).expect("haystack is UTF-8");
+        black_box(str_pat.is_match(&text));
+    });
+
+    all_results.end_category();
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BenchType {
+    IsMatch,
+    IsMatchBool,
+    Search,
+    /// `shortest_match`: where some match ends, which can be found before
+    /// the leftmost-first match's end
+    ShortestMatch,
+    FindAll,
+    /// `find_iter` counted without collecting the matches, which leaves
+    /// the scan's cost without the allocation's
+    Count,
+    /// Every match with its capture groups extracted, as code pulling
+    /// fields out of matches does
+    Captures,
+    /// `replace_all`, as redaction does
+    Replace(Replacement),
+    /// Every piece between matches, as tokenizing does
+    Split,
+    /// At most this many pieces, the last holding the rest of the text
+    SplitN(usize),
+}
+
+/// What [`BenchType::Replace`] puts in place of each match
+#[derive(Debug, Clone, Copy)]
+enum Replacement {
+    /// Fixed text, with `$` taken literally
+    Literal(&'static str),
+    /// A `$1`-style template, expanded for each match
+    Template(&'static str),
+    /// Computed by a closure: the match with its first group masked
+    MaskFirstGroup,
+}
+
+impl BenchType {
+    /// Operation name recorded in results and definitions, spelled out
+    /// rather than taken from `Debug` so the serialized names stay fixed
+    fn operation(&self) -> &'static str {
+        match self {
+            BenchType::IsMatch => "IsMatch",
+            BenchType::IsMatchBool => "IsMatchBool",
+            BenchType::Search => "Search",
+            BenchType::ShortestMatch => "ShortestMatch",
+            BenchType::FindAll => "FindAll",
+            BenchType::Count => "Count",
+            BenchType::Captures => "Captures",
+            BenchType::Replace(Replacement::Literal(_)) => "Replace",
+            BenchType::Replace(Replacement::Template(_)) => "ReplaceTemplate",
+            BenchType::Replace(Replacement::MaskFirstGroup) => "ReplaceClosure",
+            BenchType::Split => "Split",
+            BenchType::SplitN(_) => "SplitN",
+        }
+    }
+
+    /// Perform the operation once, returning how many matches it reported
+    /// (0 or 1 for the single-match operations), if it counts them
+    fn run(&self, pattern: &Regex, text: &str) -> Option<usize> {
+        let count = match self {
+            // Use find() instead of is_match() to compute match boundaries,
+            // matching what Mojo's match_first returns (start + end positions)
+            BenchType::IsMatch => black_box(pattern.find(black_box(text))).is_some() as usize,
+            BenchType::IsMatchBool => black_box(pattern.is_match(black_box(text))) as usize,
+            BenchType::Search => black_box(pattern.find(black_box(text))).is_some() as usize,
+            BenchType::ShortestMatch => black_box(pattern.shortest_match(black_box(text))).is_some() as usize,
+            BenchType::FindAll => {
+                let matches: Vec<_> = pattern.find_iter(black_box(text)).collect();
+                black_box(matches.len())
+            }
+            BenchType::Count => black_box(pattern.find_iter(black_box(text)).count()),
+            BenchType::Captures => {
+                let mut count = 0;
+                for captures in pattern.captures_iter(black_box(text)) {
+                    for group in captures.iter() {
+                        black_box(group.map(|group| group.as_str()));
+                    }
+                    count += 1;
+                }
+                count
+            }
+            BenchType::Replace(Replacement::Literal(replacement)) => {
+                black_box(pattern.replace_all(black_box(text), NoExpand(replacement)));
+                return None;
+            }
+            BenchType::Replace(Replacement::Template(template)) => {
+                black_box(pattern.replace_all(black_box(text), *template));
+                return None;
+            }
+            BenchType::Replace(Replacement::MaskFirstGroup) => {
+                let mut count = 0;
+                let replaced = pattern.replace_all(black_box(text), |captures: &Captures| {
+                    count += 1;
+                    let whole = captures.get(0).expect("group 0 always participates");
+                    let Some(group) = captures.get(1) else {
+                        return whole.as_str().to_string();
+                    };
+                    let (start, end) = (group.start() - whole.start(), group.end() - whole.start());
+                    let whole = whole.as_str();
+                    format!("{}{}{}", &whole[..start], "*".repeat(group.as_str().chars().count()), &whole[end..])
+                });
+                black_box(replaced);
+                count
+            }
+            // A match between every two pieces
+            BenchType::Split => black_box(pattern.split(black_box(text)).count()).saturating_sub(1),
+            BenchType::SplitN(limit) => {
+                black_box(pattern.splitn(black_box(text), *limit).count());
+                return None;
+            }
+        };
+        Some(count)
+    }
+}
+
+struct CompiledPatterns {
+    hello: Regex,
+    dot_star: Regex,
+    a_star: Regex,
+    a_plus: Regex,
+    a_question: Regex,
+    a: Regex,
+    range_a_z: Regex,
+    range_0_9: Regex,
+    range_alnum: Regex,
+    negated_alnum: Regex,
+    multi_char_class: Regex,
+    anchor_start: Regex,
+    anchor_end: Regex,
+    alt_simple: Regex,
+    alt_words: Regex,
+    alt_common_prefix: Regex,
+    group_quantified: Regex,
+    group_alternation: Regex,
+    email: Regex,
+    number: Regex,
+    literal_prefix_short: Regex,
+    literal_prefix_medium: Regex,
+    literal_prefix_long: Regex,
+    required_literal: Regex,
+    simple_phone: Regex,
+    flexible_phone: Regex,
+    multi_format_phone: Regex,
+    phone_validation: Regex,
+    large_alternation: Regex,
+    deep_nested: Regex,
+    literal_heavy: Regex,
+    complex_group: Regex,
+    dfa_simple_phone: Regex,
+    dfa_paren_phone: Regex,
+    dfa_dot_phone: Regex,
+    dfa_digits_only: Regex,
+    national_phone_validation: Regex,
+    // Quantifier parser optimization patterns
+    single_quantifier_digits: Regex,
+    single_quantifier_alpha: Regex,
+    dual_quantifiers: Regex,
+    triple_quantifiers: Regex,
+    quad_quantifiers: Regex,
+    range_quantifiers: Regex,
+    mixed_range_quantifiers: Regex,
+    datetime_quantifiers: Regex,
+    flexible_datetime: Regex,
+    dense_quantifiers: Regex,
+    ultra_dense_quantifiers: Regex,
+    grouped_quantifiers: Regex,
+    alternation_quantifiers: Regex,
+    // Optimization showcase patterns
+    optimize_range_quantifier: Regex,
+    optimize_multiple_quantifiers: Regex,
+    optimize_phone_quantifiers: Regex,
+    optimize_large_quantifiers: Regex,
+    optimize_extreme_quantifiers: Regex,
+    // Predefined character classes
+    predefined_digits: Regex,
+    predefined_word: Regex,
+}
+
+fn make_phone_test_data(num_phones: usize) -> String {
+    let phone_patterns = [
+        "555-123-4567",
+        "(555) 123-4567",
+        "555.123.4567",
+        "5551234567",
+        "+1-555-123-4567",
+        "1-555-123-4568",
+        "(555)123-4569",
+        "555 123 4570"
+    ];
+    let filler_text = " Contact us at ";
+    let extra_text = " or email support@company.com for assistance. ";
+
+    let mut result = String::new();
+    for i in 0..num_phones {
+        result.push_str(filler_text);
+        let pattern_idx = i % phone_patterns.len();
+        result.push_str(phone_patterns[pattern_idx]);
+        result.push_str(extra_text);
+    }
+
+    result
+}
+
+fn make_complex_pattern_test_data(num_entries: usize) -> String {
+    // Generate test data for US national phone number validation
+    let complex_patterns = [
+        "305200123456",     // Matches first alternation
+        "505601234567",     // Matches first alternation
+        "274212345678",     // Matches second alternation
+        "305912345678",     // Matches second alternation
+        "212345672890",     // Matches third alternation
+        "312345672890",     // Matches third alternation
+        "412345672890",     // Matches third alternation
+        "512345672890",     // Matches third alternation
+        "1234567890",       // Should NOT match
+        "30520",            // Should NOT match (too short)
+    ];
+    let filler_text = " ID: ";
+    let extra_text = " Status: ACTIVE ";
+
+    let mut result = String::new();
+    for i in 0..num_entries {
+        result.push_str(filler_text);
+        let pattern_idx = i % complex_patterns.len();
+        result.push_str(complex_patterns[pattern_idx]);
+        result.push_str(extra_text);
+    }
+
+    result
+}
+
+fn create_all_patterns() -> Result<CompiledPatterns, Box<dyn std::error::Error>> {
+    Ok(CompiledPatterns {
+        hello: Regex::new("hello")?,
+        dot_star: Regex::new(".*")?,
+        a_star: Regex::new("a*")?,
+        a_plus: Regex::new("a+")?,
+        a_question: Regex::new("a?")?,
+        a: Regex::new("a")?,
+        range_a_z: Regex::new("[a-z]+")?,
+        range_0_9: Regex::new("[0-9]+")?,
+        range_alnum: Regex::new("[a-zA-Z0-9]+")?,
+        negated_alnum: Regex::new("[^a-zA-Z0-9]+")?,
+        multi_char_class: Regex::new("[a-z]+[0-9]+")?,
+        anchor_start: Regex::new("^abc")?,
+        anchor_end: Regex::new("xyz$")?,
+        alt_simple: Regex::new("a|b|c")?,
+        alt_words: Regex::new("abc|def|ghi")?,
+        alt_common_prefix: Regex::new("(hello|help|helicopter)")?,
+        group_quantified: Regex::new("(abc)+")?,
+        group_alternation: Regex::new("(a|b)*")?,
+        email: Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}")?,
+        number: Regex::new(r"\d+\.?\d*")?,
+        literal_prefix_short: Regex::new("hello.*world")?,
+        literal_prefix_medium: Regex::new("hello.*")?,
+        literal_prefix_long: Regex::new("hello.*")?,
+        required_literal: Regex::new(r".*@example\.com")?,
+        simple_phone: Regex::new(r"\d{3}-\d{3}-\d{4}")?,
+        flexible_phone: Regex::new(r"\(?\d{3}\)?[\s.-]?\d{3}[\s.-]?\d{4}")?,
+        multi_format_phone: Regex::new(r"\(?\d{3}\)?[\s.-]\d{3}[\s.-]\d{4}|\d{3}-\d{3}-\d{4}|\d{10}")?,
+        phone_validation: Regex::new(r"^\+?1?[\s.-]?\(?([2-9]\d{2})\)?[\s.-]?([2-9]\d{2})[\s.-]?(\d{4})$")?,
+        large_alternation: Regex::new(r"(apple|banana|cherry|date|elderberry|fig|grape|honey)")?,
+        deep_nested: Regex::new(r"(?:(?:(?:a|b)|(?:c|d))|(?:(?:e|f)|(?:g|h)))")?,
+        literal_heavy: Regex::new(r"(user123|admin456|guest789|root000|test111|demo222|sample333|client444)")?,
+        complex_group: Regex::new(r"(hello|world|test|demo|sample)[0-9]{3}[a-z]{2}")?,
+        dfa_simple_phone: Regex::new(r"[0-9]{3}-[0-9]{3}-[0-9]{4}")?,
+        dfa_paren_phone: Regex::new(r"\([0-9]{3}\) [0-9]{3}-[0-9]{4}")?,
+        dfa_dot_phone: Regex::new(r"[0-9]{3}\.[0-9]{3}\.[0-9]{4}")?,
+        dfa_digits_only: Regex::new(r"[0-9]{10}")?,
+        national_phone_validation: Regex::new(r"(?:3052(?:0[0-8]|[1-9]\d)|5056(?:[0-35-9]\d|4[0-68]))\d{4}|(?:2742|305[3-9]|472[247-9]|505[2-57-9]|983[2-47-9])\d{6}|(?:2(?:0[1-35-9]|1[02-9]|2[03-57-9]|3[1459]|4[08]|5[1-46]|6[0279]|7[0269]|8[13])|3(?:0[1-47-9]|1[02-9]|2[0135-79]|3[0-24679]|4[167]|5[0-2]|6[01349]|8[056])|4(?:0[124-9]|1[02-579]|2[3-5]|3[0245]|4[023578]|58|6[349]|7[0589]|8[04])|5(?:0[1-47-9]|1[0235-8]|20|3[0149]|4[01]|5[179]|6[1-47]|7[0-5]|8[0256])|6(?:0[1-35-9]|1[024-9]|2[03689]|3[016]|4[0156]|5[01679]|6[0-279]|78|8[0-29])|7(?:0[1-46-8]|1[2-9]|2[04-8]|3[0-247]|4[037]|5[47]|6[02359]|7[0-59]|8[156])|8(?:0[1-68]|1[02-8]|2[0168]|3[0-2589]|4[03578]|5[046-9]|6[02-5]|7[028])|9(?:0[1346-9]|1[02-9]|2[0589]|3[0146-8]|4[01357-9]|5[12469]|7[0-389]|8[04-69]))[2-9]\d{6}")?,
+        // Quantifier parser optimization patterns
+        single_quantifier_digits: Regex::new(r"[0-9]{4}")?,
+        single_quantifier_alpha: Regex::new(r"[A-Z]{3}")?,
+        dual_quantifiers: Regex::new(r"[A-Z]{3}[0-9]{4}")?,
+        triple_quantifiers: Regex::new(r"[A-Z]{3}[0-9]{4}-[A-Z]{3}[0-9]{3}")?,
+        quad_quantifiers: Regex::new(r"[A-Z]{3}[0-9]{4}-[A-Z]{3}[0-9]{3}-[A-Z]{3}[0-9]{3}")?,
+        range_quantifiers: Regex::new(r"[A-Z]{2,4}[0-9]{3,5}")?,
+        mixed_range_quantifiers: Regex::new(r"[A-Z]{1,3}-[0-9]{2,4}-[A-Z]{2,3}[0-9]{3,4}")?,
+        datetime_quantifiers: Regex::new(r"[0-9]{4}-[0-9]{2}-[0-9]{2} [0-9]{2}:[0-9]{2}:[0-9]{2}\.[0-9]{3}")?,
+        flexible_datetime: Regex::new(r"[0-9]{4}-[0-9]{1,2}-[0-9]{1,2} [0-9]{1,2}:[0-9]{2}:[0-9]{2}")?,
+        dense_quantifiers: Regex::new(r"[A-Z]{2}[0-9]{5}-[A-Z]{4}[0-9]{3}-[A-Z]{3}[0-9]{3}-[A-Z]{2}[0-9]{3}")?,
+        ultra_dense_quantifiers: Regex::new(r"[A-Z]{1,2}[0-9]{3,5}-[A-Z]{2,4}[0-9]{2,4}-[A-Z]{1,3}[0-9]{2,4}-[A-Z]{2,3}[0-9]{2,3}")?,
+        grouped_quantifiers: Regex::new(r"([A-Z]{3}[0-9]{4})-([A-Z]{3}[0-9]{3})")?,
+        alternation_quantifiers: Regex::new(r"([A-Z]{2,3}[0-9]{3,4})|([0-9]{4}-[A-Z]{3})")?,
+        // Optimization showcase patterns
+        optimize_range_quantifier: Regex::new(r"a{2,4}")?,
+        optimize_multiple_quantifiers: Regex::new(r"[A-Z]{3}[0-9]{4}-[A-Z]{3}[0-9]{3}-[A-Z]{2}[0-9]{2}")?,
+        optimize_phone_quantifiers: Regex::new(r"[0-9]{3}-[0-9]{3}-[0-9]{4}")?,
+        optimize_large_quantifiers: Regex::new(r"[A-Z]{10,20}[0-9]{15,25}")?,
+        optimize_extreme_quantifiers: Regex::new(r"a{1}b{2}c{3}d{4}e{5}f{6}g{7}h{8}")?,
+        // Predefined character classes
+        predefined_digits: Regex::new(r"\d+")?,
+        predefined_word: Regex::new(r"\w+")?,
+    })
+}
+
+/// Time `op` batched `inner_iterations` times per closure call and return the
+/// per-operation result, adding a single-call latency distribution in
+/// latency mode
+fn measure<F: Fn() -> Option<usize>>(timer: &Timer, inner_iterations: usize, op: F) -> BenchmarkResult {
+    // Smallest and largest match count any operation reported, so one wrong
+    // count among the batched operations still shows up
+    let mut counts: Option<(usize, usize)> = None;
+    let mut result = timer
+        .bench_function(|| {
+            for _ in 0..inner_iterations {
+                if let Some(count) = op() {
+                    counts = Some(counts.map_or((count, count), |(min, max)| (min.min(count), max.max(count))));
+                }
+            }
+        })
+        .per_op(inner_iterations);
+    if let Some((min, max)) = counts {
+        result.observed_matches = Some(min);
+        result.correct = Some(min == max);
+    }
+    if timer.latency_mode() {
+        result.latency = Some(timer.bench_latency(|| {
+            op();
+        }));
+    }
+    // Counted after timing, so lazily built caches are already in place
+    result.allocations = memory::allocations_during(|| {
+        for _ in 0..inner_iterations {
+            op();
+        }
+    })
+    .map(|count| count as f64 / inner_iterations as f64);
+    result
+}
+
+/// One-line summary of a regex compile error
+fn describe_regex_error(error: &regex::Error) -> String {
+    match error {
+        regex::Error::CompiledTooBig(limit) => format!("compiled size exceeds {} bytes", limit),
+        regex::Error::Syntax(message) => message
+            .lines()
+            .rev()
+            .find(|line| line.starts_with("error:"))
+            .map_or_else(|| message.clone(), |line| line.trim_start_matches("error: ").to_string()),
+        other => other.to_string(),
+    }
+}
+
+/// Record `name` as skipped once the suite time budget is exhausted
+fn skip_if_over_budget(results: &mut ResultCollector, name: &str) -> bool {
+    if !results.budget_exhausted() {
+        return false;
+    }
+    results.insert(name, BenchmarkResult::skipped());
+    println!("- {} (skipped: time budget exhausted)", name);
+    true
+}
+
+fn run_benchmark(
+    timer: &'static Timer,
+    results: &mut ResultCollector,
+    name: &str,
+    pattern: &Regex,
+    text: &str,
+    inner_iterations: usize,
+    bench_type: BenchType,
+) {
+    if results.is_dry_run() {
+        let count = pattern.find_iter(text).count();
+        results.define(
+            name,
+            Some(pattern.as_str()),
+            bench_type.operation(),
+            text.len(),
+            inner_iterations,
+            Some(text),
+            Some(count),
+        );
+        return;
+    }
+
+    // Benchmarks run on watchdog threads, so the closures own their inputs
+    let text: Arc<str> = Arc::from(text);
+    let cold_text = text.clone();
+    let cold = measure_cold(timer, results, pattern, move |fresh| {
+        bench_type.run(fresh, &cold_text);
+    });
+    let warm_pattern = pattern.clone();
+    run_counted_benchmark(timer, results, name, bench_type.operation(), Some(pattern.as_str()), text.len(), inner_iterations, move || {
+        bench_type.run(&warm_pattern, &text)
+    });
+    record_phases(results, name, cold);
+}
+
+fn run_sub_benchmark(
+    timer: &'static Timer,
+    results: &mut ResultCollector,
+    name: &str,
+    pattern: &Regex,
+    repl: &str,
+    text: &str,
+    inner_iterations: usize,
+) {
+    if results.is_dry_run() {
+        let count = pattern.find_iter(text).count();
+        results.define(name, Some(pattern.as_str()), "Sub", text.len(), inner_iterations, Some(text), Some(count));
+        return;
+    }
+
+    let text_len = text.len();
+    let text: Arc<str> = Arc::from(text);
+    let repl: Arc<str> = Arc::from(repl);
+    let replace = move |pattern: &Regex| {
+        let replaced = pattern.replace_all(black_box(&text), &*repl);
+        black_box(&replaced);
+    };
+    let cold_replace = replace.clone();
+    let cold = measure_cold(timer, results, pattern, move |fresh| cold_replace(fresh));
+    let warm_pattern = pattern.clone();
+    run_counted_benchmark(timer, results, name, "Sub", Some(pattern.as_str()), text_len, inner_iterations, move || {
+        replace(&warm_pattern);
+        None
+    });
+    record_phases(results, name, cold);
+}
+
+/// Time compiling a fresh copy of `pattern`, then one call of `op` on it
+/// while its lazy DFA cache is still empty, with the timer's clock,
+/// returning `(compile_ns, first_ns)`; `None` past the budget or the
+/// benchmark deadline
+fn measure_cold<F>(timer: &'static Timer, results: &ResultCollector, pattern: &Regex, op: F) -> Option<(f64, f64)>
+where
+    F: FnOnce(&Regex) + Send + 'static,
+{
+    if results.budget_exhausted() {
+        return None;
+    }
+    let source = pattern.as_str().to_string();
+    let builder = results.builder().map(str::to_string);
+    with_deadline(results.benchmark_deadline(), move || {
+        let clock = timer.clock();
+        let start = clock.now();
+        let fresh = build_regex(&source, builder.as_deref()).ok()?;
+        let compiled = clock.now();
+        op(&fresh);
+        let end = clock.now();
+        Some((compiled.saturating_sub(start) as f64, end.saturating_sub(compiled) as f64))
+    })
+    .flatten()
+}
+
+/// Record the cold phases measured for `name`, once its steady state was
+fn record_phases(results: &mut ResultCollector, name: &str, cold: Option<(f64, f64)>) {
+    let Some((compile_ns, first_ns)) = cold else {
+        return;
+    };
+    if let Some(result) = results.get_mut(name).filter(|result| result.is_measured()) {
+        result.cold_time_ns = Some(first_ns);
+        result.phases = Some(Phases { compile_ns, first_ns });
+    }
+}
+
+/// Benchmark `scan`, which runs `pattern` over `text` in its own way and
+/// returns how many matches it found, as `operation`
+#[allow(clippy::too_many_arguments)]
+fn run_scan_benchmark<F: Fn(&str) -> usize + Send + 'static>(
+    timer: &'static Timer,
+    results: &mut ResultCollector,
+    name: &str,
+    operation: &str,
+    pattern: &str,
+    text: &str,
+    inner_iterations: usize,
+    scan: F,
+) {
+    if results.is_dry_run() {
+        let count = scan(text);
+        results.define(name, Some(pattern), operation, text.len(), inner_iterations, Some(text), Some(count));
+        return;
+    }
+
+    let text: Arc<str> = Arc::from(text);
+    run_counted_benchmark(timer, results, name, operation, Some(pattern), text.len(), inner_iterations, move || {
+        Some(black_box(scan(black_box(&text))))
+    });
+}
+
+/// Occurrences of `dfa`'s pattern in `text`, overlapping ones included, by
+/// where each ends
+#[cfg(feature = "overlapping")]
+fn count_overlapping(dfa: &hybrid::dfa::DFA, cache: &mut hybrid::dfa::Cache, text: &str) -> usize {
+    let input = Input::new(text);
+    let mut state = OverlappingState::start();
+    let mut count = 0;
+    loop {
+        dfa.try_search_overlapping_fwd(cache, &input, &mut state).expect("lazy DFA never gives up by default");
+        if state.get_match().is_none() {
+            return count;
+        }
+        count += 1;
+    }
+}
+
+/// Non-overlapping matches in `text` under leftmost-longest semantics,
+/// which regex-automata has no search for: every match starts where
+/// `first` (leftmost-first) finds one, as the leftmost start is the same
+/// under both, and ends where `longest` (`MatchKind::All`), anchored there,
+/// reports the last possible end
+fn count_leftmost_longest(first: &meta::Regex, longest: &meta::Regex, text: &str) -> usize {
+    let (mut pos, mut count) = (0, 0);
+    while let Some(start) = first.search(&Input::new(text).range(pos..)).map(|m| m.start()) {
+        let end = longest
+            .search(&Input::new(text).range(start..).anchored(Anchored::Yes))
+            .map_or(start, |m| m.end());
+        count += 1;
+        pos = end;
+        if end == start {
+            // Step past an empty match to the next character
+            match text[end..].chars().next() {
+                Some(c) => pos += c.len_utf8(),
+                None => break,
+            }
+        }
+    }
+    count
+}
+
+/// [`count_overlapping`] over `text` read `chunk_len` bytes at a time into a
+/// reused buffer, as from a socket or pipe: the DFA state carries over each
+/// chunk boundary, so matches spanning two chunks are still found
+#[cfg(feature = "streaming")]
+fn count_overlapping_streamed(
+    dfa: &hybrid::dfa::DFA,
+    cache: &mut hybrid::dfa::Cache,
+    text: &str,
+    chunk_len: usize,
+) -> usize {
+    use std::io::Read;
+    const GAVE_UP: &str = "lazy DFA never gives up by default";
+    let mut reader = text.as_bytes();
+    let mut chunk = vec![0; chunk_len];
+    let mut state = dfa.start_state_forward(cache, &Input::new("")).expect(GAVE_UP);
+    let mut count = 0;
+    loop {
+        let len = reader.read(&mut chunk).expect("reading a slice can't fail");
+        if len == 0 {
+            break;
+        }
+        for &byte in &chunk[..len] {
+            state = dfa.next_state(cache, state, byte).expect(GAVE_UP);
+            // Matches are reported one byte late, on the next transition
+            count += state.is_match() as usize;
+        }
+    }
+    count + dfa.next_eoi_state(cache, state).expect(GAVE_UP).is_match() as usize
+}
+
+/// [`run_benchmark`]'s `FindAll` for a `regex::bytes::Regex` on a haystack
+/// that may not be UTF-8
+fn run_bytes_benchmark(
+    timer: &'static Timer,
+    results: &mut ResultCollector,
+    name: &str,
+    pattern: &regex::bytes::Regex,
+    haystack: &[u8],
+    inner_iterations: usize,
+) {
+    let find_all = |pattern: &regex::bytes::Regex, haystack: &[u8]| {
+        let matches: Vec<_> = pattern.find_iter(black_box(haystack)).collect();
+        black_box(matches.len())
+    };
+    if results.is_dry_run() {
+        let count = find_all(pattern, haystack);
+        results.define(name, Some(pattern.as_str()), "FindAll", haystack.len(), inner_iterations, None, Some(count));
+        return;
+    }
+
+    let haystack: Arc<[u8]> = Arc::from(haystack);
+    let pattern = pattern.clone();
+    let source = pattern.as_str().to_string();
+    run_counted_benchmark(timer, results, name, "FindAll", Some(&source), haystack.len(), inner_iterations, move || {
+        Some(find_all(&pattern, &haystack))
+    });
+}
+
+/// [`run_benchmark`]'s `FindAll` for a fancy-regex pattern, which
+/// [`BenchType`] can't run
+#[cfg(feature = "fancy")]
+fn run_fancy_benchmark(
+    timer: &'static Timer,
+    results: &mut ResultCollector,
+    name: &str,
+    pattern: &fancy_regex::Regex,
+    text: &str,
+    inner_iterations: usize,
+) {
+    // Matches past a backtracking-limit error aren't counted
+    let find_all = |pattern: &fancy_regex::Regex, text: &str| {
+        let matches: Vec<_> = pattern.find_iter(black_box(text)).map_while(Result::ok).collect();
+        black_box(matches.len())
+    };
+    if results.is_dry_run() {
+        let count = find_all(pattern, text);
+        results.define(name, Some(pattern.as_str()), "FindAll", text.len(), inner_iterations, Some(text), Some(count));
+        return;
+    }
+
+    let text: Arc<str> = Arc::from(text);
+    let pattern = pattern.clone();
+    let source = pattern.as_str().to_string();
+    run_counted_benchmark(timer, results, name, "FindAll", Some(&source), text.len(), inner_iterations, move || {
+        Some(find_all(&pattern, &text))
+    });
+}
+
+/// Benchmark `Regex::new(pattern)`, as operation `Compile`; a rejected
+/// pattern records the error
+fn run_compile_benchmark(
+    timer: &'static Timer,
+    results: &mut ResultCollector,
+    name: &str,
+    pattern: &str,
+    inner_iterations: usize,
+) {
+    if results.is_dry_run() {
+        results.define(name, Some(pattern), "Compile", 0, inner_iterations, None, None);
+        return;
+    }
+
+    let source = pattern.to_string();
+    run_counted_benchmark(timer, results, name, "Compile", Some(pattern), 0, inner_iterations, move || {
+        let _ = black_box(Regex::new(black_box(&source)));
+        None
+    });
+    if let Some(result) = results.get_mut(name) {
+        result.compile_error = Regex::new(pattern).err().map(|e| describe_regex_error(&e));
+    }
+}
+
+/// Benchmark an arbitrary operation over a haystack of `haystack_len` bytes,
+/// for workloads that don't fit a `BenchType` on a `regex::Regex`; `pattern`
+/// is the driving pattern, if any, used for strategy introspection. The
+/// benchmark runs under the collector's per-benchmark deadline and is
+/// recorded as timed out if it overruns.
+fn run_op_benchmark<F: Fn() + Send + 'static>(
+    timer: &'static Timer,
+    results: &mut ResultCollector,
+    name: &str,
+    pattern: Option<&str>,
+    haystack_len: usize,
+    inner_iterations: usize,
+    op: F,
+) {
+    if results.is_dry_run() {
+        results.define(name, pattern, "Custom", haystack_len, inner_iterations, None, None);
+        return;
+    }
+    run_counted_benchmark(timer, results, name, "Custom", pattern, haystack_len, inner_iterations, move || {
+        op();
+        None
+    });
+}
+
+/// [`run_op_benchmark`] for an operation that returns how many matches it
+/// found, which must stay the same across every timed sample. `operation`,
+/// the pattern and the sizes are recorded with the result, so results of
+/// different engines can be checked to have run the same inputs.
+#[allow(clippy::too_many_arguments)]
+fn run_counted_benchmark<F: Fn() -> Option<usize> + Send + 'static>(
+    timer: &'static Timer,
+    results: &mut ResultCollector,
+    name: &str,
+    operation: &str,
+    pattern: Option<&str>,
+    haystack_len: usize,
+    inner_iterations: usize,
+    op: F,
+) {
+    if skip_if_over_budget(results, name) {
+        return;
+    }
+
+    let deadline = results.benchmark_deadline();
+    let rss_reset = memory::reset_peak_rss();
+    let Some(result) = with_deadline(deadline, move || measure(timer, inner_iterations, op)) else {
+        results.insert(name, BenchmarkResult::timed_out());
+        println!(
+            "✗ {} (timed out after {:.0?}; left running, later timings may be skewed)",
+            name,
+            deadline.unwrap_or_default()
+        );
+        return;
+    };
+    let mut result = result.with_haystack_len(haystack_len);
+    result.peak_rss_bytes = memory::peak_rss_bytes().filter(|_| rss_reset);
+    result.engine = pattern.and_then(|pattern| strategy::describe_with(pattern, is_unicode(results.builder())));
+    result.pattern = pattern.filter(|pattern| !pattern.is_empty()).map(str::to_string);
+    result.haystack_len = Some(haystack_len).filter(|&len| len > 0);
+    result.operation = Some(operation.to_string());
+    result.inner_iterations = Some(inner_iterations);
+
+    let inconsistent = result.correct == Some(false);
+    results.insert(name, result);
+    if inconsistent {
+        println!("⚠ {} (match count varied between samples)", name);
+    } else {
+        println!("✓ {}", name);
+    }
+}
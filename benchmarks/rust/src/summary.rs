@@ -0,0 +1,292 @@
+//! The results table printed after a run: rows grouped by category (or
+//! family) with a geomean subtotal per group, sorted by registration, time,
+//! throughput or name, and times in whichever of ns, µs, ms or s reads best.
+//! Against a baseline, each row also gets its (colored) change.
+
+use crate::compare::{self, DEFAULT_ALPHA};
+use crate::report::ordered_categories;
+use crate::{family, stats, Aggregates, BenchmarkResult, BenchmarkResults};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Width of the name column; longer names widen their row
+const NAME_WIDTH: usize = 30;
+
+/// Width of the baseline delta column
+const DELTA_WIDTH: usize = 8;
+
+/// Regressions printed in bold
+const TOP_REGRESSIONS: usize = 3;
+
+/// Deltas smaller than this (percent) are left uncolored as noise
+const NEUTRAL_DELTA_PCT: f64 = 1.0;
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const BOLD: &str = "\x1b[1m";
+const BOLD_RED: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+/// Format a duration in nanoseconds with the largest unit that keeps it at
+/// or above 1, e.g. `281.0 ns`, `2.81 µs`, `7.19 ms`
+pub fn format_ns(ns: f64) -> String {
+    let abs = ns.abs();
+    if abs < 1e3 {
+        format!("{:.1} ns", ns)
+    } else if abs < 1e6 {
+        format!("{:.2} µs", ns / 1e3)
+    } else if abs < 1e9 {
+        format!("{:.2} ms", ns / 1e6)
+    } else {
+        format!("{:.2} s", ns / 1e9)
+    }
+}
+
+/// Format a reading in `unit`, scaling only nanoseconds
+fn format_value(value: f64, unit: &str) -> String {
+    if unit == "ns" {
+        format_ns(value)
+    } else {
+        format!("{:.1} {}", value, unit)
+    }
+}
+
+/// Unit of the recorded times, from the `clock` field (`wall (ns)`)
+fn clock_unit(results: &BenchmarkResults) -> &str {
+    results
+        .clock
+        .as_deref()
+        .and_then(|clock| clock.rsplit_once(" ("))
+        .map_or("ns", |(_, unit)| unit.trim_end_matches(')'))
+}
+
+/// Wrap an already padded cell in ANSI `codes` (padding first keeps the
+/// escape sequences out of the column width)
+fn paint(cell: String, codes: &str, color: bool) -> String {
+    if color && !codes.is_empty() {
+        format!("{}{}{}", codes, cell, RESET)
+    } else {
+        cell
+    }
+}
+
+/// `cells` are name, time, throughput, matches and iterations; `delta` is
+/// the padded baseline column, when there's a baseline
+fn row(out: &mut String, cells: [&str; 5], delta: Option<&str>) {
+    let [name, time, throughput, matches, iterations] = cells;
+    let _ = write!(
+        out,
+        "| {:<width$} | {:>12} | {:>9} | {:>8} | {:>8} |",
+        name,
+        time,
+        throughput,
+        matches,
+        iterations,
+        width = NAME_WIDTH
+    );
+    match delta {
+        Some(delta) => {
+            let _ = writeln!(out, " {} |", delta);
+        }
+        None => {
+            let _ = writeln!(out);
+        }
+    }
+}
+
+/// Each benchmark's change against the baseline, for the extra column
+struct Deltas {
+    by_name: HashMap<String, f64>,
+    /// The largest regressions, printed in bold
+    top: Vec<String>,
+    color: bool,
+}
+
+impl Deltas {
+    fn new(baseline: &BenchmarkResults, results: &BenchmarkResults, color: bool) -> Self {
+        let comparisons = compare::compare(baseline, results, DEFAULT_ALPHA);
+        let mut regressions: Vec<_> = comparisons.iter().filter(|c| c.delta_pct >= NEUTRAL_DELTA_PCT).collect();
+        regressions.sort_by(|a, b| b.delta_pct.total_cmp(&a.delta_pct));
+        Deltas {
+            top: regressions.iter().take(TOP_REGRESSIONS).map(|c| c.name.clone()).collect(),
+            by_name: comparisons.iter().map(|c| (c.name.clone(), c.delta_pct)).collect(),
+            color,
+        }
+    }
+
+    /// Padded (and colored) delta cell of `name`
+    fn cell(&self, name: &str) -> String {
+        let Some(&delta) = self.by_name.get(name) else {
+            return format!("{:>width$}", "-", width = DELTA_WIDTH);
+        };
+        let cell = format!("{:>+width$.1}%", delta, width = DELTA_WIDTH - 1);
+        let codes = if self.is_top(name) {
+            BOLD_RED
+        } else if delta >= NEUTRAL_DELTA_PCT {
+            RED
+        } else if delta <= -NEUTRAL_DELTA_PCT {
+            GREEN
+        } else {
+            ""
+        };
+        paint(cell, codes, self.color)
+    }
+
+    fn is_top(&self, name: &str) -> bool {
+        self.top.iter().any(|top| top == name)
+    }
+}
+
+fn benchmark_row(
+    out: &mut String,
+    name: &str,
+    result: &BenchmarkResult,
+    unit: &str,
+    indent: bool,
+    deltas: Option<&Deltas>,
+) {
+    let indent = if indent { "  " } else { "" };
+    let mut label = format!("{:<width$}", format!("{}{}", indent, name), width = NAME_WIDTH);
+    if let Some(deltas) = deltas.filter(|deltas| deltas.is_top(name)) {
+        label = paint(label, BOLD, deltas.color);
+    }
+    let delta = deltas.map(|deltas| deltas.cell(name));
+    if !result.is_measured() {
+        let status = if result.skipped { "skipped" } else { "timed out" };
+        row(out, [&label, status, "-", "-", "-"], delta.as_deref());
+        return;
+    }
+    row(
+        out,
+        [
+            &label,
+            &format_value(result.time_ns, unit),
+            &result.throughput_mb_s.map_or("-".to_string(), |mb_s| format!("{:.1}", mb_s)),
+            &result.observed_matches.map_or("-".to_string(), |count| count.to_string()),
+            &result.iterations.to_string(),
+        ],
+        delta.as_deref(),
+    );
+}
+
+/// Order of the rows within each group
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// Registration order, i.e. by category
+    #[default]
+    Category,
+    /// Slowest first
+    Time,
+    /// Lowest throughput first, unknown last
+    Throughput,
+    /// Alphabetical
+    Name,
+}
+
+/// What the rows are grouped (and subtotalled) by
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    #[default]
+    Category,
+    /// See [`family`]
+    Family,
+    /// One flat list
+    None,
+}
+
+/// How [`table`] lays out the rows
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TableOptions {
+    pub sort: SortKey,
+    pub group: Grouping,
+    /// Color the baseline deltas with ANSI escapes
+    pub color: bool,
+}
+
+type Row<'a> = (&'a str, &'a BenchmarkResult);
+
+/// Compare two optional values ascending, with `None` last
+fn known_first(a: Option<f64>, b: Option<f64>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.total_cmp(&b),
+        (a, b) => b.is_some().cmp(&a.is_some()),
+    }
+}
+
+fn sort_rows(rows: &mut [Row<'_>], key: SortKey) {
+    // Unmeasured benchmarks go last whatever the key
+    let slowness = |result: &BenchmarkResult| result.is_measured().then_some(-result.time_ns);
+    match key {
+        SortKey::Category => {}
+        SortKey::Time => rows.sort_by(|a, b| known_first(slowness(a.1), slowness(b.1))),
+        SortKey::Throughput => rows.sort_by(|a, b| known_first(a.1.throughput_mb_s, b.1.throughput_mb_s)),
+        SortKey::Name => rows.sort_by_key(|(name, _)| *name),
+    }
+}
+
+/// Render the table: a header row per group, its benchmarks with their
+/// median time, throughput (MB/s), match count and sample count, then the
+/// group's geomean, and the overall geomean at the end. Groups are in the
+/// order their first benchmark was registered. Given a `baseline`, a last
+/// column has each benchmark's change against it, green or red past 1%,
+/// with the top regressions in bold.
+pub fn table(results: &BenchmarkResults, options: &TableOptions, baseline: Option<&BenchmarkResults>) -> String {
+    let unit = clock_unit(results);
+    let deltas = baseline.map(|baseline| Deltas::new(baseline, results, options.color));
+    let deltas = deltas.as_ref();
+    // Group and geomean rows leave the delta column empty
+    let blank = deltas.map(|_| " ".repeat(DELTA_WIDTH));
+    let blank = blank.as_deref();
+    let mut groups: Vec<(&str, Vec<Row<'_>>)> = Vec::new();
+    for (category, benches) in ordered_categories(results) {
+        for (name, result) in benches {
+            let group = match options.group {
+                Grouping::Category => category.as_str(),
+                Grouping::Family => family(category, name, result),
+                Grouping::None => "",
+            };
+            match groups.iter_mut().find(|(existing, _)| *existing == group) {
+                Some((_, rows)) => rows.push((name, result)),
+                None => groups.push((group, vec![(name, result)])),
+            }
+        }
+    }
+
+    let mut out = String::new();
+    let header = deltas.map(|_| format!("{:>width$}", "vs base", width = DELTA_WIDTH));
+    row(&mut out, ["name", "time", "MB/s", "matches", "iters"], header.as_deref());
+    let dashes = |n| "-".repeat(n);
+    let _ = write!(
+        out,
+        "|{}|{}:|{}:|{}:|{}:|",
+        dashes(NAME_WIDTH + 2),
+        dashes(13),
+        dashes(10),
+        dashes(9),
+        dashes(9)
+    );
+    if deltas.is_some() {
+        let _ = write!(out, "{}:|", dashes(DELTA_WIDTH + 1));
+    }
+    let _ = writeln!(out);
+    let grouped = options.group != Grouping::None;
+    for (group, mut rows) in groups {
+        sort_rows(&mut rows, options.sort);
+        if grouped {
+            row(&mut out, [group, "", "", "", ""], blank);
+        }
+        for (name, result) in &rows {
+            benchmark_row(&mut out, name, result, unit, grouped, deltas);
+        }
+        let measured = rows.iter().filter(|(_, result)| result.is_measured()).map(|(_, result)| result.time_ns);
+        if let Some(geomean) = stats::geomean(measured).filter(|_| grouped) {
+            let label = format!("  {} geomean", group);
+            row(&mut out, [&label, &format_value(geomean, unit), "", "", ""], blank);
+        }
+    }
+    if let Some(aggregates) = Aggregates::from_results(&results.results) {
+        row(&mut out, ["overall geomean", &format_value(aggregates.geomean_ns, unit), "", "", ""], blank);
+    }
+    out
+}
@@ -0,0 +1,121 @@
+//! One benchmark's timings across historical runs, from either history
+//!
+//! Pairwise comparison only sees the step between two runs, so a benchmark
+//! that creeps up 2% a week never trips a regression threshold. Fitting a
+//! line through the last N runs shows that drift directly.
+
+use crate::history::HistoryRecord;
+use chrono::DateTime;
+
+/// Recorded in place of the sha for runs outside a git checkout
+pub(crate) const UNKNOWN_SHA: &str = "unknown";
+
+/// Bars of a sparkline, lowest to highest
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+const SECONDS_PER_WEEK: f64 = 7.0 * 24.0 * 3600.0;
+
+/// Shortest span of runs a weekly drift is extrapolated from; minutes of
+/// back-to-back runs say nothing about a week
+const MIN_WEEKLY_SPAN_SECS: f64 = 24.0 * 3600.0;
+
+/// One benchmark's result at one commit
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendPoint {
+    pub git_sha: String,
+    pub engine: String,
+    pub timestamp: String,
+    pub time_ns: f64,
+    pub throughput_mb_s: Option<f64>,
+}
+
+/// Linear drift of a benchmark's time, relative to its mean
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drift {
+    /// Change per run, in percent
+    pub pct_per_run: f64,
+    /// Change per week of wall-clock time, in percent; `None` unless every
+    /// timestamp parses and they span at least a day
+    pub pct_per_week: Option<f64>,
+}
+
+/// The latest `limit` measured results of `benchmark` in a JSON Lines
+/// history, optionally for one engine only, oldest first
+pub fn from_history(records: &[HistoryRecord], benchmark: &str, engine: Option<&str>, limit: usize) -> Vec<TrendPoint> {
+    let mut points: Vec<TrendPoint> = records
+        .iter()
+        .filter(|record| engine.is_none_or(|engine| record.results.engine == engine))
+        .filter_map(|record| {
+            let result = record.results.results.values().find_map(|benches| benches.get(benchmark))?;
+            result.is_measured().then(|| TrendPoint {
+                git_sha: record.git_sha.clone().unwrap_or_else(|| UNKNOWN_SHA.to_string()),
+                engine: record.results.engine.clone(),
+                timestamp: record.results.timestamp.clone(),
+                time_ns: result.time_ns,
+                throughput_mb_s: result.throughput_mb_s,
+            })
+        })
+        .collect();
+    points.drain(..points.len().saturating_sub(limit));
+    points
+}
+
+/// Indices of the points slower than the point before by more than
+/// `max_pct` percent
+pub fn step_regressions(points: &[TrendPoint], max_pct: f64) -> Vec<usize> {
+    (1..points.len())
+        .filter(|&i| points[i - 1].time_ns > 0.0 && (points[i].time_ns / points[i - 1].time_ns - 1.0) * 100.0 > max_pct)
+        .collect()
+}
+
+/// One bar per value, scaled between the smallest and the largest
+pub fn sparkline(values: &[f64]) -> String {
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|&value| {
+            if max > min {
+                let level = (value - min) / (max - min) * (SPARKS.len() - 1) as f64;
+                SPARKS[level.round() as usize]
+            } else {
+                SPARKS[SPARKS.len() / 2]
+            }
+        })
+        .collect()
+}
+
+/// Least-squares slope of `ys` over `xs`, `None` if the xs don't vary
+fn slope(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let covariance: f64 = xs.iter().zip(ys).map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    let variance: f64 = xs.iter().map(|x| (x - mean_x).powi(2)).sum();
+    (variance > 0.0).then(|| covariance / variance)
+}
+
+/// Drift of `points`' times, from a line fitted by run index and another
+/// by timestamp; `None` with fewer than two points
+pub fn drift(points: &[TrendPoint]) -> Option<Drift> {
+    if points.len() < 2 {
+        return None;
+    }
+    let times: Vec<f64> = points.iter().map(|point| point.time_ns).collect();
+    let mean = times.iter().sum::<f64>() / times.len() as f64;
+    let runs: Vec<f64> = (0..points.len()).map(|run| run as f64).collect();
+    let pct_per_run = slope(&runs, &times)? / mean * 100.0;
+    let seconds: Option<Vec<f64>> = points
+        .iter()
+        .map(|point| DateTime::parse_from_rfc3339(&point.timestamp).ok().map(|time| time.timestamp() as f64))
+        .collect();
+    let pct_per_week = seconds
+        // Points come oldest first
+        .filter(|seconds| seconds[seconds.len() - 1] - seconds[0] >= MIN_WEEKLY_SPAN_SECS)
+        .and_then(|seconds| slope(&seconds, &times))
+        .map(|per_second| per_second * SECONDS_PER_WEEK / mean * 100.0);
+    Some(Drift {
+        pct_per_run,
+        pct_per_week,
+    })
+}
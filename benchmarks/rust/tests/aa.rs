@@ -0,0 +1,49 @@
+use mojo_regex_rust_bench::aa::AaReport;
+use mojo_regex_rust_bench::compare::Comparison;
+
+fn comparison(name: &str, delta_pct: f64, significant: bool) -> Comparison {
+    Comparison {
+        name: name.to_string(),
+        index: None,
+        category: "literal".to_string(),
+        engine_path: None,
+        family: "literal".to_string(),
+        baseline_ns: 100.0,
+        candidate_ns: 100.0 * (1.0 + delta_pct / 100.0),
+        baseline_iterations: 10,
+        candidate_iterations: 10,
+        delta_pct,
+        p_value: None,
+        significant,
+        ci_overlap: None,
+    }
+}
+
+#[test]
+fn spurious_deltas_are_bucketed_and_flagged() {
+    let comparisons = [
+        comparison("a", 0.5, false),
+        comparison("b", -1.5, false),
+        comparison("c", 3.0, true),
+        comparison("d", -7.0, true),
+        comparison("e", 12.0, false),
+    ];
+    let report = AaReport::new(&comparisons, 5.0).unwrap();
+
+    assert_eq!(report.compared, 5);
+    assert_eq!(report.buckets, [1, 1, 1, 1, 1]);
+    assert_eq!(report.median_abs_pct, 3.0);
+    assert_eq!(report.max_abs_pct, 12.0);
+    assert_eq!(report.significant, 2);
+    assert!(!report.passed());
+    let flagged: Vec<&str> = report.over_threshold.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(flagged, ["e", "d"]);
+}
+
+#[test]
+fn quiet_runs_pass() {
+    let comparisons = [comparison("a", 0.2, false), comparison("b", -4.9, false)];
+    let report = AaReport::new(&comparisons, 5.0).unwrap();
+    assert!(report.passed());
+    assert!(AaReport::new(&[], 5.0).is_none());
+}
@@ -0,0 +1,79 @@
+use chrono::{TimeZone, Utc};
+use mojo_regex_rust_bench::{archive, BenchmarkResults};
+
+#[test]
+fn runs_are_named_by_label_and_time() {
+    let time = Utc.with_ymd_and_hms(2026, 3, 1, 12, 30, 5).unwrap();
+    assert_eq!(archive::timestamped_name(None, time), "rust_results-20260301T123005Z.json");
+    assert_eq!(
+        archive::timestamped_name(Some("feature-x"), time),
+        "rust_results-feature-x-20260301T123005Z.json"
+    );
+    assert_eq!(archive::latest_name(Some("feature-x")), "rust_results-feature-x.json");
+    assert!(archive::validate_label("main_1.2").is_ok());
+    assert!(archive::validate_label("../main").is_err());
+    assert!(archive::validate_label("").is_err());
+}
+
+#[test]
+fn latest_file_is_a_regular_copy_of_the_last_run() {
+    let dir = std::env::temp_dir().join(format!("archive-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    // A checked-in results file from before
+    std::fs::write(dir.join("rust_results.json"), "old").unwrap();
+
+    for (file, content) in [("rust_results-1.json", "first"), ("rust_results-2.json", "second")] {
+        std::fs::write(dir.join(file), content).unwrap();
+        archive::copy_latest(&dir, file, "rust_results.json").unwrap();
+        assert_eq!(std::fs::read_to_string(dir.join("rust_results.json")).unwrap(), content);
+    }
+
+    let latest = std::fs::symlink_metadata(dir.join("rust_results.json")).unwrap();
+    assert!(latest.file_type().is_file());
+    // Nothing but the two runs and the latest copy
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn compressed_results_load_transparently() {
+    let dir = std::env::temp_dir().join(format!("archive-gz-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let content = r#"{"engine": "rust", "results": {"literal": {"literal_match_short": {"time_ns": 12.5}}}}"#;
+    for (name, compress) in [("plain.json", false), ("packed.json.gz", true)] {
+        let path = dir.join(name);
+        archive::write(&path, content, compress).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(bytes.starts_with(&[0x1f, 0x8b]), compress);
+
+        let path = path.to_str().unwrap();
+        assert_eq!(archive::read_to_string(path).unwrap(), content);
+        let results = BenchmarkResults::load(path).unwrap();
+        assert_eq!(results.results["literal"]["literal_match_short"].time_ns, 12.5);
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn baselines_are_saved_and_found_by_name() {
+    let dir = std::env::temp_dir().join(format!("baselines-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let saved = archive::save_baseline(&dir, "main", "{}", false).unwrap();
+    assert_eq!(saved, dir.join("baselines/main.json"));
+    assert_eq!(archive::resolve_baseline(&dir, "main").unwrap(), saved);
+
+    // Re-saving compressed replaces the plain file rather than shadowing it
+    let saved = archive::save_baseline(&dir, "main", "{}", true).unwrap();
+    assert_eq!(saved, dir.join("baselines/main.json.gz"));
+    assert!(!dir.join("baselines/main.json").exists());
+    assert_eq!(archive::resolve_baseline(&dir, "main").unwrap(), saved);
+
+    // Files are taken as they are
+    let file = saved.to_string_lossy();
+    assert_eq!(archive::resolve_baseline(&dir, &file).unwrap(), saved);
+    assert!(archive::resolve_baseline(&dir, "feature").is_err());
+    assert!(archive::save_baseline(&dir, "../main", "{}", false).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,79 @@
+use mojo_regex_rust_bench::artifacts::{built_at, find, resolve_ref};
+use mojo_regex_rust_bench::build_info::BuildInfo;
+use mojo_regex_rust_bench::history;
+use mojo_regex_rust_bench::BenchmarkResults;
+
+mod common;
+
+const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+const OTHER_SHA: &str = "fedcba9876543210fedcba9876543210fedcba98";
+
+fn build(sha: &str, dirty: bool) -> BuildInfo {
+    BuildInfo {
+        git_sha: Some(sha.to_string()),
+        git_dirty: dirty,
+        ..Default::default()
+    }
+}
+
+fn results(engine: &str, timestamp: &str, build_info: Option<BuildInfo>) -> BenchmarkResults {
+    BenchmarkResults {
+        build_info,
+        ..common::results(engine, timestamp, vec![])
+    }
+}
+
+#[test]
+fn builds_are_matched_to_a_commit_by_its_full_hash() {
+    assert!(built_at(&build(SHA, false), SHA));
+    assert!(!built_at(&build(OTHER_SHA, false), SHA));
+    // Local changes make it some other tree
+    assert!(!built_at(&build(SHA, true), SHA));
+    assert!(!built_at(&BuildInfo::default(), SHA));
+    // The recorded hash wins over a tag that merely looks like one
+    let tagged = BuildInfo {
+        git_describe: Some("0123".to_string()),
+        ..build(OTHER_SHA, false)
+    };
+    assert!(!built_at(&tagged, SHA));
+}
+
+#[test]
+fn builds_without_a_recorded_hash_resolve_their_describe_output() {
+    let head = resolve_ref("HEAD").unwrap();
+    let legacy = BuildInfo {
+        git_sha: None,
+        git_dirty: false,
+        ..BuildInfo::current()
+    };
+    assert!(built_at(&legacy, &head));
+    assert!(!built_at(&legacy, SHA));
+}
+
+#[test]
+fn the_latest_matching_run_is_found_in_history_or_results_files() {
+    let dir = std::env::temp_dir().join(format!("artifacts-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let write = |name: &str, results: &BenchmarkResults| {
+        std::fs::write(dir.join(name), serde_json::to_string(results).unwrap()).unwrap();
+    };
+    write("old.json", &results("rust", "2026-01-01T00:00:00+00:00", Some(build(SHA, false))));
+    write("other_commit.json", &results("rust", "2026-01-05T00:00:00+00:00", Some(build(OTHER_SHA, false))));
+    write("mojo.json", &results("mojo", "2026-01-05T00:00:00+00:00", Some(build(SHA, false))));
+    std::fs::write(dir.join("weights.json"), r#"{"global": 2.0}"#).unwrap();
+    let history_path = dir.join("history.jsonl");
+    let history_str = history_path.to_str().unwrap();
+    history::append(history_str, &results("rust", "2026-01-03T00:00:00+00:00", None), Some(SHA)).unwrap();
+    history::append(history_str, &results("rust", "2026-01-04T00:00:00+00:00", None), Some("fedcba9")).unwrap();
+
+    let dirs = [dir.clone()];
+    let found = find(SHA, "rust", &history_path, &dirs).unwrap().unwrap();
+    assert_eq!(found.source, format!("{}:1", history_str));
+    assert_eq!(found.path, None);
+
+    write("newer.json", &results("rust", "2026-01-06T00:00:00+00:00", Some(build(SHA, false))));
+    let found = find(SHA, "rust", &history_path, &dirs).unwrap().unwrap();
+    assert_eq!(found.path, Some(dir.join("newer.json")));
+    assert!(find("fedcba9876", "python", &history_path, &dirs).unwrap().is_none());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,25 @@
+use mojo_regex_rust_bench::build_info::BuildInfo;
+
+#[test]
+fn build_info_is_baked_in_and_round_trips() {
+    let info = BuildInfo::current();
+    assert!(info.rustc.starts_with("rustc "), "{:?}", info.rustc);
+    assert!(!info.target.is_empty());
+    assert!(!info.opt_level.is_empty());
+    assert!(info.regex_version.starts_with("1."), "{:?}", info.regex_version);
+    assert!(info.regex_automata_version.starts_with("0.4."));
+    assert!(info.summary().contains(&info.regex_version));
+
+    let json = serde_json::to_string(&info).unwrap();
+    assert_eq!(serde_json::from_str::<BuildInfo>(&json).unwrap(), info);
+}
+
+#[test]
+fn git_state_comes_from_the_harness_checkout_wherever_it_runs() {
+    let in_checkout = BuildInfo::current();
+    std::env::set_current_dir(std::env::temp_dir()).unwrap();
+    let elsewhere = BuildInfo::current();
+    assert!(elsewhere.git_sha.is_some());
+    assert_eq!(elsewhere.git_sha, in_checkout.git_sha);
+    assert_eq!(elsewhere.git_describe, in_checkout.git_describe);
+}
@@ -0,0 +1,22 @@
+//! Fixtures shared by the integration tests
+
+use mojo_regex_rust_bench::{BenchmarkResult, BenchmarkResults};
+
+/// Results of `engine` at `timestamp`, holding each category's benchmarks
+pub fn results(
+    engine: &str,
+    timestamp: &str,
+    categories: Vec<(&str, Vec<(&str, BenchmarkResult)>)>,
+) -> BenchmarkResults {
+    let results = categories
+        .into_iter()
+        .map(|(category, benches)| {
+            let benches = benches.into_iter().map(|(name, result)| (name.to_string(), result)).collect();
+            (category.to_string(), benches)
+        })
+        .collect();
+    BenchmarkResults {
+        timestamp: timestamp.to_string(),
+        ..BenchmarkResults::new(engine, results)
+    }
+}
@@ -0,0 +1,255 @@
+use mojo_regex_rust_bench::compare::{
+    calibrate, compare, geomean_ratio_by_family, geomean_ratio_by_path, input_mismatches, load_thresholds, regressions,
+    threshold_for, top_changes, Thresholds,
+};
+use mojo_regex_rust_bench::engine_path::EnginePath;
+use mojo_regex_rust_bench::stats::SampleSummary;
+use mojo_regex_rust_bench::{BenchmarkResult, BenchmarkResults, Variant};
+
+mod common;
+
+fn result(pattern: &str, haystack_len: usize) -> BenchmarkResult {
+    BenchmarkResult {
+        time_ns: 100.0,
+        pattern: Some(pattern.to_string()),
+        haystack_len: Some(haystack_len),
+        operation: Some("FindAll".to_string()),
+        inner_iterations: Some(100),
+        ..Default::default()
+    }
+}
+
+fn results(engine: &str, benches: Vec<(&str, BenchmarkResult)>) -> BenchmarkResults {
+    common::results(engine, "", vec![("phone", benches)])
+}
+
+#[test]
+fn benchmarks_run_on_different_inputs_are_listed() {
+    let baseline = results(
+        "rust",
+        vec![
+            ("same", result(r"\d{3}", 1000)),
+            ("other_pattern", result(r"\d{3}", 1000)),
+            ("other_haystack", result(r"\d{3}", 1000)),
+            ("unrecorded", result(r"\d{3}", 1000)),
+        ],
+    );
+    let candidate = results(
+        "mojo",
+        vec![
+            ("same", BenchmarkResult { inner_iterations: Some(10), ..result(r"\d{3}", 1000) }),
+            ("other_pattern", result(r"\d{4}", 1000)),
+            ("other_haystack", result(r"\d{3}", 2000)),
+            // Older harnesses recorded no inputs; nothing to check
+            ("unrecorded", BenchmarkResult::default()),
+        ],
+    );
+
+    assert_eq!(
+        input_mismatches(&baseline, &candidate),
+        [
+            "other_haystack: haystack 1000 vs 2000 bytes",
+            r#"other_pattern: pattern "\\d{3}" vs "\\d{4}""#,
+        ]
+    );
+}
+
+#[test]
+fn ratios_are_grouped_by_engine_path() {
+    let tagged = |time_ns, path| BenchmarkResult {
+        time_ns,
+        engine_path: path,
+        ..Default::default()
+    };
+    let baseline = results(
+        "rust",
+        vec![
+            ("dfa_a", tagged(100.0, Some(EnginePath::PureDfa))),
+            ("dfa_b", tagged(100.0, Some(EnginePath::PureDfa))),
+            ("simd", tagged(100.0, Some(EnginePath::SimdCharClass))),
+            ("untagged", tagged(100.0, None)),
+        ],
+    );
+    let candidate = results(
+        "mojo",
+        vec![
+            ("dfa_a", tagged(200.0, None)),
+            ("dfa_b", tagged(50.0, None)),
+            ("simd", tagged(150.0, None)),
+            ("untagged", tagged(100.0, None)),
+        ],
+    );
+
+    let by_path = geomean_ratio_by_path(&compare(&baseline, &candidate, 0.01));
+    assert_eq!(by_path.len(), 2);
+    assert_eq!(by_path[&EnginePath::PureDfa], (1.0, 2));
+    assert_eq!(by_path[&EnginePath::SimdCharClass], (1.5, 1));
+}
+
+#[test]
+fn regressions_honor_per_benchmark_thresholds_and_significance() {
+    let timed = |time_ns| BenchmarkResult {
+        time_ns,
+        ..Default::default()
+    };
+    let sampled = |time_ns: f64| BenchmarkResult {
+        samples: Some(vec![time_ns; 20]),
+        ..timed(time_ns)
+    };
+    let baseline = results(
+        "rust",
+        vec![
+            ("dfa_dot_phone", timed(100.0)),
+            ("slower", timed(100.0)),
+            ("noisy", timed(100.0)),
+            ("within", timed(100.0)),
+            ("overlapping", sampled(100.0)),
+        ],
+    );
+    let candidate = results(
+        "rust",
+        vec![
+            ("dfa_dot_phone", timed(240.0)),
+            ("slower", timed(115.0)),
+            ("noisy", timed(115.0)),
+            ("within", timed(105.0)),
+            // Identical samples: +15% by median but not significant
+            ("overlapping", BenchmarkResult { time_ns: 115.0, ..sampled(100.0) }),
+        ],
+    );
+
+    let thresholds = Thresholds::from([("noisy".to_string(), 20.0)]);
+    let found = regressions(&compare(&baseline, &candidate, 0.01), 10.0, &thresholds);
+    let names: Vec<&str> = found.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["dfa_dot_phone", "slower"]);
+    assert_eq!(found[0].to_string(), "dfa_dot_phone: +140.0% vs baseline (100.0 -> 240.0 ns, max +10.0%)");
+}
+
+#[test]
+fn regressions_need_disjoint_confidence_intervals_without_samples() {
+    let summarized = |time_ns: f64, std_dev_ns: f64| BenchmarkResult {
+        time_ns,
+        summary: Some(SampleSummary {
+            count: 100,
+            mean_ns: time_ns,
+            std_dev_ns,
+            min_ns: 0.0,
+            max_ns: 0.0,
+        }),
+        ..Default::default()
+    };
+    let baseline = results(
+        "rust",
+        vec![("steady", summarized(100.0, 5.0)), ("findall_heavy", summarized(100.0, 200.0))],
+    );
+    let candidate = results(
+        "rust",
+        vec![("steady", summarized(130.0, 5.0)), ("findall_heavy", summarized(130.0, 200.0))],
+    );
+
+    let comparisons = compare(&baseline, &candidate, 0.01);
+    assert_eq!(comparisons[0].name, "findall_heavy");
+    assert_eq!(comparisons[0].ci_overlap, Some(true));
+    assert_eq!(comparisons[1].ci_overlap, Some(false));
+    let found = regressions(&comparisons, 10.0, &Thresholds::new());
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name, "steady");
+}
+
+#[test]
+fn ratios_are_grouped_by_family() {
+    let timed = |time_ns| BenchmarkResult {
+        time_ns,
+        ..Default::default()
+    };
+    let cjk = Variant {
+        family: "corpus_email".to_string(),
+        name: "cjk".to_string(),
+    };
+    let baseline = results(
+        "rust",
+        vec![
+            ("dfa_simple_phone", timed(100.0)),
+            ("dfa_dot_phone", timed(100.0)),
+            ("literal_match_long", timed(100.0)),
+            ("national_phone_validation", timed(100.0)),
+            ("corpus_email_cjk", BenchmarkResult { variant: Some(cjk), ..timed(100.0) }),
+        ],
+    );
+    let candidate = results(
+        "mojo",
+        vec![
+            ("dfa_simple_phone", timed(100.0)),
+            ("dfa_dot_phone", timed(400.0)),
+            ("literal_match_long", timed(50.0)),
+            ("national_phone_validation", timed(200.0)),
+            ("corpus_email_cjk", timed(100.0)),
+        ],
+    );
+
+    let by_family = geomean_ratio_by_family(&compare(&baseline, &candidate, 0.01));
+    assert_eq!(by_family.keys().collect::<Vec<_>>(), ["corpus_email", "dfa", "literal", "phone"]);
+    assert_eq!(by_family["dfa"], (2.0, 2));
+    assert_eq!(by_family["literal"], (0.5, 1));
+    // No family prefix: grouped with the rest of its category
+    assert_eq!(by_family["phone"], (2.0, 1));
+}
+
+#[test]
+fn top_changes_are_the_largest_each_way() {
+    let timed = |time_ns| BenchmarkResult {
+        time_ns,
+        iterations: 10,
+        ..Default::default()
+    };
+    let names = ["a", "b", "c", "d", "e"];
+    let baseline = results("rust", names.iter().map(|name| (*name, timed(100.0))).collect());
+    let candidate = results("rust", names.iter().copied().zip([150.0, 300.0, 100.0, 90.0, 20.0].map(timed)).collect());
+
+    let comparisons = compare(&baseline, &candidate, 0.01);
+    let (regressions, improvements) = top_changes(&comparisons, 1);
+    assert_eq!(regressions.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), ["b"]);
+    assert_eq!(regressions[0].candidate_iterations, 10);
+    let (regressions, improvements_all) = top_changes(&comparisons, 5);
+    assert_eq!(regressions.len(), 2);
+    assert_eq!(improvements[0].name, "e");
+    assert_eq!(improvements_all.iter().map(|c| c.name.as_str()).collect::<Vec<_>>(), ["e", "d"]);
+}
+
+#[test]
+fn thresholds_load_from_toml_with_prefix_patterns() {
+    let path = std::env::temp_dir().join(format!("thresholds-{}.toml", std::process::id()));
+    std::fs::write(&path, "\"dfa_*\" = 5\n\"dfa_dot_*\" = 8.0\ndfa_dot_phone = 25.0\n").unwrap();
+    let thresholds = load_thresholds(path.to_str().unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(threshold_for(&thresholds, "dfa_dot_phone"), Some(25.0));
+    assert_eq!(threshold_for(&thresholds, "dfa_dot_email"), Some(8.0));
+    assert_eq!(threshold_for(&thresholds, "dfa_simple_phone"), Some(5.0));
+    assert_eq!(threshold_for(&thresholds, "sparse_phone_findall"), None);
+}
+
+#[test]
+fn calibrating_scales_the_baseline_to_the_candidates_machine() {
+    let timed = |time_ns: f64| BenchmarkResult {
+        time_ns,
+        samples: Some(vec![time_ns, time_ns * 2.0]),
+        throughput_mb_s: Some(1000.0 / time_ns),
+        ..Default::default()
+    };
+    // The candidate's machine runs everything twice as slow, and "b" also
+    // got 10% slower
+    let mut baseline = results("rust", vec![("calibration", timed(10.0)), ("b", timed(100.0))]);
+    let candidate = results("rust", vec![("calibration", timed(20.0)), ("b", timed(220.0))]);
+
+    assert_eq!(calibrate(&mut baseline, &candidate, "calibration"), Ok(2.0));
+    let b = &baseline.flat()["b"];
+    assert_eq!(b.time_ns, 200.0);
+    assert_eq!(b.samples, Some(vec![200.0, 400.0]));
+    assert_eq!(b.throughput_mb_s, Some(5.0));
+    let comparisons = compare(&baseline, &candidate, 0.01);
+    let delta = |name: &str| comparisons.iter().find(|c| c.name == name).unwrap().delta_pct;
+    assert_eq!(delta("calibration"), 0.0);
+    assert!((delta("b") - 10.0).abs() < 1e-9);
+    assert!(calibrate(&mut baseline, &candidate, "missing").is_err());
+}
@@ -0,0 +1,18 @@
+use mojo_regex_rust_bench::corpus::{parallel_text, LANGUAGES};
+use regex::Regex;
+
+#[test]
+fn every_language_has_the_same_matches() {
+    for pattern in ["invoice", r"\d{8}", r"[a-z]+@[a-z]+\.[a-z]+", r"\b\w+@\w+\.\w+\b"] {
+        let regex = Regex::new(pattern).unwrap();
+        let matches: Vec<Vec<String>> = LANGUAGES
+            .iter()
+            .map(|&language| {
+                let text = parallel_text(language, 3);
+                regex.find_iter(&text).map(|m| m.as_str().to_string()).collect()
+            })
+            .collect();
+        assert_eq!(matches[0].len(), 3, "{}", pattern);
+        assert!(matches.iter().all(|m| *m == matches[0]), "{}: {:?}", pattern, matches);
+    }
+}
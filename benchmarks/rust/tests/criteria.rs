@@ -0,0 +1,109 @@
+use mojo_regex_rust_bench::criteria::{check, Criteria, Violation};
+use mojo_regex_rust_bench::{BenchmarkResult, ResultCollector};
+
+fn measured(allocations: f64, peak_rss_bytes: u64, throughput_mb_s: f64) -> BenchmarkResult {
+    BenchmarkResult {
+        time_ns: 100.0,
+        allocations: Some(allocations),
+        peak_rss_bytes: Some(peak_rss_bytes),
+        throughput_mb_s: Some(throughput_mb_s),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn results_without_criteria_always_pass() {
+    assert!(check("bench", &measured(1e6, u64::MAX, 0.0)).is_empty());
+}
+
+#[test]
+fn results_within_budget_pass() {
+    let mut result = measured(0.0, 1024 * 1024, 2000.0);
+    result.criteria = Some(
+        Criteria::new()
+            .max_allocations(0.0)
+            .max_peak_rss_mib(1)
+            .min_throughput_mb_s(1000.0),
+    );
+    assert!(check("bench", &result).is_empty());
+}
+
+#[test]
+fn each_broken_budget_is_reported() {
+    let mut result = measured(2.0, 2 * 1024 * 1024, 500.0);
+    result.criteria = Some(
+        Criteria::new()
+            .max_allocations(1.0)
+            .max_peak_rss_mib(1)
+            .min_throughput_mb_s(1000.0),
+    );
+    let violations = check("bench", &result);
+    assert_eq!(violations.len(), 3);
+    assert!(matches!(violations[0], Violation::TooManyAllocations { .. }));
+    assert!(matches!(violations[1], Violation::PeakRssTooHigh { .. }));
+    assert!(matches!(violations[2], Violation::ThroughputTooLow { .. }));
+}
+
+#[test]
+fn timed_out_benchmarks_fail_and_skipped_ones_are_exempt() {
+    let criteria = Criteria::new().min_throughput_mb_s(1000.0);
+    let mut timed_out = BenchmarkResult::timed_out();
+    timed_out.criteria = Some(criteria.clone());
+    assert_eq!(
+        check("bench", &timed_out),
+        vec![Violation::Unmeasured {
+            name: "bench".to_string(),
+            criterion: "min_throughput_mb_s",
+        }]
+    );
+
+    let mut skipped = BenchmarkResult::skipped();
+    skipped.criteria = Some(criteria);
+    assert!(check("bench", &skipped).is_empty());
+}
+
+#[test]
+fn wrong_match_counts_fail_without_declared_criteria() {
+    let mut collector = ResultCollector::new();
+    collector.begin_category("phone", "Phone");
+    let observed = |count| BenchmarkResult {
+        observed_matches: Some(count),
+        correct: Some(true),
+        ..measured(0.0, 0, 0.0)
+    };
+    collector.insert("right", observed(125));
+    collector.insert("wrong", observed(0));
+    collector.expect_matches("right", 125);
+    collector.expect_matches("wrong", 125);
+
+    let results = &collector.results()["phone"];
+    assert_eq!(results["right"].correct, Some(true));
+    assert!(check("right", &results["right"]).is_empty());
+    assert_eq!(results["wrong"].correct, Some(false));
+    assert_eq!(
+        check("wrong", &results["wrong"]),
+        vec![Violation::WrongMatchCount {
+            name: "wrong".to_string(),
+            observed: 0,
+            expected: Some(125),
+        }]
+    );
+}
+
+#[test]
+fn tokenizers_record_their_tokens_per_second() {
+    let mut collector = ResultCollector::new();
+    collector.begin_category("lexer", "Lexer");
+    let lexed = BenchmarkResult {
+        observed_matches: Some(500),
+        ..measured(0.0, 0, 0.0)
+    };
+    collector.insert("lexer", lexed.clone());
+    collector.insert("not_a_lexer", lexed);
+    collector.counts_tokens("lexer");
+
+    let results = &collector.results()["lexer"];
+    // 500 tokens in 100ns
+    assert_eq!(results["lexer"].tokens_per_sec, Some(5e9));
+    assert_eq!(results["not_a_lexer"].tokens_per_sec, None);
+}
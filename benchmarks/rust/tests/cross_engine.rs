@@ -0,0 +1,129 @@
+use mojo_regex_rust_bench::cross_engine::{csv, geomean_ratio, join, missing, scoreboard, speedup_matrix, Row};
+use mojo_regex_rust_bench::{BenchmarkResult, BenchmarkResults};
+
+mod common;
+
+fn results(engine: &str, benches: Vec<(&str, BenchmarkResult)>) -> BenchmarkResults {
+    common::results(engine, "", vec![("all", benches)])
+}
+
+fn timed(index: Option<usize>, time_ns: f64) -> BenchmarkResult {
+    BenchmarkResult {
+        index,
+        time_ns,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn engines_are_joined_by_name_in_the_first_engines_order() {
+    let rust = results("rust", vec![("b", timed(Some(0), 100.0)), ("a", timed(Some(1), 200.0))]);
+    // Legacy harness files carry no registration index
+    let mojo = results(
+        "mojo",
+        vec![
+            ("a", timed(None, 50.0)),
+            ("b", timed(None, 400.0)),
+            ("mojo_only", timed(None, 1.0)),
+            ("timed_out", BenchmarkResult::timed_out()),
+        ],
+    );
+
+    let rows = join(&[Some(&rust), Some(&mojo), None]);
+    assert_eq!(
+        rows,
+        [
+            Row { name: "b".to_string(), times_ns: vec![Some(100.0), Some(400.0), None], match_counts: vec![None; 3] },
+            Row { name: "a".to_string(), times_ns: vec![Some(200.0), Some(50.0), None], match_counts: vec![None; 3] },
+            Row { name: "mojo_only".to_string(), times_ns: vec![None, Some(1.0), None], match_counts: vec![None; 3] },
+            Row { name: "timed_out".to_string(), times_ns: vec![None, None, None], match_counts: vec![None; 3] },
+        ]
+    );
+    assert_eq!(rows[0].ratio(1, 0), Some(4.0));
+    assert_eq!(geomean_ratio(&rows, 1, 0), Some((1.0, 2)));
+    assert_eq!(geomean_ratio(&rows, 1, 2), None);
+}
+
+#[test]
+fn rows_engines_disagree_on_are_left_out_of_geomeans() {
+    let counted = |time_ns: f64, matches: Option<usize>| BenchmarkResult {
+        observed_matches: matches,
+        ..timed(None, time_ns)
+    };
+    let rust = results("rust", vec![("a", counted(100.0, Some(3))), ("b", counted(100.0, Some(3)))]);
+    // "b" finds nothing on Mojo, which is fast but wrong
+    let mojo = results("mojo", vec![("a", counted(200.0, Some(3))), ("b", counted(1.0, Some(0)))]);
+    // Engines that don't record counts can't disagree
+    let python = results("python", vec![("a", counted(400.0, None)), ("b", counted(400.0, None))]);
+
+    let rows = join(&[Some(&rust), Some(&mojo), Some(&python)]);
+    assert_eq!(rows[1].match_counts, [Some(3), Some(0), None]);
+    assert!(!rows[0].disagrees());
+    assert!(rows[1].disagrees());
+    assert_eq!(geomean_ratio(&rows, 1, 0), Some((2.0, 1)));
+    assert_eq!(scoreboard(&rows)[1].contested, 1);
+}
+
+#[test]
+fn scoreboard_normalizes_to_the_fastest_engine() {
+    let rust = results("rust", vec![("a", timed(Some(0), 100.0)), ("b", timed(Some(1), 100.0))]);
+    let mojo = results(
+        "mojo",
+        vec![("a", timed(None, 50.0)), ("b", timed(None, 400.0)), ("mojo_only", timed(None, 1.0))],
+    );
+    let python = results("python", vec![("a", timed(None, 200.0)), ("b", timed(None, 100.0))]);
+
+    let rows = join(&[Some(&rust), Some(&mojo), Some(&python)]);
+    assert_eq!(rows[0].normalized(), Some(vec![Some(2.0), Some(1.0), Some(4.0)]));
+    assert_eq!(rows[2].normalized(), None);
+
+    let scores = scoreboard(&rows);
+    // Rust and Python tie on "b"; "mojo_only" isn't contested
+    assert_eq!(scores.iter().map(|score| (score.wins, score.contested)).collect::<Vec<_>>(), [(1, 2), (1, 2), (1, 2)]);
+    assert_eq!(scores[1].geomean, Some(2.0));
+}
+
+#[test]
+fn benchmarks_an_engine_lacks_are_listed() {
+    let rust = results("rust", vec![("a", timed(Some(0), 1.0)), ("b", timed(Some(1), 1.0))]);
+    // Skipped still counts as present
+    let mojo = results(
+        "mojo",
+        vec![("b", BenchmarkResult::skipped()), ("c", timed(None, 1.0)), ("d", timed(None, 1.0))],
+    );
+
+    assert_eq!(
+        missing(&[Some(&rust), Some(&mojo), None]),
+        [vec!["c".to_string(), "d".to_string()], vec!["a".to_string()], vec![]]
+    );
+}
+
+#[test]
+fn csv_has_a_column_per_engine_and_ratio() {
+    let rust = results("rust", vec![("a,b", timed(Some(0), 100.0)), ("plain", timed(Some(1), 200.0))]);
+    let mojo = results("mojo", vec![("a,b", timed(None, 50.0))]);
+
+    let rows = join(&[Some(&rust), Some(&mojo)]);
+    assert_eq!(
+        csv(&rows, &["rust", "mojo"], &[(1, 0)]),
+        "name,rust_ns,mojo_ns,mojo/rust\n\"a,b\",100,50,0.5\nplain,200,,\n"
+    );
+}
+
+#[test]
+fn speedup_matrix_covers_every_engine_pair() {
+    let names = ["a", "b"];
+    let engine = |engine: &str, times: [f64; 2]| {
+        results(engine, names.iter().copied().zip(times.map(|time| timed(None, time))).collect())
+    };
+    let rust = engine("rust", [100.0, 100.0]);
+    let mojo = engine("mojo", [50.0, 200.0]);
+    let pcre2 = engine("pcre2", [25.0, 25.0]);
+    let rows = join(&[Some(&rust), Some(&mojo), None, Some(&pcre2)]);
+
+    let matrix = speedup_matrix(&rows, 4);
+    assert_eq!(matrix[0], [None, Some(1.0), None, Some(0.25)]);
+    assert_eq!(matrix[3][0], Some(4.0));
+    assert_eq!(matrix[3][1], Some(4.0));
+    assert_eq!(matrix[2], [None; 4]);
+}
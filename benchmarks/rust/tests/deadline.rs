@@ -0,0 +1,40 @@
+use mojo_regex_rust_bench::{with_deadline, ResultCollector};
+use std::time::Duration;
+
+#[test]
+fn returns_value_within_deadline() {
+    assert_eq!(with_deadline(Some(Duration::from_secs(10)), || 42), Some(42));
+}
+
+#[test]
+fn abandons_closure_past_deadline() {
+    let result = with_deadline(Some(Duration::from_millis(20)), || {
+        std::thread::sleep(Duration::from_secs(5));
+        42
+    });
+    assert_eq!(result, None);
+}
+
+#[test]
+fn runs_inline_without_deadline() {
+    let caller = std::thread::current().id();
+    assert_eq!(with_deadline(None, move || std::thread::current().id() == caller), Some(true));
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn propagates_panics() {
+    with_deadline(Some(Duration::from_secs(10)), || -> u32 { panic!("boom") });
+}
+
+#[test]
+fn category_deadlines_tighten_the_suite_one_until_the_next_category() {
+    let mut collector = ResultCollector::new().with_benchmark_deadline(Duration::from_secs(60));
+    collector.begin_category("pathological", "Pathological");
+    collector.set_category_deadline(Duration::from_secs(10));
+    assert_eq!(collector.benchmark_deadline(), Some(Duration::from_secs(10)));
+    collector.set_category_deadline(Duration::from_secs(120));
+    assert_eq!(collector.benchmark_deadline(), Some(Duration::from_secs(60)));
+    collector.begin_category("overhead", "Overhead");
+    assert_eq!(collector.benchmark_deadline(), Some(Duration::from_secs(60)));
+}
@@ -0,0 +1,53 @@
+use mojo_regex_rust_bench::{BenchmarkResults, ResultCollector};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn teardowns_run_when_their_category_ends_in_reverse_order() {
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let mut collector = ResultCollector::dry_run();
+
+    collector.begin_category("first", "First");
+    let corpus = collector.setup("corpus", || "abc".repeat(3));
+    for step in ["remove temp file", "stop engine"] {
+        let log = log.clone();
+        collector.on_teardown(move || log.borrow_mut().push(step));
+    }
+    assert!(log.borrow().is_empty());
+
+    collector.begin_category("second", "Second");
+    assert_eq!(*log.borrow(), ["stop engine", "remove temp file"]);
+    // The first category's setup is still usable once its teardowns have run
+    assert_eq!(corpus, "abcabcabc");
+
+    let log_on_drop = log.clone();
+    collector.on_teardown(move || log_on_drop.borrow_mut().push("dropped"));
+    let costs: Vec<_> = collector
+        .setup_costs()
+        .iter()
+        .map(|cost| (cost.category.clone(), cost.label.clone()))
+        .collect();
+    assert_eq!(costs, [("first".to_string(), "corpus".to_string())]);
+    drop(collector);
+    assert_eq!(log.borrow().last(), Some(&"dropped"));
+}
+
+#[test]
+fn setup_costs_are_exported_with_the_results() {
+    let mut collector = ResultCollector::dry_run();
+    collector.begin_category("first", "First");
+    collector.setup("corpus", || ());
+    let results = BenchmarkResults {
+        setup_costs: collector.setup_costs().to_vec(),
+        ..BenchmarkResults::new("rust", Default::default())
+    };
+
+    let json = serde_json::to_value(&results).unwrap();
+    assert_eq!(json["setup_costs"][0]["category"], "first");
+    assert_eq!(json["setup_costs"][0]["label"], "corpus");
+    let read_back: BenchmarkResults = serde_json::from_value(json).unwrap();
+    assert_eq!(read_back.setup_costs.len(), 1);
+
+    let without: serde_json::Value = serde_json::to_value(BenchmarkResults::new("rust", Default::default())).unwrap();
+    assert!(without.get("setup_costs").is_none());
+}
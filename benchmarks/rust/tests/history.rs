@@ -0,0 +1,34 @@
+use mojo_regex_rust_bench::history;
+use mojo_regex_rust_bench::{BenchmarkResult, BenchmarkResults};
+
+mod common;
+
+fn results(timestamp: &str, time_ns: f64) -> BenchmarkResults {
+    let bench = BenchmarkResult {
+        time_ns,
+        ..Default::default()
+    };
+    common::results("rust", timestamp, vec![("literal", vec![("literal_match_short", bench)])])
+}
+
+#[test]
+fn runs_are_appended_one_line_each() {
+    let path = std::env::temp_dir().join(format!("history-{}.jsonl", std::process::id()));
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    history::append(path, &results("2026-01-01T00:00:00Z", 100.0), Some("abc123")).unwrap();
+    history::append(path, &results("2026-01-02T00:00:00Z", 90.0), None).unwrap();
+
+    let content = std::fs::read_to_string(path).unwrap();
+    assert_eq!(content.lines().count(), 2);
+    let records = history::load(path).unwrap();
+    std::fs::remove_file(path).unwrap();
+
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].git_sha.as_deref(), Some("abc123"));
+    assert_eq!(records[0].results.engine, "rust");
+    assert_eq!(records[1].git_sha, None);
+    assert_eq!(records[1].results.timestamp, "2026-01-02T00:00:00Z");
+    assert_eq!(records[1].results.flat()["literal_match_short"].time_ns, 90.0);
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "sqlite")]
+
+use mojo_regex_rust_bench::history_db::HistoryDb;
+use mojo_regex_rust_bench::{BenchmarkResult, BenchmarkResults};
+
+mod common;
+
+fn results(timestamp: &str, time_ns: f64) -> BenchmarkResults {
+    let bench = BenchmarkResult {
+        time_ns,
+        iterations: 1000,
+        ..Default::default()
+    };
+    let benches = vec![("literal_match_short", bench), ("literal_match_long", BenchmarkResult::skipped())];
+    common::results("rust", timestamp, vec![("literal", benches)])
+}
+
+#[test]
+fn trend_lists_latest_results_per_commit_oldest_first() {
+    let path = std::env::temp_dir().join(format!("history-{}.db", std::process::id()));
+    let path = path.to_str().unwrap();
+    let _ = std::fs::remove_file(path);
+
+    let mut db = HistoryDb::open(path).unwrap();
+    // Skipped benchmarks aren't recorded
+    assert_eq!(db.insert(&results("2026-01-01T00:00:00Z", 100.0), Some("aaa")).unwrap(), 1);
+    db.insert(&results("2026-01-02T00:00:00Z", 90.0), Some("bbb")).unwrap();
+    // Re-running at the same commit replaces its row
+    db.insert(&results("2026-01-03T00:00:00Z", 80.0), Some("bbb")).unwrap();
+    db.insert(&results("2026-01-04T00:00:00Z", 70.0), Some("ccc")).unwrap();
+    drop(db);
+
+    let db = HistoryDb::open(path).unwrap();
+    let trend = db.trend("literal_match_short", None, 50).unwrap();
+    let shas: Vec<&str> = trend.iter().map(|point| point.git_sha.as_str()).collect();
+    assert_eq!(shas, ["aaa", "bbb", "ccc"]);
+    assert_eq!(trend[1].time_ns, 80.0);
+
+    let latest = db.trend("literal_match_short", Some("rust"), 2).unwrap();
+    assert_eq!(latest.iter().map(|point| point.time_ns).collect::<Vec<_>>(), [80.0, 70.0]);
+    assert!(db.trend("literal_match_short", Some("python"), 50).unwrap().is_empty());
+    std::fs::remove_file(path).unwrap();
+}
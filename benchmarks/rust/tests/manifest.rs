@@ -0,0 +1,54 @@
+use mojo_regex_rust_bench::manifest::{diff, Manifest, ManifestEntry};
+use mojo_regex_rust_bench::suite::all_benchmarks;
+
+fn entry(name: &str, pattern: &str, haystack_len: Option<usize>) -> ManifestEntry {
+    ManifestEntry {
+        name: name.to_string(),
+        pattern: Some(pattern.to_string()),
+        haystack_len,
+        operation: Some("FindAll".to_string()),
+        inner_iterations: Some(10),
+    }
+}
+
+#[test]
+fn the_suite_manifest_records_every_definition() {
+    let definitions = all_benchmarks();
+    let manifest = Manifest::from_definitions("rust", &definitions);
+    assert_eq!(manifest.benchmarks.len(), definitions.len());
+    let first = &manifest.benchmarks[0];
+    assert_eq!(first.name, definitions[0].name);
+    assert!(first.haystack_len.is_some() && first.inner_iterations.is_some());
+}
+
+#[test]
+fn mismatched_inputs_and_definitions_are_listed() {
+    let ours = Manifest {
+        engine: "rust".to_string(),
+        benchmarks: vec![
+            entry("same", r"\d+", Some(10_000)),
+            entry("bigger_text", r"\d+", Some(10_000)),
+            entry("rust_only", r"\d+", Some(10_000)),
+            entry("unrecorded_size", r"\w+", Some(10_000)),
+        ],
+    };
+    let theirs = Manifest {
+        engine: "mojo".to_string(),
+        benchmarks: vec![
+            entry("mojo_only", "a", None),
+            entry("unrecorded_size", r"\w", None),
+            entry("bigger_text", r"\d+", Some(100_000)),
+            entry("same", r"\d+", Some(10_000)),
+        ],
+    };
+
+    assert_eq!(
+        diff(&ours, &theirs),
+        [
+            "rust_only: missing from mojo",
+            "mojo_only: only in mojo",
+            "bigger_text: haystack 10000 vs 100000 bytes",
+            r#"unrecorded_size: pattern "\\w+" vs "\\w""#,
+        ]
+    );
+}
@@ -0,0 +1,48 @@
+use mojo_regex_rust_bench::merge::merge;
+use mojo_regex_rust_bench::{BenchmarkResult, BenchmarkResults};
+
+mod common;
+
+fn run(engine: &str, timestamp: &str, benches: Vec<(&str, BenchmarkResult)>) -> BenchmarkResults {
+    common::results(engine, timestamp, vec![("literal", benches)])
+}
+
+fn timed(time_ns: f64, iterations: u64) -> BenchmarkResult {
+    BenchmarkResult {
+        time_ns,
+        iterations,
+        throughput_mb_s: Some(1000.0 / time_ns),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn each_benchmark_takes_the_median_of_its_runs() {
+    let runs = [
+        run("rust", "2026-01-01T00:00:00Z", vec![("short", timed(100.0, 1)), ("long", BenchmarkResult::skipped())]),
+        run("rust", "2026-01-01T00:20:00Z", vec![("short", timed(130.0, 2)), ("long", timed(50.0, 1))]),
+        run("rust", "2026-01-01T00:10:00Z", vec![("short", timed(110.0, 3)), ("long", timed(70.0, 2))]),
+    ];
+
+    let merged = merge(&runs).unwrap();
+    assert_eq!(merged.timestamp, "2026-01-01T00:20:00Z");
+    let results = merged.flat();
+    let short = &results["short"];
+    assert_eq!(short.time_ns, 110.0);
+    assert_eq!(short.iterations, 3);
+    assert_eq!(short.round_medians_ns, Some(vec![100.0, 130.0, 110.0]));
+    assert_eq!(short.stability_score, Some(1.3));
+    // Skipped in one run: the median of the two that measured it, with the
+    // throughput rescaled to match
+    let long = &results["long"];
+    assert_eq!(long.time_ns, 60.0);
+    assert!((long.throughput_mb_s.unwrap() - 1000.0 / 60.0).abs() < 1e-9);
+    assert!(merged.aggregates.is_some());
+}
+
+#[test]
+fn runs_of_different_engines_are_refused() {
+    let runs = [run("rust", "", vec![]), run("mojo", "", vec![])];
+    assert!(merge(&runs).unwrap_err().contains("different engines"));
+    assert!(merge(&[]).is_err());
+}
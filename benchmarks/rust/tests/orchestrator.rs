@@ -0,0 +1,89 @@
+//! Failure injection for the orchestrator, using the `fake_engine` harness
+
+use mojo_regex_rust_bench::orchestrator::{orchestrate, run_engine, EngineError, EngineSpec};
+
+fn fake(name: &str, mode: &str, timeout_secs: f64) -> EngineSpec {
+    let results = std::env::temp_dir()
+        .join(format!("orchestrator-test-{}-{}.json", std::process::id(), name))
+        .to_string_lossy()
+        .into_owned();
+    EngineSpec {
+        name: name.to_string(),
+        command: vec![
+            env!("CARGO_BIN_EXE_fake_engine").to_string(),
+            mode.to_string(),
+            results.clone(),
+        ],
+        results,
+        timeout_secs,
+    }
+}
+
+#[test]
+fn healthy_engine_is_loaded() {
+    let results = run_engine(&fake("healthy", "ok", 10.0)).unwrap();
+    assert_eq!(results.flat().len(), 3);
+}
+
+#[test]
+fn hanging_engine_times_out() {
+    let start = std::time::Instant::now();
+    let error = run_engine(&fake("hang", "hang", 0.5)).unwrap_err();
+    assert_eq!(error, EngineError::Timeout { after_secs: 0.5 });
+    assert!(start.elapsed() < std::time::Duration::from_secs(5));
+}
+
+#[test]
+fn crashing_engine_records_status_and_stderr() {
+    match run_engine(&fake("crash", "crash", 10.0)).unwrap_err() {
+        EngineError::Crashed { status, stderr } => {
+            assert_eq!(status, Some(3));
+            assert!(stderr.contains("simulated crash"), "stderr: {}", stderr);
+        }
+        other => panic!("expected a crash, got {:?}", other),
+    }
+}
+
+#[test]
+fn malformed_results_are_rejected() {
+    let error = run_engine(&fake("malformed", "malformed", 10.0)).unwrap_err();
+    assert!(matches!(error, EngineError::MalformedResults { .. }), "{:?}", error);
+}
+
+#[test]
+fn missing_executable_is_a_spawn_error() {
+    let mut spec = fake("missing", "ok", 10.0);
+    spec.command[0] = "/nonexistent/engine".to_string();
+    let error = run_engine(&spec).unwrap_err();
+    assert!(matches!(error, EngineError::Spawn { .. }), "{:?}", error);
+}
+
+#[test]
+fn unusable_timeouts_are_spawn_errors() {
+    for timeout_secs in [-1.0, 0.0, f64::INFINITY, f64::NAN, 1e300] {
+        let error = run_engine(&fake("bad-timeout", "ok", timeout_secs)).unwrap_err();
+        assert!(matches!(error, EngineError::Spawn { .. }), "{}: {:?}", timeout_secs, error);
+    }
+}
+
+#[test]
+fn failures_do_not_prevent_a_partial_merge() {
+    let merged = orchestrate(&[
+        fake("merge-ok", "ok", 10.0),
+        fake("merge-short", "wrong-counts", 10.0),
+        fake("merge-crash", "crash", 10.0),
+        fake("merge-hang", "hang", 0.5),
+    ]);
+
+    let engines: Vec<&str> = merged.engines.keys().map(String::as_str).collect();
+    assert_eq!(engines, ["merge-ok", "merge-short"]);
+    assert_eq!(
+        merged.errors["merge-short"],
+        [EngineError::MissingBenchmarks {
+            names: vec!["b".to_string(), "c".to_string()]
+        }]
+    );
+    assert!(matches!(merged.errors["merge-crash"][..], [EngineError::Crashed { .. }]));
+    assert!(matches!(merged.errors["merge-hang"][..], [EngineError::Timeout { .. }]));
+    assert!(!merged.errors.contains_key("merge-ok"));
+}
@@ -0,0 +1,67 @@
+use mojo_regex_rust_bench::cross_engine::Row;
+use mojo_regex_rust_bench::plot::{file_stem, grouped_bars, ratio_scatter, time_series};
+use mojo_regex_rust_bench::trend::{step_regressions, TrendPoint};
+
+fn row(name: &str, times_ns: [Option<f64>; 2]) -> Row {
+    Row {
+        name: name.to_string(),
+        times_ns: times_ns.to_vec(),
+        match_counts: vec![None; 2],
+    }
+}
+
+#[test]
+fn bars_are_scaled_to_the_slowest_engine_of_each_benchmark() {
+    let rows = [row("a<b", [Some(100.0), Some(25.0)]), row("rust_only", [Some(1e6), None])];
+    let svg = grouped_bars("literal", &rows.iter().collect::<Vec<_>>(), &["rust", "mojo"]);
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.contains(">a&lt;b</text>"));
+    assert!(svg.contains("width=\"360.0\" height=\"9\" fill=\"#4e79a7\"><title>rust: 100.0 ns</title>"));
+    assert!(svg.contains("width=\"90.0\" height=\"9\" fill=\"#f28e2b\"><title>mojo: 25.0 ns</title>"));
+    assert_eq!(svg.matches("</title></rect>").count(), 3);
+}
+
+#[test]
+fn scatter_has_a_point_per_benchmark_both_engines_measured() {
+    let rows = [
+        row("faster", [Some(100.0), Some(50.0)]),
+        row("slower", [Some(1000.0), Some(4000.0)]),
+        row("rust_only", [Some(10.0), None]),
+    ];
+    let svg = ratio_scatter("Mojo/Rust", &rows, 1, 0);
+
+    assert_eq!(svg.matches("<circle").count(), 2);
+    assert!(svg.contains("<title>slower: 4.00x at 1.00 µs</title>"));
+    assert!(svg.contains(">1.0x</text>"));
+    assert!(ratio_scatter("Mojo/Rust", &rows[2..], 1, 0).contains("no benchmarks in common"));
+}
+
+#[test]
+fn time_series_marks_the_regressed_runs() {
+    let points: Vec<TrendPoint> = [100.0, 102.0, 130.0, 129.0]
+        .iter()
+        .enumerate()
+        .map(|(run, &time_ns)| TrendPoint {
+            git_sha: format!("abcdef{}0123", run),
+            engine: "rust".to_string(),
+            timestamp: format!("2026-01-0{}T00:00:00+00:00", run + 1),
+            time_ns,
+            throughput_mb_s: None,
+        })
+        .collect();
+
+    let regressions = step_regressions(&points, 10.0);
+    assert_eq!(regressions, [2]);
+    let svg = time_series("dfa_dot_phone", &points, &regressions);
+    assert_eq!(svg.matches("<circle").count(), 4);
+    assert_eq!(svg.matches("r=\"5\" fill=\"#e15759\"").count(), 1);
+    assert!(svg.contains("<title>abcdef2 2026-01-03T00:00:00+00:00: 130.0 ns (+27.5%)</title>"));
+}
+
+#[test]
+fn file_stems_stay_inside_the_plots_directory() {
+    assert_eq!(file_stem("literal_match_short"), "literal_match_short");
+    assert_eq!(file_stem("../../etc/passwd"), "______etc_passwd");
+    assert_eq!(file_stem("ci/ascii"), "ci_ascii");
+}
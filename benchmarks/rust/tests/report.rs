@@ -0,0 +1,184 @@
+use mojo_regex_rust_bench::compare::{self, Thresholds};
+use mojo_regex_rust_bench::report::{html, junit, markdown, pr_comment};
+use mojo_regex_rust_bench::{BenchmarkResult, BenchmarkResults};
+
+mod common;
+
+fn result(index: usize, time_ns: f64) -> BenchmarkResult {
+    BenchmarkResult {
+        index: Some(index),
+        time_ns,
+        iterations: 10,
+        ..Default::default()
+    }
+}
+
+fn results(categories: Vec<(&str, Vec<(&str, BenchmarkResult)>)>) -> BenchmarkResults {
+    common::results("rust", "2026-01-01T00:00:00Z", categories)
+}
+
+#[test]
+fn categories_and_rows_follow_registration_order() {
+    let report = markdown(&results(vec![
+        ("zeta", vec![("z_first", result(0, 1.0)), ("a_second", result(1, 1.0))]),
+        ("alpha", vec![("later", result(2, 1.0))]),
+    ]));
+    let position = |needle: &str| report.find(needle).unwrap();
+    assert!(position("### zeta") < position("### alpha"));
+    assert!(position("| z_first ") < position("| a_second "));
+}
+
+#[test]
+fn pipes_are_escaped_and_unmeasured_rows_marked() {
+    let report = markdown(&results(vec![(
+        "literal",
+        vec![("a|b", result(0, 1.0)), ("skipped_one", BenchmarkResult::skipped())],
+    )]));
+    assert!(report.contains("| a\\|b | 1.0 |"));
+    assert!(report.contains("| skipped_one | skipped | - | - | - |"));
+}
+
+#[test]
+fn html_report_is_self_contained_and_escaped() {
+    let mut sampled = result(0, 2.0);
+    sampled.samples = Some(vec![1.0, 2.0, 2.0, 3.0]);
+    let report = html(&results(vec![("literal", vec![("<b>", sampled)])]));
+    assert!(report.starts_with("<!DOCTYPE html>"));
+    assert!(!report.contains("<script"));
+    assert!(report.contains("&lt;b&gt;"));
+    assert!(report.contains("class=\"bar\""));
+    assert!(report.contains("class=\"dist\""));
+}
+
+#[test]
+fn criterion_tree_has_estimates_for_measured_benchmarks() {
+    let dir = std::env::temp_dir().join(format!("criterion-{}", std::process::id()));
+    let mut measured = result(0, 200.0);
+    measured.throughput_mb_s = Some(500.0);
+    let written = mojo_regex_rust_bench::criterion::export(
+        &results(vec![("literal", vec![("fast", measured), ("slow", BenchmarkResult::timed_out())])]),
+        &dir,
+        "nightly",
+    )
+    .unwrap();
+    assert_eq!(written, 1);
+
+    let base = dir.join("literal/fast/nightly");
+    let read = |file: &str| -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(base.join(file)).unwrap()).unwrap()
+    };
+    let benchmark = read("benchmark.json");
+    let estimates = read("estimates.json");
+    assert!(!dir.join("literal/slow").exists());
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(benchmark["full_id"], "literal/fast");
+    assert_eq!(benchmark["throughput"]["Bytes"], 100);
+    assert_eq!(estimates["median"]["point_estimate"], 200.0);
+    assert_eq!(estimates["mean"]["confidence_interval"]["confidence_level"], 0.95);
+    assert!(estimates["slope"].is_null());
+}
+
+#[test]
+fn brief_lists_the_worst_regressions_against_the_baseline() {
+    let baseline = results(vec![(
+        "literal",
+        (0..5).map(|i| (["a", "b", "c", "d", "e"][i], result(i, 100.0))).collect(),
+    )]);
+    let current = results(vec![(
+        "literal",
+        vec![
+            ("a", result(0, 110.0)),
+            ("b", result(1, 150.0)),
+            ("c", result(2, 90.0)),
+            ("d", result(3, 120.0)),
+            ("e", result(4, 105.0)),
+        ],
+    )]);
+    let brief = mojo_regex_rust_bench::report::brief(&current, Some(&baseline));
+
+    assert!(brief.starts_with("rust benchmarks, 2026-01-01T00:00:00Z: 5 measured\n"), "{}", brief);
+    assert!(brief.contains("Geomean vs baseline"));
+    let regressions: Vec<&str> = brief
+        .lines()
+        .skip_while(|line| *line != "Top 3 regressions:")
+        .skip(1)
+        .take_while(|line| line.starts_with("  "))
+        .map(|line| line.split_whitespace().nth(1).unwrap())
+        .collect();
+    assert_eq!(regressions, ["b", "d", "a"]);
+    assert!(!brief.contains("Environment notes"));
+}
+
+#[test]
+fn google_benchmark_json_names_by_category_and_flags_unmeasured() {
+    let mut fast = result(0, 250.0);
+    fast.throughput_mb_s = Some(4.0);
+    let json = mojo_regex_rust_bench::report::google_benchmark(&results(vec![(
+        "literal",
+        vec![("fast", fast), ("slow", BenchmarkResult::skipped())],
+    )]));
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let benchmarks = parsed["benchmarks"].as_array().unwrap();
+
+    assert_eq!(benchmarks.len(), 2);
+    assert_eq!(benchmarks[0]["name"], "literal/fast");
+    assert_eq!(benchmarks[0]["real_time"], 250.0);
+    assert_eq!(benchmarks[0]["cpu_time"], 250.0);
+    assert_eq!(benchmarks[0]["time_unit"], "ns");
+    assert_eq!(benchmarks[0]["bytes_per_second"], 4e6);
+    assert!(benchmarks[0].get("error_occurred").is_none());
+    assert_eq!(benchmarks[1]["error_occurred"], true);
+    assert_eq!(parsed["context"]["date"], "2026-01-01T00:00:00Z");
+}
+
+#[test]
+fn junit_reports_regressions_and_broken_criteria_as_failures() {
+    let mut wrong = result(2, 1.0);
+    wrong.observed_matches = Some(0);
+    wrong.expected_matches = Some(3);
+    wrong.correct = Some(false);
+    let candidate = results(vec![(
+        "a<b",
+        vec![
+            ("steady", result(0, 1000.0)),
+            ("slower", result(1, 2000.0)),
+            ("wrong", wrong),
+            ("skipped_one", BenchmarkResult::skipped()),
+            ("hung", BenchmarkResult::timed_out()),
+        ],
+    )]);
+    let baseline = results(vec![(
+        "a<b",
+        vec![("steady", result(0, 1000.0)), ("slower", result(1, 1000.0))],
+    )]);
+
+    let regressions = compare::regressions(&compare::compare(&baseline, &candidate, 0.01), 10.0, &Thresholds::new());
+    let xml = junit(&candidate, &regressions);
+    assert!(xml.contains(
+        r#"<testsuite name="a&lt;b" tests="5" failures="2" errors="1" skipped="1" time="0.000003001""#
+    ));
+    assert!(xml.contains(r#"<testcase classname="rust.a&lt;b" name="steady" time="0.000001000"/>"#));
+    assert!(xml.contains(r#"<failure message="slower: +100.0% vs baseline (1000.0 -&gt; 2000.0 ns, max +10.0%)" type="regression">"#));
+    assert!(xml.contains(r#"<failure message="wrong: reported 0 matches (expected 3)" type="criteria">"#));
+    assert!(xml.contains(r#"<skipped message="time budget exhausted"/>"#));
+    assert!(xml.contains(r#"<error message="timed out" type="timeout"/>"#));
+
+    // Without a baseline only the wrong match count fails
+    assert!(junit(&candidate, &[]).contains(r#"failures="1""#));
+}
+
+#[test]
+fn pr_comment_leads_with_the_geomean_and_folds_the_full_table() {
+    let baseline = results(vec![("literal", vec![("slow|er", result(0, 100.0)), ("faster", result(1, 100.0))])]);
+    let candidate = results(vec![("literal", vec![("slow|er", result(0, 400.0)), ("faster", result(1, 100.0))])]);
+    let comment = pr_comment(&baseline, &candidate);
+
+    assert!(comment.contains("**Geomean: 2.000x (+100.0%)** over 2 benchmarks"));
+    let position = |needle: &str| comment.find(needle).unwrap();
+    assert!(position("#### Top 5 regressions") < position("<details>"));
+    assert!(comment.contains("| slow\\|er | 100.0 ns | 400.0 ns | **+300.0%** |"));
+    assert!(!comment.contains("#### Top 5 improvements"));
+    assert!(comment.contains("<summary>All 2 benchmarks</summary>"));
+    assert!(comment.trim_end().ends_with("</details>"));
+}
@@ -0,0 +1,56 @@
+use mojo_regex_rust_bench::{BenchmarkResult, BenchmarkResults, LEGACY_SCHEMA_VERSION, SCHEMA_VERSION};
+
+fn load(name: &str, json: &str) -> Result<BenchmarkResults, Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("schema-{}-{}.json", name, std::process::id()));
+    let path = path.to_str().unwrap();
+    std::fs::write(path, json).unwrap();
+    let results = BenchmarkResults::load(path);
+    std::fs::remove_file(path).unwrap();
+    results
+}
+
+#[test]
+fn unversioned_files_load_as_legacy() {
+    // Python harness layout: flat, no schema_version, time_ms only
+    let results = load(
+        "legacy",
+        r#"{"engine": "python", "results": {"literal_match_short": {"time_ms": 0.0005, "iterations": 10}}}"#,
+    )
+    .unwrap();
+    assert_eq!(results.schema_version, LEGACY_SCHEMA_VERSION);
+    assert_eq!(results.timestamp, "");
+    let result = results.flat()["literal_match_short"];
+    assert!((result.time_ns - 500.0).abs() < 1e-9);
+    assert_eq!(result.iterations, 10);
+}
+
+#[test]
+fn current_version_round_trips_and_newer_is_rejected() {
+    let current = format!(
+        r#"{{"schema_version": {}, "engine": "rust", "timestamp": "t",
+            "results": {{"literal": {{"literal_match_short": {{"time_ns": 42.0}}}}}}}}"#,
+        SCHEMA_VERSION
+    );
+    let results = load("current", &current).unwrap();
+    assert_eq!(results.schema_version, SCHEMA_VERSION);
+    assert_eq!(results.flat()["literal_match_short"].time_ms, 42.0 / 1e6);
+    assert!(serde_json::to_string(&results)
+        .unwrap()
+        .contains(&format!(r#""schema_version":{}"#, SCHEMA_VERSION)));
+
+    let newer = current.replace(
+        &format!(r#""schema_version": {}"#, SCHEMA_VERSION),
+        &format!(r#""schema_version": {}"#, SCHEMA_VERSION + 1),
+    );
+    let error = load("newer", &newer).unwrap_err().to_string();
+    assert!(error.contains("only reads up to"), "{}", error);
+}
+
+#[test]
+fn unmeasured_results_carry_no_timing() {
+    let json = serde_json::to_string(&BenchmarkResult::skipped()).unwrap();
+    assert!(json.contains(r#""skipped":true"#), "{}", json);
+    assert!(!json.contains("time_ns") && !json.contains("time_ms"), "{}", json);
+    let back: BenchmarkResult = serde_json::from_str(&json).unwrap();
+    assert!(!back.is_measured());
+}
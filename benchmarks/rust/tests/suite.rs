@@ -0,0 +1,360 @@
+use mojo_regex_rust_bench::engine_path::EnginePath;
+use mojo_regex_rust_bench::suite::{all_benchmarks, build_regex, gated_len, ASCII_ONLY, GATED_CATEGORIES};
+use std::collections::HashSet;
+
+#[test]
+fn definitions_are_unique_and_complete() {
+    let definitions = all_benchmarks();
+    assert!(definitions.len() > 90);
+
+    let names: HashSet<&str> = definitions.iter().map(|def| def.name.as_str()).collect();
+    assert_eq!(names.len(), definitions.len(), "benchmark names must be unique");
+    assert!(definitions.iter().all(|def| !def.category.is_empty()));
+    assert!(definitions.windows(2).all(|pair| pair[0].index < pair[1].index));
+}
+
+#[test]
+fn indices_count_feature_gated_benchmarks_whether_built_or_not() {
+    let definitions = all_benchmarks();
+    let mut full_len = definitions.len();
+    for (category, len) in GATED_CATEGORIES {
+        match definitions.iter().filter(|def| def.category == category).count() {
+            0 => full_len += len,
+            built => assert_eq!(built, len, "{} registers {} benchmarks", category, built),
+        }
+    }
+    assert_eq!(definitions.last().map(|def| def.index + 1), Some(full_len));
+    assert_eq!(gated_len("literal"), 0);
+}
+
+#[test]
+fn pattern_benchmarks_carry_expected_counts() {
+    let definitions = all_benchmarks();
+    let literal = definitions
+        .iter()
+        .find(|def| def.name == "literal_match_short")
+        .expect("literal_match_short is registered");
+    assert_eq!(literal.category, "literal");
+    assert_eq!(literal.pattern.as_deref(), Some("hello"));
+    assert_eq!(literal.operation, "Search");
+    assert_eq!(literal.match_count, Some(1));
+}
+
+#[test]
+fn exported_definitions_describe_categories_and_haystacks() {
+    let exported = mojo_regex_rust_bench::suite::export_definitions();
+    assert_eq!(exported.categories[0].name, "literal");
+    assert_eq!(exported.categories[0].title, "Literal Matching Benchmarks");
+    assert_eq!(exported.benchmarks.len(), all_benchmarks().len());
+
+    let sub = exported
+        .benchmarks
+        .iter()
+        .find(|def| def.name == "sub_limited_count")
+        .expect("sub_limited_count is registered");
+    assert_eq!(
+        sub.haystack.as_deref(),
+        Some("\"hello world this is a test with hello again and hello there\" x 100 (5900 bytes, ASCII)")
+    );
+    assert_eq!(
+        mojo_regex_rust_bench::describe_haystack(&mojo_regex_rust_bench::make_test_string(8, "abc")),
+        "\"abc\" x 2 + 2 bytes (8 bytes, ASCII)"
+    );
+}
+
+#[test]
+fn benchmarks_are_tagged_with_the_engine_path_they_exercise() {
+    let definitions = all_benchmarks();
+    let path = |name: &str| {
+        definitions
+            .iter()
+            .find(|def| def.name == name)
+            .unwrap_or_else(|| panic!("{} is registered", name))
+            .engine_path
+    };
+    assert_eq!(path("dfa_dot_phone"), Some(EnginePath::PureDfa));
+    assert_eq!(path("simd_alphanumeric_large"), Some(EnginePath::SimdCharClass));
+    assert_eq!(path("literal_prefix_long"), Some(EnginePath::LiteralOptimizer));
+    // Overridden within its category
+    assert_eq!(path("single_quantifier_digits"), Some(EnginePath::PureDfa));
+    assert_eq!(path("grouped_quantifiers"), Some(EnginePath::Hybrid));
+    assert_eq!(path("sub_literal"), None);
+    assert_eq!(path("sub_group_word_swap"), Some(EnginePath::NfaBacktracker));
+    // Tags don't leak into the next, untagged category
+    assert_eq!(path("sparse_phone_findall"), None);
+}
+
+#[test]
+fn capture_benchmarks_extract_groups_from_every_match() {
+    let definitions = all_benchmarks();
+    let captures: Vec<_> =
+        definitions.iter().filter(|def| def.operation == "Captures" && def.category == "captures").collect();
+    assert_eq!(
+        captures.iter().map(|def| (def.name.as_str(), def.match_count)).collect::<Vec<_>>(),
+        [("phone_validation_captures", Some(1)), ("phone_captures_iter", Some(1000)), ("email_captures_iter", Some(4))]
+    );
+}
+
+#[test]
+fn replace_benchmarks_cover_literal_template_and_closure_replacements() {
+    let definitions = all_benchmarks();
+    let replace: Vec<&str> =
+        definitions.iter().filter(|def| def.operation.starts_with("Replace")).map(|def| def.name.as_str()).collect();
+    assert_eq!(
+        replace,
+        [
+            "replace_email_literal",
+            "replace_email_template",
+            "replace_email_closure",
+            "replace_phone_template",
+            "replace_phone_closure",
+            "redact_tickets_10mb"
+        ]
+    );
+}
+
+#[test]
+fn split_benchmarks_tokenize_large_texts() {
+    let definitions = all_benchmarks();
+    let split: Vec<_> = definitions.iter().filter(|def| def.category == "split").collect();
+    assert_eq!(split.len(), 5);
+    assert!(split.iter().all(|def| def.haystack_len >= 90_000 && def.match_count > Some(10_000)));
+    assert_eq!(split.iter().filter(|def| def.operation == "SplitN").count(), 2);
+}
+
+#[test]
+fn count_benchmarks_pair_with_a_collecting_findall() {
+    let definitions = all_benchmarks();
+    let counts: Vec<_> = definitions.iter().filter(|def| def.operation == "Count").collect();
+    assert!(!counts.is_empty());
+    for count in counts {
+        let family = count.name.strip_suffix("_count").expect("count benchmarks end in _count");
+        let findall = definitions
+            .iter()
+            .find(|def| def.name == format!("{}_findall", family))
+            .unwrap_or_else(|| panic!("{} has a FindAll twin", count.name));
+        assert_eq!((&findall.operation[..], &findall.pattern), ("FindAll", &count.pattern));
+        assert_eq!((findall.haystack_len, findall.match_count), (count.haystack_len, count.match_count));
+    }
+}
+
+#[test]
+fn case_insensitive_benchmarks_run_on_ascii_and_unicode_text() {
+    let definitions = all_benchmarks();
+    let count = |name: &str| definitions.iter().find(|def| def.name == name).and_then(|def| def.match_count);
+    // "HELLO" and "hello" in the ASCII text, only "HELLO" in the Unicode one
+    assert_eq!(count("ci_literal_ascii"), Some(1000));
+    assert_eq!(count("ci_literal_unicode"), Some(500));
+    // "Straße" and "STRAẞE" fold together; "Strasse" doesn't under simple folding
+    assert_eq!(count("ci_sharp_s_folding"), Some(1000));
+}
+
+#[test]
+fn generated_logs_start_lines_with_levels_and_backtrace_frames() {
+    let log = mojo_regex_rust_bench::make_log_text(20);
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(lines.len(), 21);
+    assert!(lines[0].starts_with("INFO ") && lines[0].ends_with("ms"));
+    assert!(lines[7].starts_with("ERROR "));
+    assert!(lines[8].starts_with("0: ") && lines[9].starts_with("1: "));
+    assert_eq!(lines[20], "shutdown complete");
+}
+
+#[test]
+fn backreference_and_lookaround_benchmarks_only_run_with_fancy_regex() {
+    let definitions = all_benchmarks();
+    for category in ["backreference", "lookaround"] {
+        let fancy: Vec<_> = definitions.iter().filter(|def| def.category == category).collect();
+        assert_eq!(!fancy.is_empty(), cfg!(feature = "fancy"), "{}", category);
+        assert!(fancy.iter().all(|def| def.operation == "FindAll" && def.match_count > Some(0)));
+    }
+}
+
+#[test]
+fn raw_byte_benchmarks_scan_invalid_utf8() {
+    let log = mojo_regex_rust_bench::encoding::make_binary_log(10);
+    assert!(std::str::from_utf8(&log).is_err());
+    let definitions = all_benchmarks();
+    let high_bytes = definitions
+        .iter()
+        .find(|def| def.name == "bytes_high_bytes")
+        .expect("bytes_high_bytes is registered");
+    // Three runs of non-ASCII bytes between every two records
+    assert_eq!((high_bytes.category.as_str(), high_bytes.match_count), ("raw_bytes", Some(6000)));
+}
+
+#[test]
+fn lexer_benchmarks_find_the_same_tokens_either_way() {
+    let definitions = all_benchmarks();
+    let lexer = |name: &str| {
+        let def = definitions.iter().find(|def| def.name == name).unwrap_or_else(|| panic!("{} is registered", name));
+        (def.pattern.clone(), def.match_count)
+    };
+    assert_eq!(lexer("lexer_find_at"), lexer("lexer_anchored"));
+    assert_eq!(lexer("lexer_anchored").1, Some(27000));
+    // Rules tried one by one or all at once
+    assert_eq!(lexer("lexer_ordered_rules"), lexer("lexer_multi_pattern"));
+}
+
+#[test]
+fn early_exit_benchmarks_sweep_the_needle_position() {
+    let definitions = all_benchmarks();
+    let early_exit: Vec<_> = definitions.iter().filter(|def| def.category == "early_exit").collect();
+    assert_eq!(early_exit.len(), 9);
+    assert!(early_exit.iter().all(|def| def.match_count == Some(1)));
+    assert_eq!(early_exit.iter().filter(|def| def.operation == "ShortestMatch").count(), 3);
+}
+
+#[test]
+fn pathological_benchmarks_fail_on_purpose_without_lint_errors() {
+    let definitions = all_benchmarks();
+    let pathological: Vec<_> = definitions.iter().filter(|def| def.category == "pathological").collect();
+    assert_eq!(pathological.len(), 12);
+    assert!(pathological.iter().all(|def| def.match_count == Some(0) && def.expects_no_match));
+    let categories = vec!["pathological".to_string()];
+    let owned: Vec<_> = pathological.into_iter().cloned().collect();
+    let issues = mojo_regex_rust_bench::lint::lint(&owned, &categories);
+    assert!(issues.is_empty(), "{:?}", issues);
+}
+
+#[test]
+fn the_whole_suite_lints_clean() {
+    let suite = mojo_regex_rust_bench::suite::export_definitions();
+    let categories: Vec<String> = suite.categories.iter().map(|category| category.name.clone()).collect();
+    let issues = mojo_regex_rust_bench::lint::lint(&suite.benchmarks, &categories);
+    assert!(issues.is_empty(), "{:?}", issues);
+    // Only builder options tell these twins apart
+    let def = |name: &str| suite.benchmarks.iter().find(|def| def.name == name).expect("benchmark is registered");
+    assert_eq!(def("class_word_unicode").builder, None);
+    assert_eq!(def("class_word_ascii").builder.as_deref(), Some("unicode(false)"));
+}
+
+#[test]
+fn empty_match_benchmarks_match_once_per_character_boundary() {
+    let definitions = all_benchmarks();
+    let def = |name: &str| definitions.iter().find(|def| def.name == name).expect("benchmark is registered");
+    // One empty match per char boundary, counting both ends, never inside a character
+    let ascii = def("empty_star_ascii");
+    assert_eq!(ascii.match_count, Some(ascii.haystack_len + 1));
+    let unicode = def("empty_star_unicode");
+    assert!(unicode.match_count < Some(unicode.haystack_len));
+}
+
+#[cfg(feature = "overlapping")]
+#[test]
+fn overlapping_benchmarks_find_at_least_the_leftmost_matches() {
+    let definitions = all_benchmarks();
+    let count = |name: String| definitions.iter().find(|def| def.name == name).and_then(|def| def.match_count);
+    for family in ["aba", "tata_box", "at_rich"] {
+        let leftmost = count(format!("{}_leftmost", family));
+        let overlapping = count(format!("{}_overlapping", family));
+        assert!(leftmost.is_some() && overlapping > leftmost, "{}", family);
+    }
+}
+
+#[test]
+fn leftmost_longest_benchmarks_pair_with_leftmost_first_ones() {
+    let definitions = all_benchmarks();
+    let semantics: Vec<_> = definitions.iter().filter(|def| def.category == "match_semantics").collect();
+    assert_eq!(semantics.len(), 6);
+    for pair in semantics.chunks(2) {
+        assert_eq!((pair[0].operation.as_str(), pair[1].operation.as_str()), ("FindAll", "FindAllLongest"));
+        assert_eq!(pair[0].pattern, pair[1].pattern);
+    }
+}
+
+#[test]
+fn group_benchmarks_have_non_capturing_twins() {
+    let definitions = all_benchmarks();
+    let groups: Vec<_> = definitions.iter().filter(|def| def.category == "group").collect();
+    for group in groups.iter().filter(|def| !def.name.ends_with("_noncap")) {
+        let twin = groups
+            .iter()
+            .find(|def| def.name == format!("{}_noncap", group.name))
+            .unwrap_or_else(|| panic!("{} has a non-capturing twin", group.name));
+        assert_eq!(
+            (&twin.operation, twin.haystack_len, twin.match_count),
+            (&group.operation, group.haystack_len, group.match_count)
+        );
+        let pattern = twin.pattern.as_deref().unwrap_or_default();
+        assert!(pattern.split('(').skip(1).all(|group| group.starts_with("?:")), "{}", pattern);
+    }
+}
+
+#[cfg(feature = "streaming")]
+#[test]
+fn streamed_searches_find_matches_across_chunk_boundaries() {
+    let definitions = all_benchmarks();
+    let streaming: Vec<_> = definitions.iter().filter(|def| def.category == "streaming").collect();
+    assert_eq!(streaming.len(), 6);
+    // Over 1MB of log, so some of the 2000 matches straddle a 4KB boundary
+    assert!(streaming.iter().all(|def| def.haystack_len > 1 << 20 && def.match_count == Some(2000)));
+}
+
+#[test]
+fn unicode_class_benchmarks_agree_with_their_ascii_twins_on_ascii_text() {
+    let definitions = all_benchmarks();
+    let count = |name: String| definitions.iter().find(|def| def.name == name).and_then(|def| def.match_count);
+    for family in ["class_word", "class_digits", "class_lowercase"] {
+        let unicode = count(format!("{}_unicode", family));
+        assert!(unicode.is_some() && unicode == count(format!("{}_ascii", family)), "{}", family);
+    }
+}
+
+#[test]
+fn redaction_covers_every_sensitive_value_of_a_multi_megabyte_document() {
+    let definitions = all_benchmarks();
+    let redact = definitions.iter().find(|def| def.name == "redact_tickets_10mb").expect("redaction benchmark");
+    assert!(redact.haystack_len >= 10 << 20);
+    // An email, a phone number and a card number on each of the 75000 tickets
+    assert_eq!(redact.match_count, Some(3 * 75_000));
+}
+
+#[cfg(feature = "engines")]
+#[test]
+fn engine_breakdown_runs_every_pattern_through_every_engine() {
+    let definitions = all_benchmarks();
+    let breakdown: Vec<_> = definitions.iter().filter(|def| def.category == "engine_breakdown").collect();
+    assert_eq!(breakdown.len(), 15);
+    for (family, expected) in [("engines_phone", 200), ("engines_email", 200), ("engines_fruit", 600)] {
+        let engines: Vec<_> = breakdown.iter().filter(|def| def.name.starts_with(family)).collect();
+        assert_eq!(engines.len(), 5, "{}", family);
+        assert!(engines.iter().all(|def| def.match_count == Some(expected) && def.pattern == engines[0].pattern));
+    }
+}
+
+#[test]
+fn keyword_alternations_agree_with_aho_corasick() {
+    let definitions = all_benchmarks();
+    let count = |name: String| definitions.iter().find(|def| def.name == name).and_then(|def| def.match_count);
+    for size in [10, 100, 1000] {
+        let alternation = count(format!("keywords_{}_regex", size));
+        assert!(alternation.is_some_and(|matches| matches > 0), "{}", size);
+        assert_eq!(alternation, count(format!("keywords_{}_aho", size)), "{}", size);
+    }
+}
+
+#[test]
+fn ascii_class_variants_are_rebuilt_without_unicode() {
+    let definitions = all_benchmarks();
+    let builder = |name: &str| definitions.iter().find(|def| def.name == name).unwrap().builder.clone();
+    assert_eq!(builder("class_word_unicode"), None);
+    assert_eq!(builder("class_word_ascii").as_deref(), Some(ASCII_ONLY));
+
+    // What the cold phases compile for each twin
+    let unicode = build_regex(r"\w+", builder("class_word_unicode").as_deref()).unwrap();
+    let ascii = build_regex(r"\w+", builder("class_word_ascii").as_deref()).unwrap();
+    assert!(unicode.is_match("é") && !ascii.is_match("é"));
+    // so their compile phases differ: Unicode's \w is hundreds of ranges
+    let compile_ns = |builder: Option<&str>| {
+        (0..10)
+            .map(|_| {
+                let start = std::time::Instant::now();
+                build_regex(r"\w+", builder).unwrap();
+                start.elapsed()
+            })
+            .min()
+            .unwrap()
+    };
+    assert!(compile_ns(None) > compile_ns(Some(ASCII_ONLY)) * 2);
+}
@@ -0,0 +1,127 @@
+use mojo_regex_rust_bench::summary::{format_ns, table, Grouping, SortKey, TableOptions};
+use mojo_regex_rust_bench::{Aggregates, BenchmarkResult, BenchmarkResults};
+
+mod common;
+
+fn results(categories: Vec<(&str, Vec<(&str, BenchmarkResult)>)>) -> BenchmarkResults {
+    common::results("rust", "", categories)
+}
+
+fn result(index: usize, time_ns: f64) -> BenchmarkResult {
+    BenchmarkResult {
+        index: Some(index),
+        time_ns,
+        iterations: 10,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn times_use_the_largest_unit_at_or_above_one() {
+    assert_eq!(format_ns(281.04), "281.0 ns");
+    assert_eq!(format_ns(2810.6), "2.81 µs");
+    assert_eq!(format_ns(7_185_200.0), "7.19 ms");
+    assert_eq!(format_ns(2.5e9), "2.50 s");
+}
+
+#[test]
+fn rows_are_grouped_by_category_with_subtotals() {
+    let counted = BenchmarkResult {
+        throughput_mb_s: Some(1234.56),
+        observed_matches: Some(125),
+        ..result(1, 4000.0)
+    };
+    let rows = table(
+        &results(vec![
+            ("phone", vec![("plain", result(0, 1000.0)), ("counted", counted)]),
+            ("literal", vec![("late", result(2, 1.0)), ("skipped_one", BenchmarkResult::skipped())]),
+        ]),
+        &TableOptions::default(),
+        None,
+    );
+    let lines: Vec<&str> = rows.lines().collect();
+    assert!(lines[2].starts_with("| phone "));
+    assert!(lines[3].starts_with("|   plain "));
+    assert!(lines[4].ends_with("|      4.00 µs |    1234.6 |      125 |       10 |"));
+    assert!(lines[5].starts_with("|   phone geomean ") && lines[5].contains(" 2.00 µs |"));
+    assert!(lines[6].starts_with("| literal "));
+    assert!(lines[8].contains("skipped"));
+    assert!(lines.last().unwrap().starts_with("| overall geomean "));
+
+    let mut instructions = results(vec![("phone", vec![("plain", result(0, 1500.0))])]);
+    instructions.clock = Some("instructions (instructions)".to_string());
+    assert!(table(&instructions, &TableOptions::default(), None).contains(" 1500.0 instructions |"));
+}
+
+#[test]
+fn compile_times_stay_out_of_the_overall_geomean() {
+    let compile = BenchmarkResult {
+        operation: Some("Compile".to_string()),
+        ..result(2, 1e6)
+    };
+    let results = results(vec![
+        ("phone", vec![("plain", result(0, 1000.0)), ("slow", result(1, 4000.0))]),
+        ("compile", vec![("compile_phone", compile)]),
+    ]);
+    let aggregates = Aggregates::from_results(&results.results).expect("benchmarks were measured");
+    assert!((aggregates.geomean_ns - 2000.0).abs() < 1e-6);
+    assert!((aggregates.categories["compile"] - 1e6).abs() < 1e-3);
+}
+
+#[test]
+fn rows_can_be_sorted_and_grouped_by_family() {
+    let throughput = |index, time_ns, mb_s| BenchmarkResult {
+        throughput_mb_s: Some(mb_s),
+        ..result(index, time_ns)
+    };
+    let results = results(vec![
+        ("dfa", vec![("dfa_fast", throughput(0, 10.0, 900.0)), ("dfa_slow", throughput(1, 300.0, 30.0))]),
+        (
+            "phone",
+            vec![
+                ("phone_mid", throughput(2, 20.0, 450.0)),
+                ("dfa_skipped", BenchmarkResult { index: Some(3), ..BenchmarkResult::skipped() }),
+            ],
+        ),
+    ]);
+    let names = |sort, group| -> Vec<String> {
+        table(&results, &TableOptions { sort, group, color: false }, None)
+            .lines()
+            .skip(2)
+            .map(|line| line.split('|').nth(1).unwrap().trim().to_string())
+            .collect()
+    };
+
+    assert_eq!(
+        names(SortKey::Time, Grouping::None),
+        ["dfa_slow", "phone_mid", "dfa_fast", "dfa_skipped", "overall geomean"]
+    );
+    assert_eq!(
+        names(SortKey::Throughput, Grouping::Family),
+        ["dfa", "dfa_slow", "dfa_fast", "dfa_skipped", "dfa geomean", "phone", "phone_mid", "phone geomean", "overall geomean"]
+    );
+    assert_eq!(names(SortKey::Name, Grouping::Category)[..3], ["dfa", "dfa_fast", "dfa_slow"]);
+}
+
+#[test]
+fn baseline_deltas_are_colored_with_top_regressions_in_bold() {
+    let run = |times: [f64; 5]| {
+        let names = ["a", "b", "c", "d", "e"];
+        let benches = names.iter().zip(times).enumerate().map(|(i, (name, t))| (*name, result(i, t))).collect();
+        results(vec![("phone", benches)])
+    };
+    let baseline = run([100.0; 5]);
+    let candidate = run([150.0, 140.0, 130.0, 120.0, 50.0]);
+
+    let plain = table(&candidate, &TableOptions::default(), Some(&baseline));
+    assert!(plain.lines().next().unwrap().ends_with("|  vs base |"));
+    assert!(plain.contains("|   a ") && plain.contains("|   +50.0% |"));
+    assert!(!plain.contains('\x1b'));
+
+    let colored = table(&candidate, &TableOptions { color: true, ..TableOptions::default() }, Some(&baseline));
+    let line = |name: &str| colored.lines().find(|line| line.contains(&format!("  {} ", name))).unwrap().to_string();
+    assert!(line("a").contains("\x1b[1m") && line("a").contains("\x1b[1;31m  +50.0%\x1b[0m"));
+    assert!(line("c").contains("\x1b[1;31m"));
+    assert!(line("d").contains("\x1b[31m  +20.0%\x1b[0m") && !line("d").contains("\x1b[1m"));
+    assert!(line("e").contains("\x1b[32m  -50.0%\x1b[0m"));
+}
@@ -0,0 +1,165 @@
+//! Deterministic tests of the timer logic, driven by a mock clock
+
+use mojo_regex_rust_bench::clock::MockClock;
+use mojo_regex_rust_bench::metric::Metric;
+use mojo_regex_rust_bench::BenchmarkTimer;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+#[test]
+fn constant_cost_is_measured_exactly() {
+    let clock = MockClock::new(0);
+    let timer = BenchmarkTimer::with_clock(&clock);
+    let result = timer.bench_function(|| clock.advance(2_000_000));
+
+    assert_eq!(result.time_ns, 2_000_000.0);
+    assert_eq!(result.cv, Some(0.0));
+    assert_eq!(result.reran, 0);
+    let summary = result.summary.unwrap();
+    assert_eq!((summary.min_ns, summary.max_ns), (2_000_000.0, 2_000_000.0));
+    // 500ms target at 2ms per sample
+    assert_eq!(result.outer_iterations, Some(250));
+}
+
+#[test]
+fn fast_operations_are_batched_to_the_minimum_sample_time() {
+    let clock = MockClock::new(0);
+    let calls = Cell::new(0u64);
+    let timer = BenchmarkTimer::with_clock(&clock);
+    let result = timer.bench_function(|| {
+        calls.set(calls.get() + 1);
+        clock.advance(100);
+    });
+
+    // 1ms minimum sample / 100 per call, plus one
+    let repetitions = 1_000_000 / 100 + 1;
+    assert_eq!(result.time_ns, 100.0);
+    assert_eq!(result.iterations, result.outer_iterations.unwrap() * repetitions);
+    // Warmup and calibration calls come on top of the measured ones
+    assert_eq!(calls.get(), 10 + 1 + result.iterations);
+}
+
+#[test]
+fn fixed_iterations_do_identical_work() {
+    let clock = MockClock::new(0);
+    let calls = Cell::new(0u64);
+    let timer = BenchmarkTimer::with_clock(&clock).with_fixed_iterations(7);
+    let result = timer.bench_function(|| {
+        calls.set(calls.get() + 1);
+        // Noisy enough to trigger re-runs, which fixed mode must not do
+        clock.advance(if calls.get().is_multiple_of(2) { 10 } else { 1_000 });
+    });
+
+    assert_eq!(result.outer_iterations, Some(7));
+    assert_eq!(result.iterations, 7);
+    assert_eq!(result.reran, 0);
+    assert_eq!(calls.get(), 10 + 7);
+}
+
+#[test]
+fn slow_operations_still_get_the_minimum_sample_count() {
+    let clock = MockClock::new(0);
+    let timer = BenchmarkTimer::with_clock(&clock).with_min_outer_iterations(12);
+    // A single call blows through the 500ms target
+    let result = timer.bench_function(|| clock.advance(2_000_000_000));
+
+    assert_eq!(result.outer_iterations, Some(12));
+}
+
+#[test]
+fn noisy_benchmarks_are_rerun() {
+    let clock = MockClock::new(0);
+    let calls = Cell::new(0u64);
+    let timer = BenchmarkTimer::with_clock(&clock).with_noise_rerun(0.05, 3);
+    let result = timer.bench_function(|| {
+        calls.set(calls.get() + 1);
+        clock.advance(if calls.get().is_multiple_of(2) { 1_000_000 } else { 5_000_000 });
+    });
+
+    assert_eq!(result.reran, 3);
+    assert!(result.cv.unwrap() > 0.05);
+}
+
+#[test]
+fn extra_rounds_report_a_stability_score() {
+    let clock = MockClock::new(0);
+    let timer = BenchmarkTimer::with_clock(&clock).with_rounds(3);
+    let result = timer.bench_function(|| clock.advance(2_000_000));
+
+    assert_eq!(result.round_medians_ns, Some(vec![2_000_000.0; 3]));
+    assert_eq!(result.stability_score, Some(1.0));
+    assert_eq!(result.reran, 0);
+}
+
+#[test]
+fn latency_includes_clock_overhead() {
+    let clock = MockClock::new(5);
+    let timer = BenchmarkTimer::with_clock(&clock).with_latency_samples(100);
+    let latency = timer.bench_latency(|| clock.advance(500));
+
+    assert_eq!(latency.samples, 100);
+    assert_eq!(latency.clock_overhead_ns, 5.0);
+    assert_eq!((latency.min_ns, latency.p50_ns, latency.max_ns), (505.0, 505.0, 505.0));
+}
+
+/// Units of work done by the benchmarked closure
+static WORK: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug)]
+struct WorkMetric;
+
+impl Metric for WorkMetric {
+    fn name(&self) -> &'static str {
+        "work"
+    }
+    fn unit(&self) -> &'static str {
+        "units"
+    }
+    fn start(&self) -> u64 {
+        WORK.load(Ordering::Relaxed)
+    }
+    fn stop(&self, start: u64) -> Option<f64> {
+        Some((WORK.load(Ordering::Relaxed) - start) as f64)
+    }
+}
+
+#[derive(Debug)]
+struct LevelMetric;
+
+impl Metric for LevelMetric {
+    fn name(&self) -> &'static str {
+        "level"
+    }
+    fn unit(&self) -> &'static str {
+        "bytes"
+    }
+    fn start(&self) -> u64 {
+        0
+    }
+    fn stop(&self, _start: u64) -> Option<f64> {
+        Some(4096.0)
+    }
+    fn per_operation(&self) -> bool {
+        false
+    }
+}
+
+#[test]
+fn attached_metrics_are_recorded_per_operation() {
+    let clock = MockClock::new(0);
+    let timer = BenchmarkTimer::with_clock(&clock).with_metrics(vec![Box::new(WorkMetric), Box::new(LevelMetric)]);
+    let result = timer
+        .bench_function(|| {
+            for _ in 0..10 {
+                WORK.fetch_add(3, Ordering::Relaxed);
+            }
+            clock.advance(2_000_000);
+        })
+        .per_op(10);
+
+    let metrics = result.metrics.unwrap();
+    assert_eq!(metrics["work"].value, 3.0);
+    assert!(metrics["work"].per_op);
+    assert_eq!(metrics["level"].value, 4096.0);
+    assert_eq!(metrics["level"].unit, "bytes");
+}
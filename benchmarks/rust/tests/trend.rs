@@ -0,0 +1,57 @@
+use mojo_regex_rust_bench::history::HistoryRecord;
+use mojo_regex_rust_bench::trend::{drift, from_history, sparkline};
+use mojo_regex_rust_bench::BenchmarkResult;
+
+mod common;
+
+fn record(engine: &str, day: u32, time_ns: f64) -> HistoryRecord {
+    let bench = BenchmarkResult {
+        time_ns,
+        iterations: 10,
+        ..Default::default()
+    };
+    let timestamp = format!("2026-01-{:02}T00:00:00+00:00", day);
+    HistoryRecord {
+        git_sha: Some(format!("sha{}", day)),
+        results: common::results(engine, &timestamp, vec![("literal", vec![("literal_match_short", bench)])]),
+    }
+}
+
+#[test]
+fn history_points_are_the_latest_runs_of_one_engine() {
+    let records = vec![
+        record("rust", 1, 100.0),
+        record("mojo", 2, 500.0),
+        record("rust", 8, 102.0),
+        record("rust", 15, 104.0),
+    ];
+
+    let points = from_history(&records, "literal_match_short", Some("rust"), 2);
+    assert_eq!(points.iter().map(|point| point.git_sha.as_str()).collect::<Vec<_>>(), ["sha8", "sha15"]);
+    assert!(from_history(&records, "missing", None, 10).is_empty());
+}
+
+#[test]
+fn weekly_creep_shows_as_drift() {
+    // 2 ns a week on ~102 ns: invisible run to run, about 2% per week
+    let records = vec![record("rust", 1, 100.0), record("rust", 8, 102.0), record("rust", 15, 104.0)];
+    let points = from_history(&records, "literal_match_short", None, 10);
+
+    let creep = drift(&points).unwrap();
+    assert!((creep.pct_per_run - 2.0 / 102.0 * 100.0).abs() < 1e-9);
+    assert!((creep.pct_per_week.unwrap() - 2.0 / 102.0 * 100.0).abs() < 1e-9);
+    assert_eq!(sparkline(&[100.0, 102.0, 104.0]), "▁▅█");
+    assert_eq!(sparkline(&[5.0, 5.0]), "▅▅");
+    assert_eq!(drift(&points[..1]), None);
+}
+
+#[test]
+fn back_to_back_runs_have_no_weekly_drift() {
+    let mut records = vec![record("rust", 1, 100.0), record("rust", 1, 110.0)];
+    records[1].results.timestamp = "2026-01-01T00:05:00+00:00".to_string();
+    let points = from_history(&records, "literal_match_short", None, 10);
+
+    let drift = drift(&points).unwrap();
+    assert!((drift.pct_per_run - 10.0 / 105.0 * 100.0).abs() < 1e-9);
+    assert_eq!(drift.pct_per_week, None);
+}